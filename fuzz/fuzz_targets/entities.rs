@@ -0,0 +1,19 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate musicbrainz;
+
+use musicbrainz::entities::*;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(xml) = ::std::str::from_utf8(data) {
+        let _ = Area::from_xml_str(xml);
+        let _ = Artist::from_xml_str(xml);
+        let _ = Event::from_xml_str(xml);
+        let _ = Label::from_xml_str(xml);
+        let _ = Place::from_xml_str(xml);
+        let _ = Recording::from_xml_str(xml);
+        let _ = Release::from_xml_str(xml);
+        let _ = ReleaseGroup::from_xml_str(xml);
+    }
+});
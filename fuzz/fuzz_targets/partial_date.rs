@@ -0,0 +1,14 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate musicbrainz;
+
+use musicbrainz::entities::PartialDate;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = ::std::str::from_utf8(data) {
+        if let Ok(date) = s.parse::<PartialDate>() {
+            let _ = date.to_string();
+        }
+    }
+});
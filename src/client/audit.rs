@@ -0,0 +1,142 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use client::Metrics;
+
+/// Records every request made by a client as a line of JSON, e. g. to keep
+/// track of the requests of a bulk job.
+///
+/// Each completed request is written as
+/// `{"timestamp_ms":…,"url":"…","outcome":"success","duration_ms":…}` with
+/// the outcome `"success"` or `"failure"`, each retry as
+/// `{"timestamp_ms":…,"url":"…","outcome":"retry","attempt":…}`. Timestamps
+/// are milliseconds since the Unix epoch.
+///
+/// This is a `Metrics` implementation, register it using
+/// `Client::set_metrics`. Errors writing the log are ignored so they don't
+/// interrupt the requests.
+pub struct AuditLog<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> AuditLog<W> {
+    /// Create an audit log writing to `writer`.
+    pub fn new(writer: W) -> Self
+    {
+        AuditLog { writer: Mutex::new(writer) }
+    }
+
+    /// Consumes the log, returning the writer.
+    pub fn into_inner(self) -> W
+    {
+        match self.writer.into_inner() {
+            Ok(writer) => writer,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn write_line(&self, line: String)
+    {
+        let mut writer = match self.writer.lock() {
+            Ok(writer) => writer,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let _ = writeln!(writer, "{}", line).and_then(|_| writer.flush());
+    }
+}
+
+impl AuditLog<File> {
+    /// Create an audit log appending to the file at `path`, creating it if it
+    /// doesn't exist.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self>
+    {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(AuditLog::new(file))
+    }
+}
+
+impl<W: Write + Send> Metrics for AuditLog<W> {
+    fn request(&self, url: &str, duration: Duration, success: bool)
+    {
+        self.write_line(format!(
+            "{{\"timestamp_ms\":{},\"url\":{},\"outcome\":\"{}\",\"duration_ms\":{}}}",
+            timestamp_ms(),
+            json_string(url),
+            if success { "success" } else { "failure" },
+            duration.as_secs() * 1000 + u64::from(duration.subsec_nanos() / 1_000_000)
+        ));
+    }
+
+    fn retry(&self, url: &str, attempt: u8)
+    {
+        self.write_line(format!(
+            "{{\"timestamp_ms\":{},\"url\":{},\"outcome\":\"retry\",\"attempt\":{}}}",
+            timestamp_ms(),
+            json_string(url),
+            attempt
+        ));
+    }
+}
+
+/// Milliseconds since the Unix epoch.
+fn timestamp_ms() -> u64
+{
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or(Duration::from_secs(0));
+    now.as_secs() * 1000 + u64::from(now.subsec_nanos() / 1_000_000)
+}
+
+/// Quotes and escapes the text as a JSON string.
+fn json_string(text: &str) -> String
+{
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_json_lines()
+    {
+        let log = AuditLog::new(Vec::new());
+        let url = "https://musicbrainz.org/ws/2/artist/650e7db6-b795-4eb5-a702-5ea2fc46c848";
+        log.retry(url, 1);
+        log.request(url, Duration::from_millis(1250), true);
+
+        let output = String::from_utf8(log.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("{\"timestamp_ms\":"));
+        assert!(lines[0].ends_with(&format!(
+            ",\"url\":\"{}\",\"outcome\":\"retry\",\"attempt\":1}}",
+            url
+        )));
+        assert!(lines[1].ends_with(&format!(
+            ",\"url\":\"{}\",\"outcome\":\"success\",\"duration_ms\":1250}}",
+            url
+        )));
+    }
+
+    #[test]
+    fn escape_json_strings()
+    {
+        assert_eq!(json_string("a\"b\\c\n\u{1}"), "\"a\\\"b\\\\c\\n\\u0001\"".to_string());
+    }
+}
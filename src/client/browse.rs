@@ -0,0 +1,273 @@
+//! Browsing the entities linked to another entity, e. g. all events which
+//! took place in an area.
+
+use xpath_reader::reader::XpathStrReader;
+
+use client::{check_response_error, Client};
+use entities::{Event, Mbid, PartialDate, Resource};
+use entities::helper::{read_attr_option, read_mb_vec};
+use errors::ClientError;
+
+/// The maximal number of entities the web service returns per page.
+const MAX_LIMIT: u8 = 100;
+
+/// One page of browsed entities.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrowsePage<E> {
+    /// The entities of this page.
+    pub entries: Vec<E>,
+
+    /// The total number of linked entities, not only of this page.
+    pub count: u32,
+
+    /// The offset of the first entity of this page in all linked entities.
+    pub offset: u32,
+}
+
+/// Selects the entity the browsed events are linked to, create instances
+/// using `Client::browse_events`.
+pub struct BrowseEvents<'cl> {
+    client: &'cl mut Client,
+}
+
+impl<'cl> BrowseEvents<'cl> {
+    pub(crate) fn new(client: &'cl mut Client) -> Self
+    {
+        BrowseEvents { client: client }
+    }
+
+    /// Browse the events which took place in the area, e. g. a city.
+    pub fn by_area(self, area: &Mbid) -> EventBrowseBuilder<'cl>
+    {
+        EventBrowseBuilder::new(self.client, "area", area)
+    }
+
+    /// Browse the events the artist performed at.
+    pub fn by_artist(self, artist: &Mbid) -> EventBrowseBuilder<'cl>
+    {
+        EventBrowseBuilder::new(self.client, "artist", artist)
+    }
+
+    /// Browse the events which took place at the place, e. g. a venue.
+    pub fn by_place(self, place: &Mbid) -> EventBrowseBuilder<'cl>
+    {
+        EventBrowseBuilder::new(self.client, "place", place)
+    }
+}
+
+/// Browses the events linked to an entity.
+///
+/// The web service can't filter events by their date, `between` filters the
+/// browsed events on the client side. So all events linked to the entity are
+/// requested, regardless of how many of them are in the range.
+pub struct EventBrowseBuilder<'cl> {
+    client: &'cl mut Client,
+    linked_entity: &'static str,
+    mbid: Mbid,
+    limit: u8,
+    offset: u32,
+    between: Option<(PartialDate, PartialDate)>,
+}
+
+impl<'cl> EventBrowseBuilder<'cl> {
+    fn new(client: &'cl mut Client, linked_entity: &'static str, mbid: &Mbid) -> Self
+    {
+        EventBrowseBuilder {
+            client: client,
+            linked_entity: linked_entity,
+            mbid: mbid.clone(),
+            limit: 25,
+            offset: 0,
+            between: None,
+        }
+    }
+
+    /// The number of events per page, at most 100. Defaults to 25 like the
+    /// web service.
+    pub fn limit(mut self, limit: u8) -> Self
+    {
+        self.limit = if limit > MAX_LIMIT { MAX_LIMIT } else { limit };
+        self
+    }
+
+    /// The offset of the first event to return, defaults to 0.
+    pub fn offset(mut self, offset: u32) -> Self
+    {
+        self.offset = offset;
+        self
+    }
+
+    /// Only return events which take place (at least partially) between
+    /// `from` and `to`, both inclusive, see `Event::overlaps`.
+    pub fn between(mut self, from: PartialDate, to: PartialDate) -> Self
+    {
+        self.between = Some((from, to));
+        self
+    }
+
+    /// Only return events which take place (at least partially) during the
+    /// period, e. g. a year.
+    pub fn during(self, period: PartialDate) -> Self
+    {
+        self.between(period.clone(), period)
+    }
+
+    /// Fetch the page of events at the offset.
+    ///
+    /// If a date range was specified the page contains only the matching
+    /// events, while `count` is the number of all linked events.
+    pub fn browse(self) -> Result<BrowsePage<Event>, ClientError>
+    {
+        let EventBrowseBuilder {
+            client,
+            linked_entity,
+            mbid,
+            limit,
+            offset,
+            between,
+        } = self;
+
+        let mut page = fetch_page(client, linked_entity, &mbid, limit, offset)?;
+        page.entries.retain(|event| in_range(event, &between));
+        Ok(page)
+    }
+
+    /// Fetch all events starting at the offset, performing as many requests
+    /// as required. The pages are requested with the maximal limit.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use musicbrainz::client::Client;
+    /// # use musicbrainz::entities::{Mbid, PartialDate};
+    /// # fn tokyo_2015(client: &mut Client, tokyo: &Mbid) {
+    /// let events = client
+    ///     .browse_events()
+    ///     .by_area(tokyo)
+    ///     .during(PartialDate::new(Some(2015), None, None))
+    ///     .browse_all()
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn browse_all(self) -> Result<Vec<Event>, ClientError>
+    {
+        let EventBrowseBuilder {
+            client,
+            linked_entity,
+            mbid,
+            limit: _,
+            mut offset,
+            between,
+        } = self;
+
+        let mut events = Vec::new();
+        loop {
+            let page = fetch_page(client, linked_entity, &mbid, MAX_LIMIT, offset)?;
+            if page.entries.is_empty() {
+                break;
+            }
+            offset += page.entries.len() as u32;
+            events.extend(page.entries.into_iter().filter(|e| in_range(e, &between)));
+            if offset >= page.count {
+                break;
+            }
+        }
+        Ok(events)
+    }
+}
+
+fn in_range(event: &Event, between: &Option<(PartialDate, PartialDate)>) -> bool
+{
+    match *between {
+        Some((ref from, ref to)) => event.overlaps(from, to),
+        None => true,
+    }
+}
+
+fn fetch_page(
+    client: &mut Client,
+    linked_entity: &str,
+    mbid: &Mbid,
+    limit: u8,
+    offset: u32,
+) -> Result<BrowsePage<Event>, ClientError>
+{
+    let url = client.endpoint().browse_url(
+        Event::get_name(),
+        linked_entity,
+        mbid,
+        limit,
+        offset,
+    );
+    client
+        .get_body(url.parse()?)
+        .and_then(|body| parse_page(&body))
+        .map_err(|e| e.with_request(url.clone(), Some(Event::get_name())))
+}
+
+/// Parse a page of the response of browsing events.
+fn parse_page(xml: &str) -> Result<BrowsePage<Event>, ClientError>
+{
+    let context = ::util::musicbrainz_context();
+    let reader = XpathStrReader::new(xml, &context)?;
+    check_response_error(&reader)?;
+
+    let entries: Vec<Event> = read_mb_vec(&reader, "//mb:metadata/mb:event-list/mb:event")?;
+    let count = read_attr_option(&reader, "//mb:metadata/mb:event-list", "count")?;
+    let offset = read_attr_option(&reader, "//mb:metadata/mb:event-list", "offset")?;
+    Ok(BrowsePage {
+        count: count.unwrap_or(entries.len() as u32),
+        offset: offset.unwrap_or(0),
+        entries: entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn parse_event_page()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><event-list count="27" offset="25"><event id="6e2ab7d5-f340-4c41-99a3-c901733402b4" type="Festival"><name>Countdown Japan 15/16</name><life-span><begin>2015-12-28</begin><end>2016-01-02</end></life-span></event><event id="9754f4dd-6fad-49b7-8f30-940c9af6b776" type="Concert"><name>Boris at Shibuya O-West</name><life-span><begin>2016-03-05</begin><end>2016-03-05</end></life-span></event></event-list></metadata>"#;
+        let page = parse_page(xml).unwrap();
+
+        assert_eq!(page.count, 27);
+        assert_eq!(page.offset, 25);
+        assert_eq!(page.entries.len(), 2);
+        assert_eq!(page.entries[0].name, "Countdown Japan 15/16".to_string());
+        assert_eq!(
+            page.entries[1].mbid,
+            Mbid::from_str("9754f4dd-6fad-49b7-8f30-940c9af6b776").unwrap()
+        );
+
+        let year = PartialDate::new(Some(2015), None, None);
+        let year = Some((year.clone(), year));
+        let in_2015: Vec<&str> = page.entries
+            .iter()
+            .filter(|e| in_range(e, &year))
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(in_2015, vec!["Countdown Japan 15/16"]);
+    }
+
+    #[test]
+    fn parse_undated_event()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><event-list count="2" offset="0"><event id="6e2ab7d5-f340-4c41-99a3-c901733402b4" type="Festival"><name>Countdown Japan 15/16</name><life-span><begin>2015-12-28</begin><end>2016-01-02</end></life-span></event><event id="9754f4dd-6fad-49b7-8f30-940c9af6b776" type="Concert"><name>Boris at Shibuya O-West</name></event></event-list></metadata>"#;
+        let page = parse_page(xml).unwrap();
+
+        assert_eq!(page.entries.len(), 2);
+        assert_eq!(page.entries[1].begin_date, None);
+        assert!(in_range(&page.entries[1], &None));
+
+        let year = PartialDate::new(Some(2015), None, None);
+        let year = Some((year.clone(), year));
+        let in_2015: Vec<&str> = page.entries
+            .iter()
+            .filter(|e| in_range(e, &year))
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(in_2015, vec!["Countdown Japan 15/16"]);
+    }
+}
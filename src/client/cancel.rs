@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A flag to stop long-running operations like `LookupQueue::process` or
+/// `Walk`, e. g. when the user pressed a cancel button.
+///
+/// The token can be cloned and sent to other threads, cancelling one of the
+/// clones cancels all of them. Operations check the token before every
+/// request, so they stop promptly and return the results gathered so far.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a new token which isn't cancelled yet.
+    pub fn new() -> Self
+    {
+        CancellationToken::default()
+    }
+
+    /// Cancel all operations using this token.
+    pub fn cancel(&self)
+    {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if `cancel` was called on this token or one of its
+    /// clones.
+    pub fn is_cancelled(&self) -> bool
+    {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Returns `true` if the optional token was cancelled.
+pub(crate) fn is_cancelled(token: &Option<CancellationToken>) -> bool
+{
+    token.as_ref().map(|t| t.is_cancelled()).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn cancel_clone()
+    {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+
+        let clone = token.clone();
+        thread::spawn(move || clone.cancel()).join().unwrap();
+        assert!(token.is_cancelled());
+
+        assert!(!is_cancelled(&None));
+        assert!(is_cancelled(&Some(token)));
+    }
+}
@@ -0,0 +1,299 @@
+//! Reading the contents of MusicBrainz collections and synchronizing them
+//! with a local library.
+
+use std::collections::HashSet;
+
+use reqwest_mock::Method;
+use xpath_reader::reader::XpathStrReader;
+
+use client::{check_response_error, BrowsePage, Client, Session, SubmissionResult};
+use entities::{Collectable, Mbid, Release};
+use entities::helper::{read_attr_option, read_mb_vec};
+use errors::ClientError;
+
+/// The maximal number of MBIDs added to or removed from a collection with one
+/// request.
+///
+/// The web service accepts about 400 MBIDs per request, with more the url
+/// gets too long.
+pub const MAX_COLLECTION_BATCH: usize = 400;
+
+/// The number of entities per page when fetching the contents of a
+/// collection.
+const PAGE_LIMIT: u8 = 100;
+
+/// The changes making a collection contain exactly the releases of a local
+/// library.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CollectionDelta {
+    /// The releases missing in the collection.
+    pub add: Vec<Mbid>,
+
+    /// The releases in the collection which aren't in the library.
+    pub remove: Vec<Mbid>,
+}
+
+impl CollectionDelta {
+    /// Compute the delta between the `local` releases and the `remote` ones
+    /// in the collection.
+    ///
+    /// Duplicates are ignored, otherwise the releases keep their order.
+    pub fn between(local: &[Mbid], remote: &[Mbid]) -> Self
+    {
+        CollectionDelta {
+            add: difference(local, remote),
+            remove: difference(remote, local),
+        }
+    }
+
+    /// Returns `true` if the collection is already in sync.
+    pub fn is_empty(&self) -> bool
+    {
+        self.add.is_empty() && self.remove.is_empty()
+    }
+
+    /// The number of requests applying the delta takes.
+    pub fn requests(&self) -> usize
+    {
+        batches(self.add.len()) + batches(self.remove.len())
+    }
+}
+
+/// The MBIDs of `a` which aren't in `b`, without duplicates.
+fn difference(a: &[Mbid], b: &[Mbid]) -> Vec<Mbid>
+{
+    let mut seen: HashSet<&Mbid> = b.iter().collect();
+    a.iter().filter(|mbid| seen.insert(*mbid)).cloned().collect()
+}
+
+fn batches(count: usize) -> usize
+{
+    (count + MAX_COLLECTION_BATCH - 1) / MAX_COLLECTION_BATCH
+}
+
+/// The outcome of synchronizing a collection with `CollectionSync::sync`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SyncReport {
+    /// The changes which were (or in a dry run would have been) applied.
+    pub delta: CollectionDelta,
+
+    /// The results of the submissions, first the ones adding releases, then
+    /// the ones removing them. Empty for a dry run.
+    pub results: Vec<SubmissionResult>,
+
+    /// Whether this was a dry run, which didn't change the collection.
+    pub dry_run: bool,
+}
+
+impl SyncReport {
+    /// Returns `true` if the server accepted all submissions.
+    pub fn is_accepted(&self) -> bool
+    {
+        self.results.iter().all(SubmissionResult::is_accepted)
+    }
+}
+
+/// Synchronizes the releases of a collection with a local library, create
+/// instances using `Session::sync_collection`.
+///
+/// # Examples
+/// ```no_run
+/// # use musicbrainz::client::Session;
+/// # use musicbrainz::entities::Mbid;
+/// # fn sync(session: &mut Session, collection: &Mbid, library: &[Mbid]) {
+/// let report = session.sync_collection(collection).dry_run().sync(library).unwrap();
+/// println!(
+///     "Would add {} and remove {} releases.",
+///     report.delta.add.len(),
+///     report.delta.remove.len()
+/// );
+/// # }
+/// ```
+pub struct CollectionSync<'s, 'cl: 's> {
+    session: &'s mut Session<'cl>,
+    collection: Mbid,
+    dry_run: bool,
+}
+
+impl<'s, 'cl> CollectionSync<'s, 'cl> {
+    pub(crate) fn new(session: &'s mut Session<'cl>, collection: &Mbid) -> Self
+    {
+        CollectionSync {
+            session: session,
+            collection: collection.clone(),
+            dry_run: false,
+        }
+    }
+
+    /// Only compute the delta without changing the collection.
+    pub fn dry_run(mut self) -> Self
+    {
+        self.dry_run = true;
+        self
+    }
+
+    /// Make the collection contain exactly the `releases`.
+    ///
+    /// The current contents of the collection are fetched first, then the
+    /// missing releases are added and the others removed, in batches of
+    /// `MAX_COLLECTION_BATCH` releases. Rejected batches don't stop the
+    /// synchronization, they are reported in the `SyncReport`.
+    pub fn sync(self, releases: &[Mbid]) -> Result<SyncReport, ClientError>
+    {
+        let CollectionSync {
+            session,
+            collection,
+            dry_run,
+        } = self;
+
+        let remote = fetch_releases(session, &collection)?;
+        let delta = CollectionDelta::between(releases, &remote);
+
+        let mut results = Vec::new();
+        if !dry_run {
+            let endpoint = session.endpoint();
+            let changes = [(Method::Put, &delta.add), (Method::Delete, &delta.remove)];
+            for &(ref method, mbids) in changes.iter() {
+                for batch in mbids.chunks(MAX_COLLECTION_BATCH) {
+                    let url = endpoint.collection_url(&collection, "releases", batch);
+                    results.push(session.submit(method.clone(), &url)?);
+                }
+            }
+        }
+
+        Ok(SyncReport {
+            delta: delta,
+            results: results,
+            dry_run: dry_run,
+        })
+    }
+}
+
+/// Fetch the MBIDs of all releases in the collection.
+fn fetch_releases(session: &mut Session, collection: &Mbid) -> Result<Vec<Mbid>, ClientError>
+{
+    let releases = session
+        .authorized(|client| fetch_collection_contents::<Release>(client, collection))?;
+    Ok(releases.into_iter().map(|release| release.mbid).collect())
+}
+
+/// Fetch all entities in the collection, performing as many requests as
+/// required.
+///
+/// The collection must hold entities of type `Res`, otherwise it appears to
+/// be empty.
+pub(crate) fn fetch_collection_contents<Res>(
+    client: &mut Client,
+    collection: &Mbid,
+) -> Result<Vec<Res::Ref>, ClientError>
+where
+    Res: Collectable,
+{
+    let endpoint = client.endpoint();
+    let mut entities = Vec::new();
+    loop {
+        let url = endpoint.browse_url(
+            Res::get_name(),
+            "collection",
+            collection,
+            PAGE_LIMIT,
+            entities.len() as u32,
+        );
+        let page = client
+            .get_body(url.parse()?)
+            .and_then(|body| parse_page::<Res>(&body))
+            .map_err(|e| e.with_request(url.clone(), Some(Res::get_name())))?;
+
+        if page.entries.is_empty() {
+            break;
+        }
+        entities.extend(page.entries);
+        if entities.len() as u32 >= page.count {
+            break;
+        }
+    }
+    Ok(entities)
+}
+
+/// Parse a page of the entities in a collection.
+fn parse_page<Res>(xml: &str) -> Result<BrowsePage<Res::Ref>, ClientError>
+where
+    Res: Collectable,
+{
+    let context = ::util::musicbrainz_context();
+    let reader = XpathStrReader::new(xml, &context)?;
+    check_response_error(&reader)?;
+
+    let list = format!("//mb:metadata/mb:{}-list", Res::get_name());
+    let entries: Vec<Res::Ref> =
+        read_mb_vec(&reader, &format!("{}/mb:{}", list, Res::get_name()))?;
+    let count = read_attr_option(&reader, &list, "count")?;
+    let offset = read_attr_option(&reader, &list, "offset")?;
+    Ok(BrowsePage {
+        count: count.unwrap_or(entries.len() as u32),
+        offset: offset.unwrap_or(0),
+        entries: entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::Artist;
+
+    fn mbids(count: usize) -> Vec<Mbid>
+    {
+        (0..count)
+            .map(|i| format!("00000000-0000-0000-0000-{:012}", i).parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn delta()
+    {
+        let all = mbids(4);
+        let local = vec![all[0].clone(), all[1].clone(), all[1].clone(), all[2].clone()];
+        let remote = vec![all[3].clone(), all[2].clone(), all[0].clone(), all[3].clone()];
+
+        let delta = CollectionDelta::between(&local, &remote);
+        assert_eq!(delta.add, vec![all[1].clone()]);
+        assert_eq!(delta.remove, vec![all[3].clone()]);
+        assert_eq!(delta.requests(), 2);
+        assert!(CollectionDelta::between(&local, &local).is_empty());
+    }
+
+    #[test]
+    fn batched_requests()
+    {
+        let delta = CollectionDelta::between(&mbids(801), &[]);
+        assert_eq!(delta.add.len(), 801);
+        assert_eq!(delta.requests(), 3);
+        assert_eq!(CollectionDelta::between(&[], &mbids(400)).requests(), 1);
+        assert_eq!(CollectionDelta::default().requests(), 0);
+    }
+
+    #[test]
+    fn parse_contents_page()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release-list count="102" offset="100"><release id="b84ee12a-09ef-421b-82de-0441a926375b"><title>OK Computer</title></release><release id="0b6b4ba0-d36f-47bd-b4ea-6a5b91842d29"><title>OK Computer</title></release></release-list></metadata>"#;
+        let page = parse_page::<Release>(xml).unwrap();
+
+        assert_eq!(page.count, 102);
+        assert_eq!(page.offset, 100);
+        let mbids: Vec<String> = page.entries.iter().map(|r| r.mbid.to_string()).collect();
+        assert_eq!(
+            mbids,
+            vec![
+                "b84ee12a-09ef-421b-82de-0441a926375b".to_string(),
+                "0b6b4ba0-d36f-47bd-b4ea-6a5b91842d29".to_string(),
+            ]
+        );
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><artist-list count="1"><artist id="a74b1b7f-71a5-4011-9441-d0b5e4122711" type="Group"><name>Radiohead</name><sort-name>Radiohead</sort-name></artist></artist-list></metadata>"#;
+        let page = parse_page::<Artist>(xml).unwrap();
+
+        assert_eq!(page.count, 1);
+        assert_eq!(page.offset, 0);
+        assert_eq!(page.entries[0].name, "Radiohead".to_string());
+    }
+}
@@ -22,10 +22,40 @@ where
     }
 }
 
+/// The maximal number of characters of a response included in an
+/// `UnexpectedContent` error.
+const SNIPPET_LENGTH: usize = 200;
+
+/// Checks that a response body (with the optionally provided content type)
+/// looks like an XML document before handing it to the XML parser.
+///
+/// When the server or a proxy in between returns an HTML page (for example
+/// during maintenance) the XML parser would fail with a rather cryptic error,
+/// instead an `UnexpectedContent` error containing the beginning of the body
+/// is returned.
+pub fn check_response_content(content_type: Option<&str>, body: &str) -> Result<(), ClientError>
+{
+    let start = body.trim_left_matches('\u{feff}').trim_left();
+    let start_lower = start.chars().take(20).collect::<String>().to_lowercase();
+
+    let html_type = content_type.map(|t| t.to_lowercase().contains("html")).unwrap_or(false);
+    let html_body = start_lower.starts_with("<!doctype html") || start_lower.starts_with("<html");
+
+    if start.starts_with('<') && !html_type && !html_body {
+        Ok(())
+    } else {
+        Err(
+            ClientErrorKind::UnexpectedContent {
+                content_type: content_type.map(|t| t.to_string()),
+                snippet: start.chars().take(SNIPPET_LENGTH).collect(),
+            }.into(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use error_chain::ChainedError;
     use xpath_reader::XpathStrReader;
 
     const XML_ERR: &'static str = r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -45,16 +75,45 @@ mod tests {
         let res = check_response_error(&reader);
         let err = res.err().unwrap();
 
-        assert_eq!(err.description(), "MusicBrainz server error");
+        match *err.kind() {
+            ClientErrorKind::MusicbrainzServerError(_) => {}
+            ref kind => panic!("Unexpected error kind: {:?}", kind),
+        }
+        assert_eq!(err.to_string(), "MusicBrainz server error: Your requests are exceeding the allowable rate limit. Please see http://wiki.musicbrainz.org/XMLWebService for more information.\nFor usage, please see: http://musicbrainz.org/development/mmd".to_string());
+    }
 
-        // If backtraces are enabled `error-chain` will appends some backtrace
-        // information to the end of the display string.
-        if ::std::env::var("RUST_BACKTRACE").unwrap_or_else(|_| "".to_string()).as_str() == "1" {
-            assert!(err.display().to_string().starts_with("Error: MusicBrainz server error: Your requests are exceeding the allowable rate limit. Please see http://wiki.musicbrainz.org/XMLWebService for more information.\nFor usage, please see: http://musicbrainz.org/development/mmd\n"));
+    #[test]
+    fn content_xml()
+    {
+        check_response_content(Some("application/xml; charset=utf-8"), XML_OK).unwrap();
+        check_response_content(None, XML_ERR).unwrap();
+    }
 
-        } else {
-            assert_eq!(err.display().to_string(), "Error: MusicBrainz server error: Your requests are exceeding the allowable rate limit. Please see http://wiki.musicbrainz.org/XMLWebService for more information.\nFor usage, please see: http://musicbrainz.org/development/mmd\n".to_string());
+    #[test]
+    fn content_html()
+    {
+        let html = "<!DOCTYPE html>\n<html><body>Down for maintenance</body></html>";
+        match *check_response_content(None, html).err().unwrap().kind() {
+            ClientErrorKind::UnexpectedContent {
+                ref content_type,
+                ref snippet,
+            } => {
+                assert_eq!(*content_type, None);
+                assert_eq!(snippet.as_str(), html);
+            }
+            ref kind => panic!("Unexpected error kind: {:?}", kind),
         }
+
+        let res = check_response_content(Some("text/html; charset=utf-8"), "<p>Hello</p>");
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn content_not_markup()
+    {
+        let body = "Service temporarily unavailable";
+        assert!(check_response_content(Some("text/plain"), body).is_err());
+        assert!(check_response_content(None, "").is_err());
     }
 
     #[test]
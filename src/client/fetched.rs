@@ -0,0 +1,123 @@
+use std::ops::Deref;
+use std::time::{Duration, SystemTime};
+
+use entities::Include;
+
+/// An entity together with information about how and when it was fetched.
+///
+/// This is returned by `Client::get_fetched` and
+/// `Client::get_fetched_with_includes`, e. g. for caches which need to know
+/// how old an entry is and which optional data of the entity is populated.
+///
+/// The wrapper dereferences to the entity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fetched<T> {
+    /// The fetched entity.
+    pub entity: T,
+
+    /// The moment the response was received.
+    pub fetched_at: SystemTime,
+
+    /// The url the entity was fetched from.
+    pub url: String,
+
+    /// The includes which were requested, data belonging to other includes
+    /// is missing from the entity.
+    pub includes: Vec<Include>,
+
+    /// The privileged includes which were requested too, but dropped because
+    /// the server required authentication for them, see
+    /// `Client::set_drop_denied_includes`.
+    pub denied_includes: Vec<Include>,
+}
+
+impl<T> Fetched<T> {
+    /// The time which passed since the entity was fetched.
+    ///
+    /// Returns zero if the system clock was changed to an earlier time in
+    /// the meantime.
+    pub fn age(&self) -> Duration
+    {
+        SystemTime::now()
+            .duration_since(self.fetched_at)
+            .unwrap_or(Duration::from_secs(0))
+    }
+
+    /// Returns `true` if the entity was fetched more than `max_age` ago.
+    pub fn is_older_than(&self, max_age: Duration) -> bool
+    {
+        self.age() > max_age
+    }
+
+    /// Returns `true` if the data of the include was requested.
+    pub fn has_include(&self, include: Include) -> bool
+    {
+        self.includes.contains(&include)
+    }
+
+    /// Returns `true` if the data of the include is unavailable because the
+    /// server denied the include.
+    pub fn is_denied(&self, include: Include) -> bool
+    {
+        self.denied_includes.contains(&include)
+    }
+
+    /// Consumes the wrapper, returning the entity.
+    pub fn into_inner(self) -> T
+    {
+        self.entity
+    }
+
+    /// Convert the entity while keeping the metadata, e. g. to store only a
+    /// part of it.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Fetched<U>
+    {
+        Fetched {
+            entity: f(self.entity),
+            fetched_at: self.fetched_at,
+            url: self.url,
+            includes: self.includes,
+            denied_includes: self.denied_includes,
+        }
+    }
+}
+
+impl<T> Deref for Fetched<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T
+    {
+        &self.entity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fetched_metadata()
+    {
+        let fetched = Fetched {
+            entity: "Radiohead".to_string(),
+            fetched_at: SystemTime::now() - Duration::from_secs(120),
+            url: "https://musicbrainz.org/ws/2/artist/a74b1b7f-71a5-4011-9441-d0b5e4122711"
+                .to_string(),
+            includes: vec![Include::Aliases],
+            denied_includes: vec![Include::UserTags],
+        };
+
+        assert!(fetched.age() >= Duration::from_secs(120));
+        assert!(fetched.is_older_than(Duration::from_secs(60)));
+        assert!(!fetched.is_older_than(Duration::from_secs(3600)));
+        assert!(fetched.has_include(Include::Aliases));
+        assert!(!fetched.has_include(Include::Tags));
+        assert!(fetched.is_denied(Include::UserTags));
+        assert!(!fetched.is_denied(Include::Aliases));
+        assert_eq!(fetched.len(), 9);
+
+        let length = fetched.map(|name| name.len());
+        assert_eq!(length.entity, 9);
+        assert_eq!(length.includes, vec![Include::Aliases]);
+    }
+}
@@ -0,0 +1,73 @@
+//! Fetching the list of all genres.
+
+use xpath_reader::reader::XpathStrReader;
+
+use client::{check_response_error, BrowsePage, Client};
+use entities::Genre;
+use entities::helper::{read_attr_option, read_mb_vec};
+use errors::ClientError;
+
+/// The number of genres per page, the maximal limit of the web service.
+const PAGE_LIMIT: u8 = 100;
+
+/// How long the genre list is cached by the client, in seconds.
+///
+/// New genres are added rarely, so one day is plenty.
+pub const GENRE_CACHE_SECS: u64 = 24 * 60 * 60;
+
+/// Fetch all genres known to MusicBrainz, performing as many requests as
+/// required.
+pub(crate) fn fetch_genres(client: &mut Client) -> Result<Vec<Genre>, ClientError>
+{
+    let endpoint = client.endpoint();
+    let mut genres = Vec::new();
+    loop {
+        let url = endpoint.all_url("genre", PAGE_LIMIT, genres.len() as u32);
+        let page = client
+            .get_body(url.parse()?)
+            .and_then(|body| parse_page(&body))
+            .map_err(|e| e.with_request(url.clone(), Some("genre")))?;
+
+        if page.entries.is_empty() {
+            break;
+        }
+        genres.extend(page.entries);
+        if genres.len() as u32 >= page.count {
+            break;
+        }
+    }
+    Ok(genres)
+}
+
+/// Parse a page of the genre list.
+fn parse_page(xml: &str) -> Result<BrowsePage<Genre>, ClientError>
+{
+    let context = ::util::musicbrainz_context();
+    let reader = XpathStrReader::new(xml, &context)?;
+    check_response_error(&reader)?;
+
+    let entries: Vec<Genre> = read_mb_vec(&reader, "//mb:metadata/mb:genre-list/mb:genre")?;
+    let count = read_attr_option(&reader, "//mb:metadata/mb:genre-list", "count")?;
+    let offset = read_attr_option(&reader, "//mb:metadata/mb:genre-list", "offset")?;
+    Ok(BrowsePage {
+        count: count.unwrap_or(entries.len() as u32),
+        offset: offset.unwrap_or(0),
+        entries: entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_genre_page()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><genre-list count="1187" offset="100"><genre id="b2d3ad75-3e19-4f1b-8b5d-b9bbd9b70a7d"><name>shoegaze</name></genre></genre-list></metadata>"#;
+        let page = parse_page(xml).unwrap();
+
+        assert_eq!(page.count, 1187);
+        assert_eq!(page.offset, 100);
+        assert_eq!(page.entries[0].name, "shoegaze".to_string());
+    }
+}
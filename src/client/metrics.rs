@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+/// Receives measurements of the client, e. g. to export them to a monitoring
+/// system like Prometheus.
+///
+/// All methods have empty default implementations, so only the interesting
+/// ones have to be implemented. Register an implementation using
+/// `Client::set_metrics`.
+pub trait Metrics: Send {
+    /// A HTTP request to `url` was completed, `success` is `false` if it
+    /// failed for any reason, including the server being unavailable.
+    ///
+    /// The entity type can be told from the first path segment after the base
+    /// url, e. g. `artist` or `release`.
+    fn request(&self, url: &str, duration: Duration, success: bool)
+    {
+        let _ = (url, duration, success);
+    }
+
+    /// The request to `url` is retried as decided by the `RetryPolicy`,
+    /// `attempt` is the number of the failed attempt starting at `1`.
+    fn retry(&self, url: &str, attempt: u8)
+    {
+        let _ = (url, attempt);
+    }
+
+    /// A response was parsed into an entity of the type `entity` (e. g.
+    /// `"artist"`), taking `duration`.
+    fn parse(&self, entity: &'static str, duration: Duration)
+    {
+        let _ = (entity, duration);
+    }
+}
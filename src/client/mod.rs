@@ -1,26 +1,93 @@
 //! Contains the types and functions to communicate with the MusicBrainz API.
 
+use endpoint::Endpoint;
 use errors::{ClientError, ClientErrorKind};
-use entities::{Mbid, Resource};
+use entities::{barcode_variants, match_barcodes, top_tags, Area, AreaRef, AreaType, Artist,
+               BarcodeMatch, Genre, Include, Mbid, Rating, Relation, Release, ReleaseRef,
+               Resource};
+use entities::helper::read_mb_vec;
+use entities::relationships::RELATION_INCS;
 
 use reqwest_mock::Client as MockClient;
 use reqwest_mock::GenericClient as HttpClient;
-use reqwest_mock::{StatusCode, Url};
-use reqwest_mock::header::UserAgent;
+use reqwest_mock::{Method, StatusCode, Url};
+use reqwest_mock::header::{ContentLength, ContentType, Headers, UserAgent};
+use xpath_reader::{FromXml, XpathReader};
 use xpath_reader::reader::{FromXmlContained, XpathStrReader};
 
-use std::time::{Duration, Instant};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::thread::sleep;
 
-use search::{AreaSearchBuilder, ArtistSearchBuilder, ReleaseGroupSearchBuilder, SearchBuilder};
+use search::query::{escape_lucene, search_url};
+use search::{AreaSearchBuilder, ArtistSearchBuilder, LabelSearchBuilder, RecordingSearchBuilder,
+             ReleaseGroupSearchBuilder, ReleaseSearchBuilder, SearchBuilder, SearchEntry,
+             WorkSearchBuilder};
 
 mod error;
-pub(crate) use self::error::check_response_error;
+pub(crate) use self::error::{check_response_content, check_response_error};
 
-/// Helper extracting the number of milliseconds from a `Duration`.
-fn as_millis(duration: &Duration) -> u64
+mod browse;
+pub use self::browse::{BrowseEvents, BrowsePage, EventBrowseBuilder};
+
+mod queue;
+pub use self::queue::LookupQueue;
+
+mod progress;
+pub use self::progress::Progress;
+
+mod cancel;
+pub use self::cancel::CancellationToken;
+
+mod fetched;
+pub use self::fetched::Fetched;
+
+mod metrics;
+pub use self::metrics::Metrics;
+
+mod audit;
+pub use self::audit::AuditLog;
+
+mod rate_limit;
+pub use self::rate_limit::RateLimitStatus;
+
+mod retry;
+pub use self::retry::{ExponentialBackoff, FailureKind, NoRetry, RetryPolicy};
+
+mod submission;
+pub use self::submission::SubmissionResult;
+
+mod session;
+pub use self::session::{AccessToken, Session, TokenRefresher};
+
+mod collection;
+pub(crate) use self::collection::fetch_collection_contents;
+pub use self::collection::{CollectionDelta, CollectionSync, SyncReport, MAX_COLLECTION_BATCH};
+
+mod genres;
+pub use self::genres::GENRE_CACHE_SECS;
+
+mod walk;
+pub use self::walk::Walk;
+
+mod plan;
+pub use self::plan::{RequestEstimate, RequestPlan};
+
+/// Returns a pseudo random number of milliseconds between `0` and `max`.
+///
+/// This doesn't need to be a good random number, it is only used to spread
+/// out requests, so the sub-second part of the current time is sufficient.
+fn random_jitter(max: u64) -> u64
 {
-    ((duration.as_secs() as f64) + (duration.subsec_nanos() as f64) * 1e6) as u64
+    if max == 0 {
+        return 0;
+    }
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    nanos as u64 % (max + 1)
 }
 
 /// Returns an `Instant` at least 1000 seconds ago.
@@ -29,6 +96,14 @@ fn past_instant() -> Instant
     Instant::now() - Duration::new(1000, 0)
 }
 
+pub use util::DEFAULT_BASE_URL;
+
+/// The maximal size of responses used by the `ClientConfig` presets, 32 MiB.
+///
+/// The largest regular responses, browse pages of 100 releases with all of
+/// their mediums, are only a few MiB.
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 32 * 1024 * 1024;
+
 /// Configuration for the client.
 #[derive(Clone, Debug)]
 pub struct ClientConfig {
@@ -48,10 +123,84 @@ pub struct ClientConfig {
 
     /// How many times to retry requests where MusicBrainz returned 503 because
     /// too many requests were being made.
+    ///
+    /// Only used if no other policy was set using `Client::set_retry_policy`.
     pub max_retries: u8,
 
     /// Specifies amounts of time to wait between certain actions.
     pub waits: ClientWaits,
+
+    /// The url of the web service to use instead of `DEFAULT_BASE_URL`, e. g.
+    /// `"https://test.musicbrainz.org/ws/2"` or the url of a local mirror.
+    pub base_url: Option<String>,
+
+    /// The value of the `Accept-Language` header to send with every request,
+    /// e. g. `"ja, en;q=0.5"`.
+    ///
+    /// Note that the web service always returns all aliases regardless of this
+    /// header, use methods like `Artist::localized_name` to pick the name to
+    /// display in a locale.
+    pub accept_language: Option<String>,
+
+    /// The maximal size of a response body in bytes, `None` for no limit.
+    ///
+    /// Larger responses, e. g. from a misbehaving proxy, fail with a
+    /// `ResponseTooLarge` error instead of being decoded and parsed. The size
+    /// announced by the server (`Content-Length`) is checked first, then the
+    /// actual size.
+    ///
+    /// This only validates responses after they were received: the HTTP
+    /// client reads the whole body into memory before it can be checked, so
+    /// the limit doesn't protect against the memory used by large responses.
+    pub max_response_size: Option<usize>,
+}
+
+impl ClientConfig {
+    /// The configuration for the public server at musicbrainz.org, waiting a
+    /// second between requests and backing off when it is busy.
+    pub fn public(user_agent: &str) -> Self
+    {
+        ClientConfig {
+            user_agent: user_agent.to_string(),
+            max_retries: 5,
+            waits: ClientWaits::default(),
+            base_url: None,
+            accept_language: None,
+            max_response_size: Some(DEFAULT_MAX_RESPONSE_SIZE),
+        }
+    }
+
+    /// The configuration for a mirror of the web service at `base_url` (e. g.
+    /// `"http://localhost:5000/ws/2"`) which isn't rate limited.
+    ///
+    /// Requests are made without waiting in between and are retried more often
+    /// and after shorter waits. Never use this for the public server.
+    pub fn mirror(user_agent: &str, base_url: &str) -> Self
+    {
+        ClientConfig {
+            user_agent: user_agent.to_string(),
+            max_retries: 10,
+            waits: ClientWaits {
+                backoff_init: 100,
+                requests: 0,
+                jitter: 0,
+                max_reset_wait: 60_000,
+            },
+            base_url: Some(base_url.to_string()),
+            accept_language: None,
+            max_response_size: Some(DEFAULT_MAX_RESPONSE_SIZE),
+        }
+    }
+
+    /// The configuration for the test server at test.musicbrainz.org, which
+    /// has the same rate limit as the public server.
+    pub fn test_server(user_agent: &str) -> Self
+    {
+        ClientConfig {
+            base_url: Some("https://test.musicbrainz.org/ws/2".to_string()),
+            ..ClientConfig::public(user_agent)
+        }
+    }
 }
 
 /// Specification of the wait time between requests.
@@ -67,6 +216,17 @@ pub struct ClientWaits {
     //       to make abuse of the main servers harder.
     /// Minimal time between requests
     requests: u64,
+
+    /// Maximal random time added to the wait between requests, `0` disables
+    /// jitter.
+    ///
+    /// When multiple processes share the same rate limit (e. g. behind one IP
+    /// address) this prevents them from sending their requests in lockstep.
+    pub jitter: u64,
+
+    /// Maximal time to wait for the end of the rate limit window once the
+    /// server reported that no requests remain in it (`X-RateLimit-Reset`).
+    pub max_reset_wait: u64,
 }
 
 impl Default for ClientWaits {
@@ -75,6 +235,8 @@ impl Default for ClientWaits {
         ClientWaits {
             backoff_init: 400,
             requests: 1000,
+            jitter: 0,
+            max_reset_wait: 60_000,
         }
     }
 }
@@ -93,6 +255,31 @@ pub struct Client {
     /// between any two requests
     /// to the MusicBrainz API.
     last_request: Instant,
+
+    /// The rate limit state reported by the server in the last response
+    /// containing the corresponding headers.
+    rate_limit: Option<RateLimitStatus>,
+
+    /// Receiver of measurements, if any.
+    metrics: Option<Box<Metrics>>,
+
+    /// The policy deciding about retries, if not the one of the config.
+    retry_policy: Option<Box<RetryPolicy>>,
+
+    /// Whether to repeat lookups the server denied without the privileged
+    /// includes.
+    drop_denied_includes: bool,
+
+    /// The value of the `Authorization` header while a `Session` performs
+    /// requests.
+    authorization: Option<String>,
+
+    /// The includes to request by default, by the name of the entity type,
+    /// for types whose defaults were changed.
+    default_includes: HashMap<&'static str, Vec<Include>>,
+
+    /// The cached genre list and when it was fetched.
+    genres: Option<(Instant, Vec<Genre>)>,
 }
 
 impl Client {
@@ -103,6 +290,13 @@ impl Client {
             config: config,
             http_client: HttpClient::direct(),
             last_request: past_instant(),
+            rate_limit: None,
+            metrics: None,
+            retry_policy: None,
+            drop_denied_includes: false,
+            authorization: None,
+            default_includes: HashMap::new(),
+            genres: None,
         }
     }
 
@@ -117,64 +311,615 @@ impl Client {
             config: config,
             http_client: client,
             last_request: past_instant(),
+            rate_limit: None,
+            metrics: None,
+            retry_policy: None,
+            drop_denied_includes: false,
+            authorization: None,
+            default_includes: HashMap::new(),
+            genres: None,
         }
     }
 }
 
 impl Client {
+    /// Report measurements of requests and parsing to `metrics`.
+    pub fn set_metrics(&mut self, metrics: Box<Metrics>)
+    {
+        self.metrics = Some(metrics);
+    }
+
+    /// Decide about retries of failed requests using `policy` instead of the
+    /// `ExponentialBackoff` described by the config.
+    pub fn set_retry_policy(&mut self, policy: Box<RetryPolicy>)
+    {
+        self.retry_policy = Some(policy);
+    }
+
+    /// Request the includes instead of the ones of `Resource::get_incs`
+    /// whenever an entity of the type is fetched without specifying includes,
+    /// e. g. by `get_by_mbid`.
+    ///
+    /// Methods which take includes, like `get_by_mbid_with_includes`, still
+    /// request exactly the specified ones. Combinations of includes the web
+    /// service would reject result in an error and leave the defaults
+    /// unchanged.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use musicbrainz::client::Client;
+    /// # use musicbrainz::entities::{Artist, Include};
+    /// # fn configure(client: &mut Client) {
+    /// client.set_default_includes::<Artist>(&[Include::Aliases, Include::Tags]).unwrap();
+    /// # }
+    /// ```
+    pub fn set_default_includes<Res>(&mut self, includes: &[Include]) -> Result<(), ClientError>
+    where
+        Res: Resource,
+    {
+        Res::check_includes(includes)?;
+        self.default_includes.insert(Res::get_name(), includes.to_vec());
+        Ok(())
+    }
+
+    /// Request the includes of `Resource::get_incs` by default again.
+    pub fn reset_default_includes<Res>(&mut self)
+    where
+        Res: Resource,
+    {
+        self.default_includes.remove(Res::get_name());
+    }
+
+    /// The includes requested when fetching an entity of the type without
+    /// specifying includes.
+    pub fn default_includes<Res>(&self) -> Vec<Include>
+    where
+        Res: Resource,
+    {
+        match self.default_includes.get(Res::get_name()) {
+            Some(includes) => includes.clone(),
+            None => Include::split(Res::get_incs()),
+        }
+    }
+
+    /// The url to fetch the entity with the default includes at.
+    fn default_url<Res>(&self, mbid: &Mbid) -> String
+    where
+        Res: Resource,
+    {
+        match self.default_includes.get(Res::get_name()) {
+            Some(includes) => Res::get_url_with_includes_at(self.base_url(), mbid, includes),
+            None => Res::get_url_at(self.base_url(), mbid),
+        }
+    }
+
+    /// Whether to repeat lookups which failed because the server requires
+    /// authentication for privileged includes (like `Include::UserTags`)
+    /// without these includes, instead of failing.
+    ///
+    /// The data of the dropped includes is missing from the entity like the
+    /// data of includes which weren't requested. `get_fetched` and
+    /// `get_fetched_with_includes` report them in `Fetched::denied_includes`.
+    /// Disabled by default.
+    pub fn set_drop_denied_includes(&mut self, drop: bool)
+    {
+        self.drop_denied_includes = drop;
+    }
+
+    /// Start a session performing requests on behalf of the user the access
+    /// token was issued for.
+    ///
+    /// `client_name` identifies the application in submissions, the
+    /// MusicBrainz docs suggest the format `"application-version"`.
+    pub fn login(&mut self, token: AccessToken, client_name: &str) -> Session
+    {
+        Session::new(self, token, client_name)
+    }
+
+    /// The time to wait before retrying a failed request, `None` to give up.
+    fn retry_after(&self, kind: FailureKind, attempt: u8) -> Option<Duration>
+    {
+        match self.retry_policy {
+            Some(ref policy) => policy.retry_after(kind, attempt),
+            None => ExponentialBackoff::from_config(&self.config).retry_after(kind, attempt),
+        }
+    }
+
+    /// The url of the web service the requests are made to.
+    pub fn base_url(&self) -> &str
+    {
+        self.config.base_url.as_ref().map(|s| s.as_str()).unwrap_or(DEFAULT_BASE_URL)
+    }
+
+    /// The endpoint of the web service the requests are made to.
+    pub fn endpoint(&self) -> Endpoint
+    {
+        Endpoint::from_base_url(self.base_url())
+    }
+
     /// Waits until we are allowed to make the next request to the MusicBrainz
     /// API.
     fn wait_if_needed(&mut self)
     {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_request);
-        if as_millis(&elapsed) < self.config.waits.requests {
-            sleep(Duration::from_millis(self.config.waits.requests) - elapsed);
+        // If the server told us there are no requests left in the current time
+        // window, wait until the window is over.
+        let max_reset_wait = Duration::from_millis(self.config.waits.max_reset_wait);
+        let reset_wait = self.rate_limit.as_ref().and_then(|status| {
+            status.wait_before_next(max_reset_wait)
+        });
+        if let Some(wait) = reset_wait {
+            sleep(wait);
         }
-        self.last_request = now;
+
+        let wait = Duration::from_millis(
+            self.config.waits.requests + random_jitter(self.config.waits.jitter),
+        );
+        let elapsed = Instant::now().duration_since(self.last_request);
+        if elapsed < wait {
+            sleep(wait - elapsed);
+        }
+        self.last_request = Instant::now();
     }
 
     /// Fetch the specified ressource from the server and parse it.
+    ///
+    /// The default includes of the type are requested, see
+    /// `set_default_includes`.
+    ///
+    /// Errors provide the url and the type of the requested entity.
     pub fn get_by_mbid<Res>(&mut self, mbid: &Mbid) -> Result<Res, ClientError>
     where
         Res: Resource + FromXmlContained,
     {
-        let url = Res::get_url(mbid);
+        self.get_fetched(mbid).map(Fetched::into_inner)
+    }
+
+    /// Fetch the specified ressource from the server, requesting only the
+    /// specified includes instead of the default ones.
+    ///
+    /// Combinations of includes the web service would reject result in an
+    /// `InvalidIncludes` error without making a request.
+    ///
+    /// Data which wasn't requested will be missing from the parsed ressource,
+    /// e. g. a `Release` fetched with only `Include::Media` has no artists and
+    /// labels and its mediums contain no tracks, but the response is a lot
+    /// smaller.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use musicbrainz::client::Client;
+    /// # use musicbrainz::entities::{Include, Mbid, Release};
+    /// # fn structure(client: &mut Client, mbid: &Mbid) {
+    /// let release: Release = client
+    ///     .get_by_mbid_with_includes(mbid, &[Include::Media])
+    ///     .unwrap();
+    /// for medium in release.mediums.unwrap_or_default() {
+    ///     println!("{:?}: {} tracks", medium.format, medium.track_count);
+    /// }
+    /// # }
+    /// ```
+    pub fn get_by_mbid_with_includes<Res>(
+        &mut self,
+        mbid: &Mbid,
+        includes: &[Include],
+    ) -> Result<Res, ClientError>
+    where
+        Res: Resource + FromXmlContained,
+    {
+        self.get_fetched_with_includes(mbid, includes).map(Fetched::into_inner)
+    }
+
+    /// Like `get_by_mbid` but returns the entity together with the time it was
+    /// fetched, its url and the includes which were requested.
+    pub fn get_fetched<Res>(&mut self, mbid: &Mbid) -> Result<Fetched<Res>, ClientError>
+    where
+        Res: Resource + FromXmlContained,
+    {
+        let url = self.default_url::<Res>(mbid);
+        let includes = self.default_includes::<Res>();
+        self.fetch(mbid, url, includes)
+    }
+
+    /// Like `get_by_mbid_with_includes` but returns the entity together with
+    /// the time it was fetched, its url and the includes which were requested.
+    pub fn get_fetched_with_includes<Res>(
+        &mut self,
+        mbid: &Mbid,
+        includes: &[Include],
+    ) -> Result<Fetched<Res>, ClientError>
+    where
+        Res: Resource + FromXmlContained,
+    {
+        let url = Res::get_url_with_includes_at(self.base_url(), mbid, includes);
+        Res::check_includes(includes).map_err(|e| {
+            ClientError::from(e).with_request(url.clone(), Some(Res::get_name()))
+        })?;
+        self.fetch(mbid, url, includes.to_vec())
+    }
+
+    /// Fetch the entity from the url, which requests the includes.
+    ///
+    /// If the server denies the request, privileged includes were requested
+    /// and `set_drop_denied_includes` is enabled, the request is repeated
+    /// without the privileged includes.
+    fn fetch<Res>(
+        &mut self,
+        mbid: &Mbid,
+        url: String,
+        includes: Vec<Include>,
+    ) -> Result<Fetched<Res>, ClientError>
+    where
+        Res: Resource + FromXmlContained,
+    {
+        self.fetch_with(mbid, url, includes, |client, url, includes| {
+            client.get_and_parse(url, includes)
+        })
+    }
+
+    /// Like `fetch`, but performs the requests with `get`, which is passed the
+    /// url and the requested includes.
+    fn fetch_with<Res, G>(
+        &mut self,
+        mbid: &Mbid,
+        url: String,
+        includes: Vec<Include>,
+        mut get: G,
+    ) -> Result<Fetched<Res>, ClientError>
+    where
+        Res: Resource,
+        G: FnMut(&mut Client, &str, &[Include]) -> Result<Res, ClientError>,
+    {
+        let err = match get(self, &url, &includes) {
+            Ok(entity) => {
+                return Ok(Fetched {
+                    entity: entity,
+                    fetched_at: SystemTime::now(),
+                    url: url,
+                    includes: includes,
+                    denied_includes: Vec::new(),
+                })
+            }
+            Err(err) => err,
+        };
+
+        let unauthorized = match *err.kind() {
+            ClientErrorKind::Unauthorized => true,
+            _ => false,
+        };
+        let (denied, allowed): (Vec<Include>, Vec<Include>) =
+            includes.into_iter().partition(|include| include.is_privileged());
+        if !unauthorized || !self.drop_denied_includes || denied.is_empty() {
+            return Err(err.with_request(url, Some(Res::get_name())));
+        }
+
+        let url = Res::get_url_with_includes_at(self.base_url(), mbid, &allowed);
+        let mut fetched = self.fetch_with(mbid, url, allowed, get)?;
+        fetched.denied_includes = denied;
+        Ok(fetched)
+    }
+
+    /// Fetch the document at the url, which was requested with the includes,
+    /// and parse the ressource from it.
+    fn get_and_parse<Res>(&mut self, url: &str, includes: &[Include]) -> Result<Res, ClientError>
+    where
+        Res: Resource + FromXmlContained,
+    {
         let response_body = self.get_body(url.parse()?)?;
 
         // Parse the response.
+        let started = Instant::now();
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(&response_body[..], &context)?;
+        check_response_error(&reader)?;
+        let mut entity = Res::from_xml(&reader)?;
+        entity.mark_requested(includes);
+        if let Some(ref metrics) = self.metrics {
+            metrics.parse(Res::get_name(), started.elapsed());
+        }
+        Ok(entity)
+    }
+
+    /// Fetch all relationships of the entity with the specified MBID.
+    pub fn get_relations<Res>(&mut self, mbid: &Mbid) -> Result<Vec<Relation>, ClientError>
+    where
+        Res: Resource,
+    {
+        let url = self.endpoint().lookup_url(Res::get_name(), mbid, Some(RELATION_INCS));
+        self.get_relations_at(&url).map_err(|e| e.with_request(url.clone(), Some(Res::get_name())))
+    }
+
+    fn get_relations_at(&mut self, url: &str) -> Result<Vec<Relation>, ClientError>
+    {
+        let response_body = self.get_body(url.parse()?)?;
+
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(&response_body[..], &context)?;
+        check_response_error(&reader)?;
+        Ok(read_mb_vec(&reader, "//mb:relation-list/mb:relation")?)
+    }
+
+    /// Fetch the community rating of the entity with the specified MBID.
+    ///
+    /// Entities which were never rated have a rating without any votes.
+    pub fn get_rating<Res>(&mut self, mbid: &Mbid) -> Result<Rating, ClientError>
+    where
+        Res: Resource,
+    {
+        let url = Res::get_url_with_includes_at(self.base_url(), mbid, &[Include::Ratings]);
+        self.get_rating_at(&url).map_err(|e| e.with_request(url.clone(), Some(Res::get_name())))
+    }
+
+    fn get_rating_at(&mut self, url: &str) -> Result<Rating, ClientError>
+    {
+        let response_body = self.get_body(url.parse()?)?;
+
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(&response_body[..], &context)?;
+        check_response_error(&reader)?;
+        let rating: Option<Rating> = reader.read_option("//mb:metadata/*/mb:rating")?;
+        Ok(rating.unwrap_or_default())
+    }
+
+    /// The list of all genres known to MusicBrainz.
+    ///
+    /// The list is fetched on the first call (taking about a dozen requests)
+    /// and cached for `GENRE_CACHE_SECS` seconds.
+    pub fn genres(&mut self) -> Result<&[Genre], ClientError>
+    {
+        let expired = match self.genres {
+            Some((ref fetched, _)) => fetched.elapsed() >= Duration::from_secs(GENRE_CACHE_SECS),
+            None => true,
+        };
+        if expired {
+            let genres = self::genres::fetch_genres(self)?;
+            self.genres = Some((Instant::now(), genres));
+        }
+
+        match self.genres {
+            Some((_, ref genres)) => Ok(&genres[..]),
+            None => Ok(&[]),
+        }
+    }
+
+    /// Fill the genre cache with a list obtained before, e. g. one the
+    /// application stored between runs, which is then used by `genres`
+    /// instead of fetching it for `GENRE_CACHE_SECS` seconds.
+    pub fn set_genres(&mut self, genres: Vec<Genre>)
+    {
+        self.genres = Some((Instant::now(), genres));
+    }
+
+    /// Browse the events linked to an area, artist or place.
+    ///
+    /// See `EventBrowseBuilder::browse_all` for an example.
+    pub fn browse_events(&mut self) -> BrowseEvents
+    {
+        BrowseEvents::new(self)
+    }
+
+    /// Start walking the relationships of the entity with the specified MBID.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use musicbrainz::client::Client;
+    /// # use musicbrainz::entities::{Mbid, RelType};
+    /// # fn members(client: &mut Client, band: Mbid) {
+    /// let members = client
+    ///     .walk(band)
+    ///     .via(RelType::MemberOf)
+    ///     .collect_artists()
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn walk(&mut self, start: Mbid) -> Walk
+    {
+        Walk::new(self, start)
+    }
+
+    /// Find the `Release` containing the track with the specified MBID.
+    ///
+    /// The web service provides no lookup of tracks, but releases can be
+    /// browsed by the MBID of one of their tracks. A track is part of exactly
+    /// one release, which is requested with its artists, labels and track
+    /// lists. This is useful since tag readers often only have the track MBID
+    /// stored.
+    ///
+    /// Returns the release together with the position of the `ReleaseMedium`
+    /// containing the track, or `None` if no release contains the track.
+    pub fn find_release_by_track_mbid(
+        &mut self,
+        track: &Mbid,
+    ) -> Result<Option<(Release, u16)>, ClientError>
+    {
+        let url = format!(
+            "{}&inc=artist-credits+labels+recordings",
+            self.endpoint().browse_url(Release::get_name(), "track", track, 1, 0)
+        );
+        self.get_body(url.parse()?)
+            .and_then(|body| parse_release_by_track(&body, track))
+            .map_err(|e| e.with_request(url.clone(), Some(Release::get_name())))
+    }
+
+    /// Find the releases with the specified barcode.
+    ///
+    /// If `correct` is `true` the common variations of the barcode (see
+    /// `barcode_variants`) are searched as well, e. g. the EAN-13 of a UPC-A,
+    /// since scanned barcodes often differ from the ones in the database in
+    /// these ways. All variants are searched with a single request and each
+    /// match reports which variant it was found by, the releases with the
+    /// barcode as specified come first.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use musicbrainz::client::Client;
+    /// # fn lookup(client: &mut Client) {
+    /// for found in client.find_releases_by_barcode("724388023429", true).unwrap() {
+    ///     println!("{} ({:?})", found.release.title, found.variant);
+    /// }
+    /// # }
+    /// ```
+    pub fn find_releases_by_barcode(
+        &mut self,
+        barcode: &str,
+        correct: bool,
+    ) -> Result<Vec<BarcodeMatch>, ClientError>
+    {
+        let mut variants = barcode_variants(barcode);
+        if !correct {
+            variants.truncate(1);
+        }
+        if variants.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query: Vec<String> = variants
+            .iter()
+            .map(|&(ref code, _)| format!("barcode:{}", escape_lucene(code)))
+            .collect();
+        let url = search_url(&self.endpoint(), Release::get_name(), &query.join(" OR "), &[])?;
+        let response_body = self.get_body(url)?;
+
         let context = ::util::musicbrainz_context();
         let reader = XpathStrReader::new(&response_body[..], &context)?;
         check_response_error(&reader)?;
-        Ok(Res::from_xml(&reader)?)
+        let releases: Vec<ReleaseRef> = read_mb_vec(&reader, "//mb:release-list/mb:release")?;
+        let mut found = Vec::new();
+        for (i, release) in releases.into_iter().enumerate() {
+            let barcode: Option<String> = reader.read_option(
+                format!("//mb:release-list/mb:release[{}]/mb:barcode/text()", i + 1).as_str(),
+            )?;
+            found.push((release, barcode));
+        }
+        Ok(match_barcodes(found, &variants))
     }
 
     pub(crate) fn get_body(&mut self, url: Url) -> Result<String, ClientError>
+    {
+        self.get_body_checked(url, check_response_content)
+    }
+
+    /// Performs a request like `get_body`, checking the content type and body
+    /// of a successful response with `check` instead of expecting an XML
+    /// document, e. g. for requests to other web services.
+    pub(crate) fn get_body_checked<F>(&mut self, url: Url, check: F) -> Result<String, ClientError>
+    where
+        F: Fn(Option<&str>, &str) -> Result<(), ClientError>,
+    {
+        self.request(Method::Get, url, check)
+    }
+
+    /// Performs a request with the method, checking the response like
+    /// `get_body_checked`.
+    ///
+    /// The request is authorized if a `Session` set the `authorization`.
+    pub(crate) fn request<F>(
+        &mut self,
+        method: Method,
+        url: Url,
+        check: F,
+    ) -> Result<String, ClientError>
+    where
+        F: Fn(Option<&str>, &str) -> Result<(), ClientError>,
     {
         self.wait_if_needed();
 
-        let mut attempts = 0;
-        let mut backoff = self.config.waits.backoff_init;
-
-        while attempts < self.config.max_retries {
-            let response = self.http_client
-                .get(url.clone())
-                .header(UserAgent::new(self.config.user_agent.clone()))
-                .send()?;
-            if response.status == StatusCode::ServiceUnavailable {
-                sleep(Duration::from_millis(backoff));
-                attempts += 1;
-                backoff *= 2;
-                // If we are in testing we want to avoid always failing.
-                self.http_client.force_record_next();
+        let mut attempts: u8 = 0;
+        loop {
+            let mut headers = Headers::new();
+            headers.set(UserAgent::new(self.config.user_agent.clone()));
+            if let Some(ref accept_language) = self.config.accept_language {
+                headers.set_raw("Accept-Language", accept_language.clone());
+            }
+            if let Some(ref authorization) = self.authorization {
+                headers.set_raw("Authorization", authorization.clone());
+            }
+
+            let started = Instant::now();
+            let request = self.http_client.request(method.clone(), url.clone());
+            let response = match request.headers(headers).send() {
+                Ok(response) => response,
+                Err(e) => {
+                    self.record_request(&url, started, false);
+                    attempts = attempts.saturating_add(1);
+                    match self.retry_after(FailureKind::Http, attempts) {
+                        Some(wait) => {
+                            self.record_retry(&url, attempts);
+                            sleep(wait);
+                            continue;
+                        }
+                        None => return Err(e.into()),
+                    }
+                }
+            };
+            if let Some(status) = RateLimitStatus::from_headers(&response.headers) {
+                self.rate_limit = Some(status);
+            }
+
+            if response.status == StatusCode::Unauthorized {
+                self.record_request(&url, started, false);
+                return Err(ClientErrorKind::Unauthorized.into());
+            } else if response.status == StatusCode::ServiceUnavailable {
+                self.record_request(&url, started, false);
+                attempts = attempts.saturating_add(1);
+                match self.retry_after(FailureKind::ServiceUnavailable, attempts) {
+                    Some(wait) => {
+                        self.record_retry(&url, attempts);
+                        sleep(wait);
+                        // If we are in testing we want to avoid always failing.
+                        self.http_client.force_record_next();
+                    }
+                    None => {
+                        return Err(
+                            ClientErrorKind::ServiceUnavailable { attempts: attempts }.into(),
+                        )
+                    }
+                }
             } else {
-                let response_body = response.body_to_utf8()?;
-                return Ok(response_body);
+                let content_type = response.headers.get::<ContentType>().map(|c| c.to_string());
+                let result = validate_response_size(
+                    &response.headers,
+                    response.body.len(),
+                    self.config.max_response_size,
+                )
+                    .and_then(|_| response.body_to_utf8().map_err(ClientError::from))
+                    .and_then(|body| {
+                        check(content_type.as_ref().map(|s| s.as_str()), &body)?;
+                        Ok(body)
+                    });
+                self.record_request(&url, started, result.is_ok());
+                return result;
             }
         }
-        Err(
-            "MusicBrainz returned 503 (ServiceUnavailable) too many times.".into(),
-        )
+    }
+
+    fn record_request(&self, url: &Url, started: Instant, success: bool)
+    {
+        if let Some(ref metrics) = self.metrics {
+            metrics.request(url.as_str(), started.elapsed(), success);
+        }
+    }
+
+    fn record_retry(&self, url: &Url, attempt: u8)
+    {
+        if let Some(ref metrics) = self.metrics {
+            metrics.retry(url.as_str(), attempt);
+        }
+    }
+
+    /// Estimate how many requests the operations described by `plan` make
+    /// and how long they take at least with the settings of this client.
+    ///
+    /// This allows to warn users before starting a long running job.
+    pub fn estimate_requests(&self, plan: &RequestPlan) -> RequestEstimate
+    {
+        RequestEstimate::new(plan, &self.config)
+    }
+
+    /// Returns the rate limit state as reported by the server in the most
+    /// recent response which contained this information.
+    pub fn rate_limit_status(&self) -> Option<&RateLimitStatus>
+    {
+        self.rate_limit.as_ref()
     }
 
     /// Returns a search builder to search for an area.
@@ -183,17 +928,230 @@ impl Client {
         AreaSearchBuilder::new(self)
     }
 
+    /// Find the country `Area` with the specified ISO 3166-1 code, e.g. `"JP"`.
+    ///
+    /// Returns `None` if there is no such country in the database.
+    pub fn find_area_by_iso(&mut self, iso_code: &str) -> Result<Option<Area>, ClientError>
+    {
+        use search::fields::area::{AreaIso, AreaType as AreaTypeField};
+
+        let results = self.search_area()
+            .add(AreaIso(iso_code.to_uppercase()))
+            .add(AreaTypeField(AreaType::Country))
+            .search()?;
+        Ok(results.into_iter().map(|entry| entry.entity).find(|area| {
+            area.area_type == AreaType::Country &&
+                area.iso_3166.as_ref().map(|c| c.eq_ignore_ascii_case(iso_code)) == Some(true)
+        }))
+    }
+
+    /// Find areas by name, optionally only areas of the specified type, e. g.
+    /// to autocomplete locations entered by users.
+    ///
+    /// Areas whose name matches exactly come first. Of these the ones without
+    /// a disambiguation comment are ranked higher, since the comment usually
+    /// marks the less known of areas sharing a name. Otherwise the areas are
+    /// ordered by their search score.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use musicbrainz::client::Client;
+    /// # use musicbrainz::entities::AreaType;
+    /// # fn autocomplete(client: &mut Client) {
+    /// let cities = client.find_area("Tokyo", Some(AreaType::City)).unwrap();
+    /// # }
+    /// ```
+    pub fn find_area(
+        &mut self,
+        name: &str,
+        area_type: Option<AreaType>,
+    ) -> Result<Vec<AreaRef>, ClientError>
+    {
+        use search::fields::area::{AreaName, AreaType as AreaTypeField};
+
+        let mut builder = self.search_area().add(AreaName(name.to_string()));
+        if let Some(ref area_type) = area_type {
+            builder = builder.add(AreaTypeField(area_type.clone()));
+        }
+        let entries = builder
+            .search()?
+            .into_iter()
+            .filter(|entry| area_type.as_ref().map_or(true, |t| entry.entity.area_type == *t))
+            .collect();
+        Ok(rank_areas(name, entries))
+    }
+
+    /// Find artists similar to the specified one by searching for artists
+    /// with any of its `max_tags` most used tags.
+    ///
+    /// If the tags of the artist weren't fetched it is fetched again with
+    /// `Include::Tags`. The found artists are returned together with their
+    /// similarity (see `Artist::similarity`), the most similar ones first.
+    /// The artist itself is excluded.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use musicbrainz::client::Client;
+    /// # use musicbrainz::entities::Artist;
+    /// # fn recommend(client: &mut Client, artist: &Artist) {
+    /// for (similar, score) in client.find_similar_artists(artist, 3).unwrap() {
+    ///     println!("{} ({:.2})", similar.name, score);
+    /// }
+    /// # }
+    /// ```
+    pub fn find_similar_artists(
+        &mut self,
+        artist: &Artist,
+        max_tags: usize,
+    ) -> Result<Vec<(Artist, f32)>, ClientError>
+    {
+        use search::fields::artist::Tag as TagField;
+
+        let fetched;
+        let artist = match artist.tags {
+            Some(_) => artist,
+            None => {
+                fetched = self.get_by_mbid_with_includes::<Artist>(
+                    &artist.mbid,
+                    &[Include::Aliases, Include::Tags],
+                )?;
+                &fetched
+            }
+        };
+        let tags = artist.tags.as_ref().map_or(&[][..], |t| &t[..]);
+        let fields: Vec<TagField> = top_tags(tags, max_tags)
+            .into_iter()
+            .map(|tag| TagField(format!("\"{}\"", escape_lucene(&tag.name))))
+            .collect();
+        if fields.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let entries = self.search_artist().add_any(fields).search()?;
+        Ok(rank_similar(artist, entries))
+    }
+
     /// Returns a search biulder to search for an artist.
     pub fn search_artist<'cl>(&'cl mut self) -> ArtistSearchBuilder<'cl>
     {
         ArtistSearchBuilder::new(self)
     }
 
+    /// Returns a search builder to search for a label, e. g. by its label
+    /// code.
+    pub fn search_label<'cl>(&'cl mut self) -> LabelSearchBuilder<'cl>
+    {
+        LabelSearchBuilder::new(self)
+    }
+
+    /// Returns a search builder to search for a recording, e. g. by its ISRC
+    /// or its title and artist.
+    pub fn search_recording<'cl>(&'cl mut self) -> RecordingSearchBuilder<'cl>
+    {
+        RecordingSearchBuilder::new(self)
+    }
+
+    /// Returns a search builder to search for a release, e. g. by its barcode
+    /// or catalog number.
+    pub fn search_release<'cl>(&'cl mut self) -> ReleaseSearchBuilder<'cl>
+    {
+        ReleaseSearchBuilder::new(self)
+    }
+
     /// Returns a search builder to search for a release group.
     pub fn search_release_group<'cl>(&'cl mut self) -> ReleaseGroupSearchBuilder<'cl>
     {
         ReleaseGroupSearchBuilder::new(self)
     }
+
+    /// Returns a search builder to search for a work, e. g. by its ISWC or its
+    /// title and artist.
+    pub fn search_work<'cl>(&'cl mut self) -> WorkSearchBuilder<'cl>
+    {
+        WorkSearchBuilder::new(self)
+    }
+}
+
+/// Validates the size of a received response body against the limit, see
+/// `ClientConfig::max_response_size`.
+///
+/// The size announced by the server is checked before the size of the body
+/// which was read, so a response announced as too large fails with the
+/// announced size even if the body was cut short.
+fn validate_response_size(
+    headers: &Headers,
+    body_len: usize,
+    limit: Option<usize>,
+) -> Result<(), ClientError>
+{
+    let limit = match limit {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let size = match headers.get::<ContentLength>() {
+        Some(&ContentLength(announced)) if announced as usize > limit => announced as usize,
+        _ => body_len,
+    };
+    if size > limit {
+        Err(ClientErrorKind::ResponseTooLarge { size: size, limit: limit }.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Orders the areas found by `Client::find_area`.
+fn rank_areas(name: &str, mut entries: Vec<SearchEntry<Area>>) -> Vec<AreaRef>
+{
+    let name = name.trim().to_lowercase();
+
+    // The sort is stable, so equally ranked areas keep the order of the server.
+    entries.sort_by_key(|entry| {
+        let exact = entry.entity.name.to_lowercase() == name;
+        (!exact, entry.entity.disambiguation.is_some(), u8::max_value() - entry.score)
+    });
+    entries.into_iter().map(|entry| AreaRef::from(entry.entity)).collect()
+}
+
+/// Orders the artists found by `Client::find_similar_artists`.
+fn rank_similar(artist: &Artist, entries: Vec<SearchEntry<Artist>>) -> Vec<(Artist, f32)>
+{
+    let mut similar: Vec<(Artist, f32)> = entries
+        .into_iter()
+        .filter(|entry| entry.entity.mbid != artist.mbid)
+        .map(|entry| {
+            let score = artist.similarity(&entry.entity).unwrap_or(0.);
+            (entry.entity, score)
+        })
+        .collect();
+    // The sort is stable, so equally similar artists keep the order of the server.
+    similar.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    similar
+}
+
+/// Parse the response of browsing the releases by the MBID of a track, see
+/// `Client::find_release_by_track_mbid`.
+fn parse_release_by_track(xml: &str, track: &Mbid) -> Result<Option<(Release, u16)>, ClientError>
+{
+    let context = ::util::musicbrainz_context();
+    let reader = XpathStrReader::new(xml, &context)?;
+    check_response_error(&reader)?;
+
+    // The page holds at most one release, so it can be read like the response
+    // of a lookup.
+    if reader.evaluate("count(//mb:release-list/mb:release)")?.number() == 0. {
+        return Ok(None);
+    }
+    let release = Release::from_xml(&reader)?;
+
+    let position = release
+        .mediums
+        .iter()
+        .flat_map(|mediums| mediums.iter())
+        .find(|medium| {
+            medium.tracks.iter().flat_map(|t| t.iter()).any(|t| t.mbid == *track)
+        })
+        .map(|medium| medium.position);
+    Ok(position.map(|position| (release, position)))
 }
 
 #[cfg(test)]
@@ -207,6 +1165,9 @@ mod tests {
                 user_agent: "MusicBrainz-Rust/Testing".to_string(),
                 max_retries: 5,
                 waits: ClientWaits::default(),
+                base_url: None,
+                accept_language: None,
+                max_response_size: None,
             },
             HttpClient::replay_file(format!("replay/test_client/search/{}.json", testname)),
         )
@@ -232,4 +1193,342 @@ mod tests {
         );
         assert_eq!(results[0].entity.title, "霊魂消滅".to_string());
     }
+
+    #[test]
+    fn find_release_by_track()
+    {
+        let mut client = Client::with_http_client(
+            ClientConfig {
+                user_agent: "MusicBrainz-Rust/Testing".to_string(),
+                max_retries: 5,
+                waits: ClientWaits::default(),
+                base_url: None,
+                accept_language: None,
+                max_response_size: None,
+            },
+            HttpClient::replay_file("replay/test_client/browse/release_by_track.json"),
+        );
+        let track: Mbid = "237450df-3ba8-3627-b270-49231eca1165".parse().unwrap();
+        let (release, position) = client.find_release_by_track_mbid(&track).unwrap().unwrap();
+
+        assert_eq!(release.mbid, "ed118c5f-d940-4b52-a37b-b1a205374abe".parse().unwrap());
+        assert_eq!(position, 1);
+        let lurgee = release
+            .find_track_by_recording(&"c17a2b44-8b59-4575-a557-b0bfa03df868".parse().unwrap())
+            .unwrap();
+        assert_eq!(lurgee.mbid, track);
+        assert_eq!(lurgee.title, "Lurgee".to_string());
+
+        let empty = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release-list count="0" offset="0"></release-list></metadata>"#;
+        assert!(parse_release_by_track(empty, &track).unwrap().is_none());
+    }
+
+    #[test]
+    fn response_too_large()
+    {
+        let mut headers = Headers::new();
+        assert!(validate_response_size(&headers, 100, Some(100)).is_ok());
+        assert!(validate_response_size(&headers, 1 << 30, None).is_ok());
+
+        let err = validate_response_size(&headers, 101, Some(100)).unwrap_err();
+        match *err.kind() {
+            ClientErrorKind::ResponseTooLarge { size, limit } => {
+                assert_eq!(size, 101);
+                assert_eq!(limit, 100);
+            }
+            ref kind => panic!("Unexpected error kind: {:?}", kind),
+        }
+
+        // The announced size counts even if the body turns out to be shorter.
+        headers.set(ContentLength(5000));
+        let err = validate_response_size(&headers, 50, Some(100)).unwrap_err();
+        match *err.kind() {
+            ClientErrorKind::ResponseTooLarge { size, .. } => assert_eq!(size, 5000),
+            ref kind => panic!("Unexpected error kind: {:?}", kind),
+        }
+
+        // A body larger than announced is caught as well.
+        headers.set(ContentLength(50));
+        let err = validate_response_size(&headers, 5000, Some(100)).unwrap_err();
+        match *err.kind() {
+            ClientErrorKind::ResponseTooLarge { size, .. } => assert_eq!(size, 5000),
+            ref kind => panic!("Unexpected error kind: {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn configure_default_includes()
+    {
+        let mut client = Client::new(ClientConfig::public("MusicBrainz-Rust/Testing"));
+        let mbid: Mbid = "650e7db6-b795-4eb5-a702-5ea2fc46c848".parse().unwrap();
+        let url = "https://musicbrainz.org/ws/2/artist/650e7db6-b795-4eb5-a702-5ea2fc46c848";
+        assert_eq!(client.default_includes::<Artist>(), vec![Include::Aliases]);
+        assert_eq!(client.default_url::<Artist>(&mbid), format!("{}?inc=aliases", url));
+
+        client.set_default_includes::<Artist>(&[Include::Aliases, Include::Tags]).unwrap();
+        assert_eq!(client.default_includes::<Artist>(), vec![Include::Aliases, Include::Tags]);
+        assert_eq!(client.default_url::<Artist>(&mbid), format!("{}?inc=aliases+tags", url));
+        // Other types keep their defaults.
+        assert_eq!(
+            client.default_includes::<Release>(),
+            Include::split(Release::get_incs())
+        );
+
+        assert!(client.set_default_includes::<Artist>(&[Include::Isrcs]).is_err());
+        assert_eq!(client.default_includes::<Artist>(), vec![Include::Aliases, Include::Tags]);
+
+        client.set_default_includes::<Artist>(&[]).unwrap();
+        assert_eq!(client.default_url::<Artist>(&mbid), url);
+
+        client.reset_default_includes::<Artist>();
+        assert_eq!(client.default_includes::<Artist>(), vec![Include::Aliases]);
+    }
+
+    #[test]
+    fn cached_genres()
+    {
+        let mut client = Client::new(ClientConfig::public("MusicBrainz-Rust/Testing"));
+        client.set_genres(vec![
+            Genre {
+                mbid: "b2d3ad75-3e19-4f1b-8b5d-b9bbd9b70a7d".parse().unwrap(),
+                name: "shoegaze".to_string(),
+                disambiguation: None,
+            },
+        ]);
+
+        assert_eq!(client.genres().unwrap().len(), 1);
+        let genre = Genre::resolve("Shoe-Gaze", &mut client).unwrap().unwrap();
+        assert_eq!(genre.name, "shoegaze".to_string());
+        assert_eq!(Genre::resolve("seen live", &mut client).unwrap(), None);
+    }
+
+    #[test]
+    fn rank_similar_artists()
+    {
+        use entities::Tag;
+
+        let artist = |mbid: &str, tags: &[(&str, i32)]| Artist {
+            mbid: mbid.parse().unwrap(),
+            name: mbid[..4].to_string(),
+            sort_name: mbid[..4].to_string(),
+            aliases: None,
+            annotation: None,
+            disambiguation: None,
+            artist_type: None,
+            gender: None,
+            area: None,
+            begin_date: None,
+            end_date: None,
+            ended: false,
+            ipi_code: None,
+            isni_code: None,
+            tags: Some(
+                tags.iter()
+                    .map(|&(name, count)| Tag {
+                        name: name.to_string(),
+                        count: count,
+                    })
+                    .collect(),
+            ),
+        };
+        let entry = |artist: Artist| SearchEntry {
+            entity: artist,
+            score: 100,
+        };
+
+        let radiohead = artist(
+            "a74b1b7f-71a5-4011-9441-d0b5e4122711",
+            &[("rock", 10), ("alternative rock", 5)],
+        );
+        let entries = vec![
+            entry(artist("0a1b2c3d-0000-4000-8000-000000000001", &[("rock", 1)])),
+            entry(radiohead.clone()),
+            entry(artist(
+                "0a1b2c3d-0000-4000-8000-000000000002",
+                &[("rock", 8), ("alternative rock", 5)],
+            )),
+            entry(artist("0a1b2c3d-0000-4000-8000-000000000003", &[("jazz", 3)])),
+        ];
+
+        let ranked: Vec<(String, f32)> = rank_similar(&radiohead, entries)
+            .into_iter()
+            .map(|(artist, score)| (artist.mbid.to_string(), score))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                ("0a1b2c3d-0000-4000-8000-000000000002".to_string(), 13. / 15.),
+                ("0a1b2c3d-0000-4000-8000-000000000001".to_string(), 1. / 15.),
+                ("0a1b2c3d-0000-4000-8000-000000000003".to_string(), 0.),
+            ]
+        );
+    }
+
+    #[test]
+    fn rank_found_areas()
+    {
+        let area = |mbid: &str, name: &str, disambiguation: Option<&str>, score: u8| {
+            SearchEntry {
+                entity: Area {
+                    mbid: mbid.parse().unwrap(),
+                    name: name.to_string(),
+                    sort_name: name.to_string(),
+                    area_type: AreaType::City,
+                    iso_3166: None,
+                    disambiguation: disambiguation.map(|d| d.to_string()),
+                },
+                score: score,
+            }
+        };
+        let entries = vec![
+            area("f42e38f3-6b19-4c82-9b3e-1a2a2b0b1e01", "Tokyo-ku", None, 100),
+            area("f42e38f3-6b19-4c82-9b3e-1a2a2b0b1e02", "Tokyo", Some("Minato"), 100),
+            area("f42e38f3-6b19-4c82-9b3e-1a2a2b0b1e03", "Tokyo", None, 90),
+            area("f42e38f3-6b19-4c82-9b3e-1a2a2b0b1e04", "Tokyo Bay", None, 95),
+        ];
+
+        let ranked: Vec<String> = rank_areas("tokyo", entries)
+            .into_iter()
+            .map(|area| area.mbid.to_string())
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                "f42e38f3-6b19-4c82-9b3e-1a2a2b0b1e03".to_string(),
+                "f42e38f3-6b19-4c82-9b3e-1a2a2b0b1e02".to_string(),
+                "f42e38f3-6b19-4c82-9b3e-1a2a2b0b1e01".to_string(),
+                "f42e38f3-6b19-4c82-9b3e-1a2a2b0b1e04".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn report_metrics()
+    {
+        use entities::Artist;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Counts {
+            requests: Arc<AtomicUsize>,
+            parsed: Arc<AtomicUsize>,
+        }
+
+        impl Metrics for Counts {
+            fn request(&self, url: &str, _: Duration, success: bool)
+            {
+                assert!(url.contains("/artist/"));
+                assert!(success);
+                self.requests.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn parse(&self, entity: &'static str, _: Duration)
+            {
+                assert_eq!(entity, "artist");
+                self.parsed.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let parsed = Arc::new(AtomicUsize::new(0));
+        let mbid: Mbid = "90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e".parse().unwrap();
+        let mut client = Client::with_http_client(
+            ClientConfig {
+                user_agent: "MusicBrainz-Rust/Testing".to_string(),
+                max_retries: 5,
+                waits: ClientWaits::default(),
+                base_url: None,
+                accept_language: None,
+                max_response_size: None,
+            },
+            HttpClient::replay_file(format!("replay/test_entities/artist/{}.json", mbid)),
+        );
+        client.set_metrics(Box::new(Counts {
+            requests: requests.clone(),
+            parsed: parsed.clone(),
+        }));
+
+        let _: Artist = client.get_by_mbid(&mbid).unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert_eq!(parsed.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn drop_denied_includes()
+    {
+        use entities::FromXmlStr;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><artist id="90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e" type="Group"><name>NECRONOMIDOL</name><sort-name>NECRONOMIDOL</sort-name></artist></metadata>"#;
+        let mbid: Mbid = "90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e".parse().unwrap();
+        let includes = vec![Include::Aliases, Include::UserTags, Include::UserRatings];
+        let url = Artist::get_url_with_includes_at(DEFAULT_BASE_URL, &mbid, &includes);
+
+        // The server denies every request with privileged includes.
+        let mut requested: Vec<(String, Vec<Include>)> = Vec::new();
+        let mut client = Client::new(ClientConfig::public("MusicBrainz-Rust/Testing"));
+        client.set_drop_denied_includes(true);
+        let fetched = client
+            .fetch_with(&mbid, url.clone(), includes.clone(), |_, url, includes| {
+                requested.push((url.to_string(), includes.to_vec()));
+                if includes.iter().any(|i| i.is_privileged()) {
+                    Err(ClientErrorKind::Unauthorized.into())
+                } else {
+                    Ok(Artist::from_xml_str(xml).unwrap())
+                }
+            })
+            .unwrap();
+
+        assert_eq!(requested.len(), 2);
+        assert_eq!(requested[0].0, url);
+        assert_eq!(requested[1].1, vec![Include::Aliases]);
+        assert!(!requested[1].0.contains("user-tags"));
+        assert!(!requested[1].0.contains("user-ratings"));
+        assert_eq!(fetched.url, requested[1].0);
+        assert_eq!(fetched.includes, vec![Include::Aliases]);
+        assert_eq!(fetched.denied_includes, vec![Include::UserTags, Include::UserRatings]);
+        assert_eq!(fetched.entity.name, "NECRONOMIDOL".to_string());
+
+        // Without dropping them the request just fails.
+        let mut requests = 0;
+        client.set_drop_denied_includes(false);
+        let result: Result<Fetched<Artist>, ClientError> =
+            client.fetch_with(&mbid, url, includes, |_, _, _| {
+                requests += 1;
+                Err(ClientErrorKind::Unauthorized.into())
+            });
+        assert_eq!(requests, 1);
+        match *result.unwrap_err().kind() {
+            ClientErrorKind::Unauthorized => {}
+            ref kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn config_presets()
+    {
+        let public = ClientConfig::public("MusicBrainz-Rust/Testing");
+        assert_eq!(Client::new(public.clone()).base_url(), DEFAULT_BASE_URL);
+        assert_eq!(public.waits.requests, 1000);
+
+        let test = ClientConfig::test_server("MusicBrainz-Rust/Testing");
+        assert_eq!(
+            Client::new(test.clone()).base_url(),
+            "https://test.musicbrainz.org/ws/2"
+        );
+        assert_eq!(test.waits.requests, 1000);
+
+        let mirror = ClientConfig::mirror("MusicBrainz-Rust/Testing", "http://localhost:5000/ws/2");
+        assert_eq!(Client::new(mirror.clone()).base_url(), "http://localhost:5000/ws/2");
+        assert_eq!(mirror.waits.requests, 0);
+        assert!(mirror.max_retries > public.max_retries);
+    }
+
+    #[test]
+    fn jitter_bounds()
+    {
+        assert_eq!(random_jitter(0), 0);
+        for _ in 0..100 {
+            assert!(random_jitter(250) <= 250);
+        }
+    }
 }
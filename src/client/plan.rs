@@ -0,0 +1,153 @@
+//! Estimation of the number of requests composite operations take.
+
+use std::time::Duration;
+
+use client::ClientConfig;
+
+/// A description of a composite operation, e. g. fetching a discography with
+/// all of its releases, used to estimate its cost with
+/// `Client::estimate_requests` before starting it.
+///
+/// Every step adds the requests made by the respective client methods, the
+/// plan doesn't make any requests itself.
+///
+/// # Examples
+/// ```
+/// # use musicbrainz::client::{Client, ClientConfig, ClientWaits, RequestPlan};
+/// # let client = Client::new(ClientConfig {
+/// #     user_agent: "MusicBrainz-Rust/Example".to_string(),
+/// #     max_retries: 5,
+/// #     waits: ClientWaits::default(),
+/// #     base_url: None,
+/// #     accept_language: None,
+/// #     max_response_size: None,
+/// # });
+/// // Search the release groups of an artist, then fetch their 120 releases
+/// // with everything needed to tag them.
+/// let plan = RequestPlan::new().searches(1).lookups(120);
+/// let estimate = client.estimate_requests(&plan);
+/// assert_eq!(estimate.requests, 121);
+/// if estimate.duration.as_secs() > 60 {
+///     println!("This will take about {} minutes.", estimate.duration.as_secs() / 60);
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RequestPlan {
+    requests: u64,
+}
+
+impl RequestPlan {
+    /// An empty plan making no requests.
+    pub fn new() -> Self
+    {
+        RequestPlan::default()
+    }
+
+    /// Look up `count` entities, e. g. using `Client::get_by_mbid`,
+    /// `Release::fetch_for_tagging` or a `LookupQueue`.
+    ///
+    /// Fetching the relations or the rating of an entity is a lookup as well.
+    pub fn lookups(mut self, count: u64) -> Self
+    {
+        self.requests += count;
+        self
+    }
+
+    /// Make `count` searches, each returning a single page of results.
+    pub fn searches(mut self, count: u64) -> Self
+    {
+        self.requests += count;
+        self
+    }
+
+    /// Walk the relationships of entities with `Client::walk`, expecting to
+    /// visit `entities` entities (including the start) before the depth limit
+    /// is reached.
+    ///
+    /// The entities found at the maximal depth aren't visited, so they don't
+    /// need to be counted.
+    pub fn walk(mut self, entities: u64) -> Self
+    {
+        self.requests += entities;
+        self
+    }
+
+    /// Find the releases of `count` tracks with
+    /// `Client::find_release_by_track_mbid`, expecting to look up
+    /// `releases_per_track` releases until each track is found.
+    pub fn track_lookups(mut self, count: u64, releases_per_track: u64) -> Self
+    {
+        self.requests += count * (1 + releases_per_track);
+        self
+    }
+}
+
+/// The cost of a `RequestPlan` under the settings of a client, as returned by
+/// `Client::estimate_requests`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestEstimate {
+    /// The number of HTTP requests if none of them have to be retried.
+    pub requests: u64,
+
+    /// The number of HTTP requests if every request is retried as often as
+    /// `ClientConfig::max_retries` allows.
+    pub max_requests: u64,
+
+    /// The minimal time the requests take because of the wait between two
+    /// requests, not including jitter, backoff and the time to respond.
+    pub duration: Duration,
+}
+
+impl RequestEstimate {
+    pub(crate) fn new(plan: &RequestPlan, config: &ClientConfig) -> Self
+    {
+        let requests = plan.requests;
+        // The first request is made without waiting.
+        let waits = requests.saturating_sub(1);
+
+        RequestEstimate {
+            requests: requests,
+            max_requests: requests * u64::from(config.max_retries),
+            duration: Duration::from_millis(waits * config.waits.requests),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use client::ClientWaits;
+
+    fn config() -> ClientConfig
+    {
+        ClientConfig {
+            user_agent: "MusicBrainz-Rust/Testing".to_string(),
+            max_retries: 5,
+            waits: ClientWaits::default(),
+            base_url: None,
+            accept_language: None,
+            max_response_size: None,
+        }
+    }
+
+    #[test]
+    fn estimate_plan()
+    {
+        let plan = RequestPlan::new().searches(2).lookups(10).track_lookups(3, 2).walk(4);
+        let estimate = RequestEstimate::new(&plan, &config());
+
+        assert_eq!(estimate.requests, 25);
+        assert_eq!(estimate.max_requests, 125);
+        assert_eq!(estimate.duration, Duration::from_secs(24));
+    }
+
+    #[test]
+    fn estimate_empty_plan()
+    {
+        let estimate = RequestEstimate::new(&RequestPlan::new(), &config());
+
+        assert_eq!(estimate.requests, 0);
+        assert_eq!(estimate.max_requests, 0);
+        assert_eq!(estimate.duration, Duration::from_secs(0));
+    }
+}
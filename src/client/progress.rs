@@ -0,0 +1,55 @@
+use entities::Mbid;
+
+/// The progress of a bulk operation, as passed to the progress callbacks of
+/// methods like `LookupQueue::process_with_progress`.
+///
+/// Since the client waits between requests, bulk operations can take a long
+/// time, these callbacks allow showing a progress bar in the meantime.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Progress<'a> {
+    /// The number of entities which were already processed, including
+    /// `current`.
+    pub done: usize,
+
+    /// The total number of entities to process.
+    pub total: usize,
+
+    /// The MBID of the entity which was just processed.
+    pub current: &'a Mbid,
+}
+
+impl<'a> Progress<'a> {
+    pub(crate) fn new(done: usize, total: usize, current: &'a Mbid) -> Self
+    {
+        Progress {
+            done: done,
+            total: total,
+            current: current,
+        }
+    }
+
+    /// The fraction of processed entities between `0.0` and `1.0`.
+    pub fn fraction(&self) -> f32
+    {
+        if self.total == 0 {
+            1.
+        } else {
+            self.done as f32 / self.total as f32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn progress_fraction()
+    {
+        let mbid = Mbid::from_str("90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e").unwrap();
+        assert_eq!(Progress::new(1, 4, &mbid).fraction(), 0.25);
+        assert_eq!(Progress::new(4, 4, &mbid).fraction(), 1.);
+        assert_eq!(Progress::new(0, 0, &mbid).fraction(), 1.);
+    }
+}
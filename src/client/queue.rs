@@ -0,0 +1,351 @@
+//! A persistent queue of pending lookups for large bulk jobs.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use xpath_reader::reader::FromXmlContained;
+
+use client::{CancellationToken, Client, Progress};
+use client::cancel::is_cancelled;
+use entities::{Mbid, Resource};
+use errors::ClientError;
+
+/// A file backed queue of MBIDs of entities to look up.
+///
+/// The pending MBIDs are stored in a plain text file, one per line. Processed
+/// entities are appended to a log next to it (the path with `.done` appended)
+/// instead of rewriting the whole file every time, the log is merged into the
+/// queue file when the queue is opened again or processing finishes. If the
+/// process is interrupted, e. g. by a crash, opening the queue again resumes
+/// the job where it stopped.
+///
+/// Entities are processed *at least once*: if the process dies after an
+/// entity was handled but before it was logged, the entity will be looked up
+/// and handled again after resuming.
+///
+/// # Examples
+/// ```no_run
+/// # use musicbrainz::client::{Client, LookupQueue};
+/// # use musicbrainz::entities::{Artist, Mbid};
+/// # fn import(client: &mut Client, mbids: Vec<Mbid>) {
+/// let mut queue = LookupQueue::<Artist>::open("artists.queue").unwrap();
+/// if queue.is_empty() {
+///     queue.extend(mbids).unwrap();
+/// }
+/// queue
+///     .process(client, |artist| println!("{}", artist.name))
+///     .unwrap();
+/// # }
+/// ```
+pub struct LookupQueue<Res> {
+    path: PathBuf,
+    pending: VecDeque<Mbid>,
+    done_log: Option<File>,
+    cancel: Option<CancellationToken>,
+    _resource: PhantomData<Res>,
+}
+
+impl<Res> LookupQueue<Res>
+where
+    Res: Resource + FromXmlContained,
+{
+    /// Open the queue stored at `path`, creating an empty one if the file
+    /// doesn't exist yet.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self>
+    {
+        let path = path.as_ref().to_path_buf();
+        let mut pending: VecDeque<Mbid> = read_mbids(&path)?.into_iter().collect();
+        let completed = read_mbids(&log_path(&path))?;
+
+        // An MBID queued multiple times is only removed as often as it was
+        // completed.
+        let mut completed_counts: HashMap<&Mbid, usize> = HashMap::new();
+        for mbid in completed.iter() {
+            *completed_counts.entry(mbid).or_insert(0) += 1;
+        }
+        pending.retain(|mbid| match completed_counts.get_mut(mbid) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        });
+
+        let mut queue = LookupQueue {
+            path: path,
+            pending: pending,
+            done_log: None,
+            cancel: None,
+            _resource: PhantomData,
+        };
+        if !completed.is_empty() {
+            queue.compact()?;
+        }
+        Ok(queue)
+    }
+
+    /// The number of pending lookups.
+    pub fn len(&self) -> usize
+    {
+        self.pending.len()
+    }
+
+    /// Returns `true` if there are no pending lookups.
+    pub fn is_empty(&self) -> bool
+    {
+        self.pending.is_empty()
+    }
+
+    /// The MBID which will be looked up next.
+    pub fn peek(&self) -> Option<&Mbid>
+    {
+        self.pending.front()
+    }
+
+    /// Stop processing once the token is cancelled.
+    ///
+    /// The remaining lookups stay in the queue, so processing can be resumed
+    /// later.
+    pub fn cancel_on(&mut self, token: CancellationToken)
+    {
+        self.cancel = Some(token);
+    }
+
+    /// Add a lookup to the end of the queue.
+    pub fn push(&mut self, mbid: Mbid) -> io::Result<()>
+    {
+        self.extend(Some(mbid))
+    }
+
+    /// Add multiple lookups to the end of the queue.
+    pub fn extend<I>(&mut self, mbids: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Mbid>,
+    {
+        let mbids: Vec<Mbid> = mbids.into_iter().collect();
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        for mbid in mbids.iter() {
+            writeln!(file, "{}", mbid)?;
+        }
+        self.pending.extend(mbids);
+        Ok(())
+    }
+
+    /// Remove the next lookup without performing it, e. g. after it failed
+    /// because the entity was deleted from the database.
+    pub fn skip(&mut self) -> io::Result<Option<Mbid>>
+    {
+        let mbid = self.pending.pop_front();
+        if let Some(ref mbid) = mbid {
+            self.log_completed(mbid)?;
+        }
+        Ok(mbid)
+    }
+
+    /// Look up all pending entities in order, passing each one to `handle`.
+    ///
+    /// The requests are performed by the client and thus subject to its rate
+    /// limiting. Returns early if the token set with `cancel_on` was
+    /// cancelled. If a lookup fails the error is returned and the failed MBID
+    /// stays at the front of the queue, so the job can be resumed later or the
+    /// entity can be skipped using `skip`.
+    pub fn process<F>(&mut self, client: &mut Client, handle: F) -> Result<(), ClientError>
+    where
+        F: FnMut(Res),
+    {
+        self.process_with_progress(client, handle, |_| {})
+    }
+
+    /// Like `process`, but calls `on_progress` after every handled entity.
+    ///
+    /// The total is the number of lookups which were pending when processing
+    /// started.
+    pub fn process_with_progress<F, P>(
+        &mut self,
+        client: &mut Client,
+        mut handle: F,
+        mut on_progress: P,
+    ) -> Result<(), ClientError>
+    where
+        F: FnMut(Res),
+        P: FnMut(Progress),
+    {
+        let total = self.pending.len();
+        let mut done = 0;
+        while let Some(mbid) = self.pending.front().cloned() {
+            if is_cancelled(&self.cancel) {
+                break;
+            }
+
+            let entity = client.get_by_mbid(&mbid)?;
+            handle(entity);
+
+            self.pending.pop_front();
+            self.log_completed(&mbid)?;
+
+            done += 1;
+            on_progress(Progress::new(done, total, &mbid));
+        }
+        if done > 0 {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    /// Append a processed or skipped MBID to the log of completed lookups.
+    ///
+    /// The log isn't synced to disk, an entry lost in a system crash only
+    /// means the entity is processed again.
+    fn log_completed(&mut self, mbid: &Mbid) -> io::Result<()>
+    {
+        if self.done_log.is_none() {
+            let file = OpenOptions::new().create(true).append(true).open(log_path(&self.path))?;
+            self.done_log = Some(file);
+        }
+        if let Some(ref mut log) = self.done_log {
+            writeln!(log, "{}", mbid)?;
+        }
+        Ok(())
+    }
+
+    /// Merge the log of completed lookups into the queue file.
+    fn compact(&mut self) -> io::Result<()>
+    {
+        self.done_log = None;
+        self.save()?;
+        match fs::remove_file(log_path(&self.path)) {
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Write the pending lookups to the file, replacing it atomically so an
+    /// interruption never leaves a truncated queue behind.
+    fn save(&self) -> io::Result<()>
+    {
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        {
+            let mut file = File::create(&tmp_path)?;
+            for mbid in self.pending.iter() {
+                writeln!(file, "{}", mbid)?;
+            }
+            file.sync_all()?;
+        }
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+/// The path of the log of completed lookups of the queue at `path`.
+fn log_path(path: &Path) -> PathBuf
+{
+    let mut log = path.as_os_str().to_owned();
+    log.push(".done");
+    PathBuf::from(log)
+}
+
+/// Read a file of MBIDs, one per line, which is considered empty if it
+/// doesn't exist.
+fn read_mbids(path: &Path) -> io::Result<Vec<Mbid>>
+{
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut mbids = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mbid = line.parse().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid MBID in lookup queue: {}", line),
+            )
+        })?;
+        mbids.push(mbid);
+    }
+    Ok(mbids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::Artist;
+    use std::env;
+    use std::str::FromStr;
+
+    #[test]
+    fn resume_queue()
+    {
+        let path = env::temp_dir().join("musicbrainz_rust_test_resume_queue");
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(log_path(&path));
+
+        let a = Mbid::from_str("90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e").unwrap();
+        let b = Mbid::from_str("650e7db6-b795-4eb5-a702-5ea2fc46c848").unwrap();
+
+        let mut queue = LookupQueue::<Artist>::open(&path).unwrap();
+        assert!(queue.is_empty());
+        queue.extend(vec![a.clone(), b.clone()]).unwrap();
+        assert_eq!(queue.skip().unwrap(), Some(a.clone()));
+
+        // The skipped lookup is only logged, the queue file is compacted when
+        // it's opened again.
+        assert_eq!(read_mbids(&path).unwrap().len(), 2);
+        assert_eq!(read_mbids(&log_path(&path)).unwrap(), vec![a.clone()]);
+
+        let resumed = LookupQueue::<Artist>::open(&path).unwrap();
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed.peek(), Some(&b));
+        assert_eq!(read_mbids(&path).unwrap(), vec![b.clone()]);
+        assert!(!log_path(&path).exists());
+
+        // A cancelled queue doesn't perform any requests.
+        let mut cancelled = resumed;
+        let token = CancellationToken::new();
+        token.cancel();
+        cancelled.cancel_on(token);
+        let mut client = Client::new(::client::ClientConfig {
+            user_agent: "MusicBrainz-Rust/Testing".to_string(),
+            max_retries: 5,
+            waits: ::client::ClientWaits::default(),
+            base_url: Some("http://localhost:1".to_string()),
+            accept_language: None,
+            max_response_size: None,
+        });
+        cancelled.process(&mut client, |_| panic!("not cancelled")).unwrap();
+        assert_eq!(cancelled.len(), 1);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn resume_queue_with_duplicates()
+    {
+        let path = env::temp_dir().join("musicbrainz_rust_test_resume_queue_duplicates");
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(log_path(&path));
+
+        let a = Mbid::from_str("90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e").unwrap();
+        let b = Mbid::from_str("650e7db6-b795-4eb5-a702-5ea2fc46c848").unwrap();
+
+        let mut queue = LookupQueue::<Artist>::open(&path).unwrap();
+        queue.extend(vec![a.clone(), b.clone(), a.clone()]).unwrap();
+        assert_eq!(queue.skip().unwrap(), Some(a.clone()));
+
+        let resumed = LookupQueue::<Artist>::open(&path).unwrap();
+        assert_eq!(read_mbids(&path).unwrap(), vec![b.clone(), a.clone()]);
+        assert_eq!(resumed.len(), 2);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,140 @@
+use reqwest_mock::header::Headers;
+use std::cmp;
+use std::str::{self, FromStr};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The state of the rate limit as reported by the server through the
+/// `X-RateLimit-*` headers of the last response.
+///
+/// Not all responses include these headers, so each of the values is optional.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitStatus {
+    /// The number of requests allowed in the current time window.
+    pub limit: Option<u32>,
+
+    /// The number of requests remaining in the current time window.
+    pub remaining: Option<u32>,
+
+    /// Unix timestamp (in seconds) of the moment the current window ends.
+    pub reset: Option<u64>,
+}
+
+impl RateLimitStatus {
+    /// Parse the status from the raw header values.
+    ///
+    /// Returns `None` if none of the values were provided.
+    fn parse(limit: Option<&str>, remaining: Option<&str>, reset: Option<&str>) -> Option<Self>
+    {
+        fn parse_value<T: FromStr>(value: Option<&str>) -> Option<T>
+        {
+            value.and_then(|v| v.trim().parse().ok())
+        }
+
+        let status = RateLimitStatus {
+            limit: parse_value(limit),
+            remaining: parse_value(remaining),
+            reset: parse_value(reset),
+        };
+
+        if status.limit.is_none() && status.remaining.is_none() && status.reset.is_none() {
+            None
+        } else {
+            Some(status)
+        }
+    }
+
+    /// Extract the status from the headers of a response.
+    pub(crate) fn from_headers(headers: &Headers) -> Option<Self>
+    {
+        let raw = |name: &str| {
+            headers.get_raw(name).and_then(|raw| raw.one()).and_then(
+                |v| str::from_utf8(v).ok(),
+            )
+        };
+
+        Self::parse(
+            raw("X-RateLimit-Limit"),
+            raw("X-RateLimit-Remaining"),
+            raw("X-RateLimit-Reset"),
+        )
+    }
+
+    /// Returns `true` if the server reported that no requests remain in the
+    /// current time window.
+    pub fn is_exhausted(&self) -> bool
+    {
+        self.remaining == Some(0)
+    }
+
+    /// The amount of time until the current time window ends, if known.
+    pub fn time_until_reset(&self) -> Option<Duration>
+    {
+        let reset = UNIX_EPOCH + Duration::from_secs(self.reset?);
+        reset.duration_since(SystemTime::now()).ok()
+    }
+
+    /// The time to wait before the next request, which is the time until the
+    /// current window ends if no requests remain, but at most `max`.
+    ///
+    /// The end of the window is reported by the server, so a wrong timestamp
+    /// far in the future mustn't block the client for hours.
+    pub(crate) fn wait_before_next(&self, max: Duration) -> Option<Duration>
+    {
+        if self.is_exhausted() {
+            self.time_until_reset().map(|wait| cmp::min(wait, max))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_headers()
+    {
+        let status = RateLimitStatus::parse(Some("1200"), Some("1010"), Some("1503663986"));
+        assert_eq!(
+            status,
+            Some(RateLimitStatus {
+                limit: Some(1200),
+                remaining: Some(1010),
+                reset: Some(1503663986),
+            })
+        );
+        assert!(!status.unwrap().is_exhausted());
+    }
+
+    #[test]
+    fn parse_missing_headers()
+    {
+        assert_eq!(RateLimitStatus::parse(None, None, None), None);
+        assert_eq!(RateLimitStatus::parse(Some("x"), None, None), None);
+
+        let status = RateLimitStatus::parse(None, Some("0"), None).unwrap();
+        assert!(status.is_exhausted());
+        assert_eq!(status.time_until_reset(), None);
+    }
+
+    #[test]
+    fn reset_in_past()
+    {
+        let status = RateLimitStatus::parse(None, Some("0"), Some("1503663986")).unwrap();
+        assert_eq!(status.time_until_reset(), None);
+    }
+
+    #[test]
+    fn clamp_wait_for_reset()
+    {
+        let max = Duration::from_secs(60);
+
+        // A reset in the year 2286.
+        let status = RateLimitStatus::parse(None, Some("0"), Some("9999999999")).unwrap();
+        assert_eq!(status.wait_before_next(max), Some(max));
+
+        let status = RateLimitStatus::parse(None, Some("3"), Some("9999999999")).unwrap();
+        assert_eq!(status.wait_before_next(max), None);
+    }
+}
@@ -0,0 +1,105 @@
+use std::cmp::min;
+use std::time::Duration;
+
+use client::ClientConfig;
+
+/// The kind of failure of a request, see `RetryPolicy`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FailureKind {
+    /// The server responded with 503 (ServiceUnavailable), usually because too
+    /// many requests were made.
+    ServiceUnavailable,
+
+    /// The request couldn't be performed, e. g. because the connection to the
+    /// server failed.
+    Http,
+}
+
+/// Decides whether and when failed requests are retried, e. g. to retry
+/// aggressively against a local mirror but not at all against the public
+/// server.
+///
+/// Register an implementation using `Client::set_retry_policy`, otherwise
+/// `ExponentialBackoff::from_config` is used.
+pub trait RetryPolicy: Send {
+    /// The time to wait before retrying a request which failed with `kind`
+    /// for the `attempt`-th time (starting at `1`), or `None` to give up and
+    /// return the error.
+    ///
+    /// Policies which never give up see `attempt` stay at `255` once it is
+    /// reached.
+    fn retry_after(&self, kind: FailureKind, attempt: u8) -> Option<Duration>;
+}
+
+/// Retries requests the server was unavailable for, doubling the wait after
+/// every attempt. Other failures aren't retried.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExponentialBackoff {
+    /// The maximal number of attempts, including the first one.
+    pub max_attempts: u8,
+
+    /// The wait after the first failed attempt.
+    pub initial_wait: Duration,
+}
+
+impl ExponentialBackoff {
+    /// The policy described by `max_retries` and `waits.backoff_init` of the
+    /// configuration of a client.
+    pub fn from_config(config: &ClientConfig) -> Self
+    {
+        ExponentialBackoff {
+            max_attempts: config.max_retries,
+            initial_wait: Duration::from_millis(config.waits.backoff_init),
+        }
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn retry_after(&self, kind: FailureKind, attempt: u8) -> Option<Duration>
+    {
+        if kind == FailureKind::ServiceUnavailable && attempt < self.max_attempts {
+            // Cap the exponent, longer waits aren't useful anyway.
+            Some(self.initial_wait * 2u32.pow(min(attempt as u32 - 1, 16)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Never retries failed requests.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NoRetry;
+
+impl RetryPolicy for NoRetry {
+    fn retry_after(&self, _: FailureKind, _: u8) -> Option<Duration>
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exponential_backoff()
+    {
+        let policy = ExponentialBackoff {
+            max_attempts: 4,
+            initial_wait: Duration::from_millis(400),
+        };
+        let unavailable = FailureKind::ServiceUnavailable;
+        assert_eq!(policy.retry_after(unavailable, 1), Some(Duration::from_millis(400)));
+        assert_eq!(policy.retry_after(unavailable, 2), Some(Duration::from_millis(800)));
+        assert_eq!(policy.retry_after(unavailable, 3), Some(Duration::from_millis(1600)));
+        assert_eq!(policy.retry_after(unavailable, 4), None);
+        assert_eq!(policy.retry_after(FailureKind::Http, 1), None);
+    }
+
+    #[test]
+    fn no_retry()
+    {
+        assert_eq!(NoRetry.retry_after(FailureKind::ServiceUnavailable, 1), None);
+        assert_eq!(NoRetry.retry_after(FailureKind::Http, 1), None);
+    }
+}
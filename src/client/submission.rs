@@ -0,0 +1,135 @@
+use xpath_reader::XpathReader;
+use xpath_reader::reader::XpathStrReader;
+
+use errors::{ClientError, ClientErrorKind};
+
+/// The prefix of the message the server appends to all error responses,
+/// pointing to the documentation instead of describing the problem.
+const USAGE_PREFIX: &'static str = "For usage, please see:";
+
+/// The outcome of a submission to the web service, e. g. of tags, ratings or
+/// ISRCs, as described by the response of the server.
+///
+/// The server explains why it rejected a submission (e. g. an invalid ISRC or
+/// a missing `client` parameter) in the response body, these explanations are
+/// kept so applications can show them to their users.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SubmissionResult {
+    /// The submission was accepted.
+    Accepted {
+        /// The message of the server, usually `"OK"`.
+        message: String,
+    },
+
+    /// The submission was rejected.
+    Rejected {
+        /// The reasons given by the server, in the order of the response.
+        reasons: Vec<String>,
+
+        /// The url of the documentation the server pointed to, if any.
+        help_url: Option<String>,
+    },
+}
+
+impl SubmissionResult {
+    /// Parse the response body of a submission.
+    ///
+    /// Fails if the body is neither a message nor an error document.
+    pub fn parse(xml: &str) -> Result<Self, ClientError>
+    {
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context)?;
+
+        let errors: Vec<String> = reader.read_vec("//error/text")?;
+        if !errors.is_empty() {
+            let (help, reasons): (Vec<String>, Vec<String>) = errors
+                .into_iter()
+                .map(|text| text.trim().to_string())
+                .partition(|text| text.starts_with(USAGE_PREFIX));
+            return Ok(SubmissionResult::Rejected {
+                reasons: reasons,
+                help_url: help.first()
+                    .map(|text| text[USAGE_PREFIX.len()..].trim().to_string()),
+            });
+        }
+
+        let message: Option<String> =
+            reader.read_option("//mb:metadata/mb:message/mb:text/text()")?;
+        match message {
+            Some(message) => Ok(SubmissionResult::Accepted { message: message }),
+            None => Err(
+                ClientErrorKind::UnexpectedContent {
+                    content_type: None,
+                    snippet: xml.chars().take(200).collect(),
+                }.into(),
+            ),
+        }
+    }
+
+    /// Returns `true` if the submission was accepted.
+    pub fn is_accepted(&self) -> bool
+    {
+        match *self {
+            SubmissionResult::Accepted { .. } => true,
+            SubmissionResult::Rejected { .. } => false,
+        }
+    }
+
+    /// The reasons the submission was rejected for, empty if it was accepted.
+    pub fn reasons(&self) -> &[String]
+    {
+        match *self {
+            SubmissionResult::Accepted { .. } => &[][..],
+            SubmissionResult::Rejected { ref reasons, .. } => &reasons[..],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepted()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><message><text>OK</text></message></metadata>"#;
+        let result = SubmissionResult::parse(xml).unwrap();
+
+        assert_eq!(
+            result,
+            SubmissionResult::Accepted {
+                message: "OK".to_string(),
+            }
+        );
+        assert!(result.is_accepted());
+        assert!(result.reasons().is_empty());
+    }
+
+    #[test]
+    fn rejected()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<error>
+  <text>USIR1970129 is not a valid ISRC</text>
+  <text>For usage, please see: http://musicbrainz.org/development/mmd</text>
+</error>"#;
+        let result = SubmissionResult::parse(xml).unwrap();
+
+        assert_eq!(
+            result,
+            SubmissionResult::Rejected {
+                reasons: vec!["USIR1970129 is not a valid ISRC".to_string()],
+                help_url: Some("http://musicbrainz.org/development/mmd".to_string()),
+            }
+        );
+        assert!(!result.is_accepted());
+        assert_eq!(result.reasons().len(), 1);
+    }
+
+    #[test]
+    fn unexpected()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"/>"#;
+        assert!(SubmissionResult::parse(xml).is_err());
+    }
+}
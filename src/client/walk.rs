@@ -0,0 +1,229 @@
+//! Traversal of the graph formed by the relationships between entities.
+
+use std::collections::HashSet;
+
+use client::{CancellationToken, Client};
+use client::cancel::is_cancelled;
+use entities::{Artist, ArtistRef, Label, LabelRef, Mbid, RelType, Relation, RelationTarget};
+use errors::ClientError;
+
+/// Follows the relationships starting at one entity, e. g. to explore the
+/// members of a band and the other bands they are members of.
+///
+/// Every entity is only visited once, so cycles in the graph are no problem.
+/// Each visited entity costs one request, so be careful with larger depths.
+///
+/// Create instances using `Client::walk`.
+pub struct Walk<'cl> {
+    client: &'cl mut Client,
+    start: Mbid,
+    via: Vec<RelType>,
+    depth: u32,
+    cancel: Option<CancellationToken>,
+}
+
+impl<'cl> Walk<'cl> {
+    pub(crate) fn new(client: &'cl mut Client, start: Mbid) -> Self
+    {
+        Walk {
+            client: client,
+            start: start,
+            via: Vec::new(),
+            depth: 1,
+            cancel: None,
+        }
+    }
+
+    /// Only follow relationships of the specified type.
+    ///
+    /// Can be called multiple times to follow multiple types, if it is never
+    /// called relationships of all types are followed.
+    pub fn via(mut self, rel_type: RelType) -> Self
+    {
+        self.via.push(rel_type);
+        self
+    }
+
+    /// The maximal number of relationships between the start and a found
+    /// entity, defaults to 1.
+    pub fn depth(mut self, depth: u32) -> Self
+    {
+        self.depth = depth;
+        self
+    }
+
+    /// Stop walking once the token is cancelled, returning the entities found
+    /// so far.
+    pub fn cancel_on(mut self, token: CancellationToken) -> Self
+    {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Walk from the start artist to related artists and return all artists
+    /// found, not including the start.
+    pub fn collect_artists(self) -> Result<Vec<ArtistRef>, ClientError>
+    {
+        let Walk {
+            client,
+            start,
+            via,
+            depth,
+            cancel,
+        } = self;
+
+        walk_graph(&start, depth, &cancel, |mbid| {
+            let relations = client.get_relations::<Artist>(mbid)?;
+            Ok(
+                followed(relations, &via)
+                    .filter_map(|r| match r.target {
+                        RelationTarget::Artist(artist) => Some((artist.mbid.clone(), artist)),
+                        _ => None,
+                    })
+                    .collect(),
+            )
+        })
+    }
+
+    /// Walk from the start label to related labels and return all labels
+    /// found, not including the start.
+    pub fn collect_labels(self) -> Result<Vec<LabelRef>, ClientError>
+    {
+        let Walk {
+            client,
+            start,
+            via,
+            depth,
+            cancel,
+        } = self;
+
+        walk_graph(&start, depth, &cancel, |mbid| {
+            let relations = client.get_relations::<Label>(mbid)?;
+            Ok(
+                followed(relations, &via)
+                    .filter_map(|r| match r.target {
+                        RelationTarget::Label(label) => Some((label.mbid.clone(), label)),
+                        _ => None,
+                    })
+                    .collect(),
+            )
+        })
+    }
+}
+
+/// Filters the relations which should be followed.
+fn followed<'a>(relations: Vec<Relation>, via: &'a [RelType]) -> Box<Iterator<Item = Relation> + 'a>
+{
+    Box::new(relations.into_iter().filter(move |r| {
+        via.is_empty() || via.iter().any(|t| r.is(t))
+    }))
+}
+
+/// Breadth first search from `start` up to the specified depth, where
+/// `neighbours` returns the adjacent nodes of a node.
+///
+/// Returns the items of all found nodes except for `start`, every node is
+/// only visited once. If the search is cancelled the nodes found until then
+/// are returned.
+fn walk_graph<T, F>(
+    start: &Mbid,
+    depth: u32,
+    cancel: &Option<CancellationToken>,
+    mut neighbours: F,
+) -> Result<Vec<T>, ClientError>
+where
+    F: FnMut(&Mbid) -> Result<Vec<(Mbid, T)>, ClientError>,
+{
+    let mut visited: HashSet<Mbid> = HashSet::new();
+    visited.insert(start.clone());
+
+    let mut found = Vec::new();
+    let mut frontier = vec![start.clone()];
+    for _ in 0..depth {
+        let mut next = Vec::new();
+        for mbid in frontier.iter() {
+            if is_cancelled(cancel) {
+                return Ok(found);
+            }
+            for (neighbour, item) in neighbours(mbid)? {
+                if visited.insert(neighbour.clone()) {
+                    found.push(item);
+                    next.push(neighbour);
+                }
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn mbid(n: u8) -> Mbid
+    {
+        Mbid::from_str(&format!("00000000-0000-0000-0000-0000000000{:02}", n)).unwrap()
+    }
+
+    /// 1 - 2 - 3 - 4 with an additional edge 3 - 1 forming a cycle.
+    fn neighbours(node: &Mbid) -> Result<Vec<(Mbid, u8)>, ClientError>
+    {
+        let edges = [(1, 2), (2, 3), (3, 4), (3, 1)];
+        Ok(
+            edges
+                .iter()
+                .filter_map(|&(a, b)| if mbid(a) == *node {
+                    Some(b)
+                } else if mbid(b) == *node {
+                    Some(a)
+                } else {
+                    None
+                })
+                .map(|n| (mbid(n), n))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn walk_depth()
+    {
+        assert_eq!(walk_graph(&mbid(1), 0, &None, neighbours).unwrap(), Vec::<u8>::new());
+        assert_eq!(walk_graph(&mbid(1), 1, &None, neighbours).unwrap(), vec![2, 3]);
+        assert_eq!(walk_graph(&mbid(1), 2, &None, neighbours).unwrap(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn walk_cycle()
+    {
+        let mut requests = 0;
+        let found = walk_graph(&mbid(1), 10, &None, |node| {
+            requests += 1;
+            neighbours(node)
+        }).unwrap();
+
+        assert_eq!(found, vec![2, 3, 4]);
+        // Every node is only expanded once.
+        assert_eq!(requests, 4);
+    }
+
+    #[test]
+    fn walk_cancel()
+    {
+        let token = CancellationToken::new();
+        let cancel = Some(token.clone());
+        let found = walk_graph(&mbid(1), 10, &cancel, |node| {
+            // Cancel while expanding the first node.
+            token.cancel();
+            neighbours(node)
+        }).unwrap();
+
+        assert_eq!(found, vec![2, 3]);
+    }
+}
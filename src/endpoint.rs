@@ -0,0 +1,234 @@
+//! Construction of the urls of the web service.
+
+use std::fmt;
+
+use entities::Mbid;
+
+/// A version of the MusicBrainz web service.
+///
+/// Only version 2 exists as of now, once there are others the `Endpoint`
+/// will take care of building the urls in the format of the respective
+/// version.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WsVersion {
+    /// `/ws/2`, responding with XML unless JSON was requested with the `fmt`
+    /// parameter.
+    V2,
+}
+
+impl WsVersion {
+    /// The path segments of the version, e. g. `"ws/2"`.
+    pub fn path(&self) -> &'static str
+    {
+        match *self {
+            WsVersion::V2 => "ws/2",
+        }
+    }
+
+    /// Detects the version from the end of the path of a base url.
+    fn from_base_url(base: &str) -> Option<Self>
+    {
+        if base.ends_with("/ws/2") {
+            Some(WsVersion::V2)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for WsVersion {
+    fn default() -> Self
+    {
+        WsVersion::V2
+    }
+}
+
+impl fmt::Display for WsVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.path())
+    }
+}
+
+/// The root of a web service instance, used to build the urls of all
+/// requests.
+///
+/// The urls are requested in the default format of the version, which is
+/// XML for `ws/2` so no `fmt` parameter is sent.
+///
+/// # Examples
+/// ```
+/// # use musicbrainz::endpoint::{Endpoint, WsVersion};
+/// let endpoint = Endpoint::new("https://test.musicbrainz.org", WsVersion::V2);
+/// assert_eq!(endpoint.base_url(), "https://test.musicbrainz.org/ws/2");
+/// assert_eq!(
+///     endpoint.entity_url("artist"),
+///     "https://test.musicbrainz.org/ws/2/artist/"
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Endpoint {
+    base: String,
+    version: WsVersion,
+}
+
+impl Endpoint {
+    /// The endpoint of `version` on the server at `server`, e. g.
+    /// `"https://musicbrainz.org"`.
+    pub fn new(server: &str, version: WsVersion) -> Self
+    {
+        Endpoint {
+            base: format!("{}/{}", server.trim_right_matches('/'), version.path()),
+            version: version,
+        }
+    }
+
+    /// The endpoint with the base url `base`, e. g.
+    /// `"https://musicbrainz.org/ws/2"`.
+    ///
+    /// The version is detected from the url, urls not ending in a version
+    /// (e. g. of a proxy) are used as is and assumed to serve the default
+    /// version.
+    pub fn from_base_url(base: &str) -> Self
+    {
+        let base = base.trim_right_matches('/');
+        Endpoint {
+            base: base.to_string(),
+            version: WsVersion::from_base_url(base).unwrap_or_default(),
+        }
+    }
+
+    /// The base url of the endpoint, without a trailing slash.
+    pub fn base_url(&self) -> &str
+    {
+        self.base.as_str()
+    }
+
+    /// The version of the web service.
+    pub fn version(&self) -> WsVersion
+    {
+        self.version
+    }
+
+    /// The url of an entity type, e. g. `"https://musicbrainz.org/ws/2/artist/"`.
+    ///
+    /// This is the url searches are made at.
+    pub fn entity_url(&self, entity: &str) -> String
+    {
+        format!("{}/{}/", self.base, entity)
+    }
+
+    /// The url to look up the entity with the MBID, with the `inc` parameter
+    /// if `includes` isn't `None`.
+    pub fn lookup_url(&self, entity: &str, mbid: &Mbid, includes: Option<&str>) -> String
+    {
+        match includes {
+            Some(inc) => format!("{}{}?inc={}", self.entity_url(entity), mbid, inc),
+            None => format!("{}{}", self.entity_url(entity), mbid),
+        }
+    }
+
+    /// The url to browse the entities of type `entity` linked to the
+    /// `linked_entity` (e. g. `"area"`) with the MBID.
+    pub fn browse_url(
+        &self,
+        entity: &str,
+        linked_entity: &str,
+        mbid: &Mbid,
+        limit: u8,
+        offset: u32,
+    ) -> String
+    {
+        format!(
+            "{}?{}={}&limit={}&offset={}",
+            self.entity_url(entity),
+            linked_entity,
+            mbid,
+            limit,
+            offset
+        )
+    }
+
+    /// The url listing all entities of type `entity`, e. g. all genres.
+    pub fn all_url(&self, entity: &str, limit: u8, offset: u32) -> String
+    {
+        format!("{}all?limit={}&offset={}", self.entity_url(entity), limit, offset)
+    }
+
+    /// The url to add entities of type `entities` (e. g. `"releases"`) to or
+    /// remove them from the collection with the MBID `collection`.
+    pub fn collection_url(&self, collection: &Mbid, entities: &str, mbids: &[Mbid]) -> String
+    {
+        let mbids: Vec<String> = mbids.iter().map(|mbid| mbid.to_string()).collect();
+        format!("{}{}/{}/{}", self.entity_url("collection"), collection, entities, mbids.join(";"))
+    }
+
+    /// The url of a search with an already encoded query.
+    pub fn search_url(&self, entity: &str, query: &str) -> String
+    {
+        format!("{}?query={}", self.entity_url(entity), query)
+    }
+}
+
+impl Default for Endpoint {
+    fn default() -> Self
+    {
+        Endpoint::from_base_url(::util::DEFAULT_BASE_URL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn detect_version()
+    {
+        let endpoint = Endpoint::from_base_url("http://localhost:5000/ws/2/");
+        assert_eq!(endpoint.base_url(), "http://localhost:5000/ws/2");
+        assert_eq!(endpoint.version(), WsVersion::V2);
+        assert_eq!(endpoint, Endpoint::new("http://localhost:5000/", WsVersion::V2));
+
+        let proxy = Endpoint::from_base_url("http://localhost:1");
+        assert_eq!(proxy.base_url(), "http://localhost:1");
+        assert_eq!(proxy.version(), WsVersion::V2);
+    }
+
+    #[test]
+    fn build_urls()
+    {
+        let endpoint = Endpoint::default();
+        let mbid = Mbid::from_str("2db42837-c832-3c27-b4a3-08198f75693c").unwrap();
+        assert_eq!(
+            endpoint.lookup_url("area", &mbid, Some("")),
+            "https://musicbrainz.org/ws/2/area/2db42837-c832-3c27-b4a3-08198f75693c?inc="
+                .to_string()
+        );
+        assert_eq!(
+            endpoint.lookup_url("area", &mbid, None),
+            "https://musicbrainz.org/ws/2/area/2db42837-c832-3c27-b4a3-08198f75693c".to_string()
+        );
+        assert_eq!(
+            endpoint.search_url("area", "area:Japan"),
+            "https://musicbrainz.org/ws/2/area/?query=area:Japan".to_string()
+        );
+        assert_eq!(
+            endpoint.browse_url("event", "area", &mbid, 100, 200),
+            "https://musicbrainz.org/ws/2/event/?area=2db42837-c832-3c27-b4a3-08198f75693c\
+             &limit=100&offset=200"
+                .to_string()
+        );
+        assert_eq!(
+            endpoint.all_url("genre", 100, 0),
+            "https://musicbrainz.org/ws/2/genre/all?limit=100&offset=0".to_string()
+        );
+        let collection = Mbid::from_str("f4784850-3844-11e0-9e42-0800200c9a66").unwrap();
+        assert_eq!(
+            endpoint.collection_url(&collection, "releases", &[mbid.clone(), mbid]),
+            "https://musicbrainz.org/ws/2/collection/f4784850-3844-11e0-9e42-0800200c9a66/\
+             releases/2db42837-c832-3c27-b4a3-08198f75693c;2db42837-c832-3c27-b4a3-08198f75693c"
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use entities::helper::read_attr_option;
+use xpath_reader::{FromXml, FromXmlError, XpathReader};
+use xpath_reader::reader::FromXmlElement;
+
+/// An alternative name of an entity, e. g. the name in a different language or
+/// script, a legal name or a common misspelling.
+///
+/// Additional information can be found in the [MusicBrainz
+/// docs](https://musicbrainz.org/doc/Aliases).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Alias {
+    /// The alias itself.
+    pub name: String,
+
+    /// Name to properly sort the alias by.
+    pub sort_name: Option<String>,
+
+    /// The locale the alias is used in, e. g. `"ja"` or `"en_US"`.
+    pub locale: Option<String>,
+
+    /// Whether this is the primary alias for its locale, i. e. the name the
+    /// entity is best known by in the locale.
+    pub primary: bool,
+
+    /// The type of the alias, e. g. `"Artist name"`, `"Legal name"` or
+    /// `"Search hint"`.
+    pub alias_type: Option<String>,
+}
+
+impl FromXmlElement for Alias {}
+impl FromXml for Alias {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        let primary: Option<String> = read_attr_option(reader, ".", "primary")?;
+        Ok(Alias {
+            name: reader.read("./text()")?,
+            sort_name: read_attr_option(reader, ".", "sort-name")?,
+            locale: read_attr_option(reader, ".", "locale")?,
+            primary: primary.is_some(),
+            alias_type: read_attr_option(reader, ".", "type")?,
+        })
+    }
+}
+
+/// Normalizes a locale for comparison, so `"en-US"` and `"en_us"` are equal.
+fn normalize_locale(locale: &str) -> String
+{
+    locale.trim().replace('-', "_").to_lowercase()
+}
+
+/// The language part of a normalized locale, e. g. `"en"` for `"en_us"`.
+fn language(locale: &str) -> &str
+{
+    locale.split('_').next().unwrap_or(locale)
+}
+
+/// Picks the alias best suited for displaying the name of an entity in the
+/// specified locale.
+///
+/// Aliases with exactly the requested locale are preferred over aliases for
+/// only the language, which are preferred over aliases for the same language
+/// but a different region. Within these primary aliases are preferred.
+///
+/// Search hints are never picked. Returns `None` if there is no alias for the
+/// language at all.
+pub(crate) fn best_alias<'a>(aliases: &'a [Alias], locale: &str) -> Option<&'a Alias>
+{
+    let wanted = normalize_locale(locale);
+
+    aliases
+        .iter()
+        .filter(|alias| alias.alias_type.as_ref().map(|t| t.as_str()) != Some("Search hint"))
+        .filter_map(|alias| {
+            let alias_locale = match alias.locale {
+                Some(ref l) => normalize_locale(l),
+                None => return None,
+            };
+            let score = if alias_locale == wanted {
+                3
+            } else if alias_locale == language(&wanted) {
+                2
+            } else if language(&alias_locale) == language(&wanted) {
+                1
+            } else {
+                return None;
+            };
+            Some(((score, alias.primary), alias))
+        })
+        // `max_by_key` returns the last maximum, so reverse to keep the order of
+        // the server for equally good aliases.
+        .rev()
+        .max_by_key(|&(key, _)| key)
+        .map(|(_, alias)| alias)
+}
+
+/// Returns `true` if `query` is the name or sort name of an entity or one of
+/// its aliases, including search hints.
+///
+/// The names are compared ignoring case and surrounding whitespace.
+pub(crate) fn is_known_as(name: &str, sort_name: &str, aliases: &[Alias], query: &str) -> bool
+{
+    let query = query.trim().to_lowercase();
+    let matches = |candidate: &str| candidate.trim().to_lowercase() == query;
+
+    matches(name) || matches(sort_name) ||
+        aliases.iter().any(|alias| {
+            matches(&alias.name) || alias.sort_name.as_ref().map_or(false, |s| matches(s))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias(name: &str, locale: Option<&str>, primary: bool, alias_type: Option<&str>) -> Alias
+    {
+        Alias {
+            name: name.to_string(),
+            sort_name: None,
+            locale: locale.map(|l| l.to_string()),
+            primary: primary,
+            alias_type: alias_type.map(|t| t.to_string()),
+        }
+    }
+
+    #[test]
+    fn best_alias_by_locale()
+    {
+        let aliases = vec![
+            alias("Search", Some("en_US"), true, Some("Search hint")),
+            alias("Legal", None, false, Some("Legal name")),
+            alias("British", Some("en_GB"), true, None),
+            alias("English", Some("en"), false, None),
+            alias("Primary English", Some("en"), true, None),
+            alias("Japanese", Some("ja"), true, None),
+        ];
+
+        let best = |locale: &str| best_alias(&aliases, locale).map(|a| a.name.as_str());
+        assert_eq!(best("en"), Some("Primary English"));
+        assert_eq!(best("en-GB"), Some("British"));
+        assert_eq!(best("en_US"), Some("Primary English"));
+        assert_eq!(best("JA"), Some("Japanese"));
+        assert_eq!(best("de"), None);
+    }
+
+    #[test]
+    fn known_as_search_hint()
+    {
+        let aliases = vec![
+            alias("Interscape Records", None, false, Some("Search hint")),
+            alias("Nothing/Interscope", None, false, None),
+        ];
+
+        let known = |query: &str| is_known_as("Interscope Records", "Interscope", &aliases, query);
+        assert!(known("interscope records"));
+        assert!(known("Interscope"));
+        assert!(known(" Interscape Records"));
+        assert!(known("nothing/interscope"));
+        assert!(!known("Interscope Geffen A&M"));
+    }
+}
@@ -2,6 +2,7 @@ use xpath_reader::{FromXml, FromXmlError, XpathReader};
 use xpath_reader::reader::{FromXmlContained, FromXmlElement};
 
 use entities::{Mbid, Resource};
+use entities::helper::read_attr;
 
 enum_mb_xml!{
     /// Specifies what a specific `Area` instance actually is.
@@ -57,6 +58,10 @@ pub struct Area {
 
     /// ISO 3166 code, assigned to countries and subdivisions.
     pub iso_3166: Option<String>,
+
+    /// A disambiguation comment if present, which allows to differentiate this
+    /// area from other areas with the same name.
+    pub disambiguation: Option<String>,
 }
 
 impl FromXmlContained for Area {}
@@ -66,12 +71,13 @@ impl FromXml for Area {
         R: XpathReader<'d>,
     {
         Ok(Area {
-            mbid: reader.read(".//mb:area/@id")?,
-            name: reader.read(".//mb:area/mb:name/text()")?,
-            sort_name: reader.read(".//mb:area/mb:sort-name/text()")?,
-            area_type: reader.read(".//mb:area/@type")?,
+            mbid: read_attr(reader, entity_path!("area", ""), "id")?,
+            name: reader.read(entity_path!("area", "/mb:name/text()"))?,
+            sort_name: reader.read(entity_path!("area", "/mb:sort-name/text()"))?,
+            area_type: read_attr(reader, entity_path!("area", ""), "type")?,
+            disambiguation: reader.read_option(entity_path!("area", "/mb:disambiguation/text()"))?,
             iso_3166: reader.read_option(
-                ".//mb:area/mb:iso-3166-1-code-list/mb:iso-3166-1-code/text()",
+                entity_path!("area", "/mb:iso-3166-1-code-list/mb:iso-3166-1-code/text()"),
             )?,
         })
     }
@@ -94,6 +100,7 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[cfg(feature = "client")]
     #[test]
     fn area_read_xml1()
     {
@@ -107,6 +114,7 @@ mod tests {
         assert_eq!(area.iso_3166, None);
     }
 
+    #[cfg(feature = "client")]
     #[test]
     fn area_read_xml2()
     {
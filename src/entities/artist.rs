@@ -1,9 +1,23 @@
 use xpath_reader::{FromXml, FromXmlError, XpathReader};
 use xpath_reader::reader::{FromXmlContained, FromXmlElement};
 
-use entities::{Mbid, Resource};
+#[cfg(feature = "client")]
+use client::Client;
+#[cfg(feature = "client")]
+use std::collections::BTreeMap;
+#[cfg(feature = "client")]
+use entities::{annotation_text, external_links, Description, DescriptionSource, LinkService};
+use entities::{tag_similarity, Alias, Include, Mbid, Resource, Tag};
+use entities::alias::best_alias;
+use entities::helper::{mark_requested, read_attr, read_attr_option, read_mb_flag,
+                      read_mb_list};
 use entities::date::PartialDate;
-use entities::refs::AreaRef;
+use entities::refs::{AreaRef, ArtistRef};
+use entities::relationships::{filter_relations, Direction, RelType, Relation, RelationTarget};
+#[cfg(feature = "client")]
+use errors::ClientError;
+#[cfg(feature = "wikipedia")]
+use wikipedia::{self, Bio};
 
 enum_mb_xml! {
     /// Specification of the gender of an artist.
@@ -26,6 +40,17 @@ enum_mb_xml! {
     }
 }
 
+/// MBID of the special purpose artist *Various Artists*, which is credited for
+/// releases with many different artists.
+const VARIOUS_ARTISTS_MBID: &'static str = "89ad4ac3-39f7-470e-963a-56509c546377";
+
+/// Returns `true` if the MBID is the one of the special purpose artist
+/// *Various Artists*.
+pub(crate) fn is_various_artists(mbid: &Mbid) -> bool
+{
+    mbid.to_string() == VARIOUS_ARTISTS_MBID
+}
+
 /// A musician, a group or another music professional.
 ///
 /// There are also a couple special purpose artists.
@@ -51,7 +76,11 @@ pub struct Artist {
     /// Aliases of the `Artist`'s name. These include alternative official
     /// spellings, common misspellings, versions in different scripts and
     /// other variations of the `Artist` name.
-    pub aliases: Vec<String>,
+    ///
+    /// Use `localized_name` to pick the name to display for a locale.
+    ///
+    /// `None` if the aliases weren't requested (`Include::Aliases`).
+    pub aliases: Option<Vec<Alias>>,
 
     /// Any additional free form annotation for this `Artist`.
     ///
@@ -71,6 +100,9 @@ pub struct Artist {
 
     /// The area an `Artist` is primarily identified with. Often, but not
     /// always, birth/formation country of the artist/group.
+    ///
+    /// Use `AreaRef::fetch_full` to get the full `Area`, which only performs a
+    /// request if the reference doesn't already contain all of its data.
     pub area: Option<AreaRef>,
 
     /// For a single person: date of birth.
@@ -83,11 +115,21 @@ pub struct Artist {
     /// For a group of people: dissolution date.
     pub end_date: Option<PartialDate>,
 
+    /// Whether the person died or the group dissolved, which can be known even
+    /// if `end_date` isn't.
+    pub ended: bool,
+
     /// [IPI Code](https://wiki.musicbrainz.org/IPI) of the `Artist`.
     pub ipi_code: Option<String>,
 
     /// [ISNI Code](https://wiki.musicbrainz.org/ISNI) of the `Artist`.
     pub isni_code: Option<String>,
+
+    /// The tags users applied to the `Artist`, e. g. its genres.
+    ///
+    /// `None` if the tags weren't requested (`Include::Tags`). Search results
+    /// contain the tags of the found artists.
+    pub tags: Option<Vec<Tag>>,
 }
 
 impl FromXmlContained for Artist {}
@@ -97,23 +139,203 @@ impl FromXml for Artist {
         R: XpathReader<'d>,
     {
         Ok(Artist {
-            aliases: reader.read_vec(".//mb:artist/mb:alias-list/mb:alias/text()")?,
-            annotation: reader.read_option(".//mb:artist/mb:annotation/text()")?,
-            area: reader.read_option(".//mb:artist/mb:area")?,
-            artist_type: reader.read_option(".//mb:artist/@type")?,
-            begin_date: reader.read_option(".//mb:artist/mb:life-span/mb:begin/text()")?,
-            disambiguation: reader.read_option(".//mb:artist/mb:disambiguation/text()")?,
-            end_date: reader.read_option(".//mb:artist/mb:life-span/mb:end/text()")?,
-            gender: reader.read_option(".//mb:artist/mb:gender/text()")?,
-            ipi_code: reader.read_option(".//mb:artist/mb:ipi/text()")?,
-            isni_code: reader.read_option(".//mb:artist/mb:isni-list/mb:isni/text()")?,
-            mbid: reader.read(".//mb:artist/@id")?,
-            name: reader.read(".//mb:artist/mb:name/text()")?,
-            sort_name: reader.read(".//mb:artist/mb:sort-name/text()")?,
+            aliases: read_mb_list(reader, entity_path!("artist", "/mb:alias-list"), "/mb:alias")?,
+            annotation: reader.read_option(entity_path!("artist", "/mb:annotation/text()"))?,
+            area: reader.read_option(entity_path!("artist", "/mb:area"))?,
+            artist_type: read_attr_option(reader, entity_path!("artist", ""), "type")?,
+            begin_date: reader.read_option(
+                entity_path!("artist", "/mb:life-span/mb:begin/text()"),
+            )?,
+            disambiguation: reader.read_option(
+                entity_path!("artist", "/mb:disambiguation/text()"),
+            )?,
+            end_date: reader.read_option(entity_path!("artist", "/mb:life-span/mb:end/text()"))?,
+            ended: read_mb_flag(reader, entity_path!("artist", "/mb:life-span/mb:ended/text()"))?,
+            gender: reader.read_option(entity_path!("artist", "/mb:gender/text()"))?,
+            ipi_code: reader.read_option(entity_path!("artist", "/mb:ipi/text()"))?,
+            isni_code: reader.read_option(entity_path!("artist", "/mb:isni-list/mb:isni/text()"))?,
+            mbid: read_attr(reader, entity_path!("artist", ""), "id")?,
+            name: reader.read(entity_path!("artist", "/mb:name/text()"))?,
+            sort_name: reader.read(entity_path!("artist", "/mb:sort-name/text()"))?,
+            tags: read_mb_list(reader, entity_path!("artist", "/mb:tag-list"), "/mb:tag")?,
         })
     }
 }
 
+/// The membership of a person in a group, as returned by
+/// `Artist::fetch_members` and `Artist::fetch_bands`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Membership {
+    /// The other artist of the membership, i. e. the member when fetching the
+    /// members of a group and the group when fetching the groups of a person.
+    pub artist: ArtistRef,
+
+    /// The date the membership began.
+    pub begin: Option<PartialDate>,
+
+    /// The date the membership ended.
+    pub end: Option<PartialDate>,
+
+    /// Whether the membership has ended.
+    pub ended: bool,
+
+    /// The instruments and vocals of the member, and further attributes like
+    /// `"original"` or `"additional"`.
+    pub attributes: Vec<String>,
+}
+
+impl Artist {
+    /// Returns `true` if this is the special purpose artist *Various Artists*.
+    pub fn is_various_artists(&self) -> bool
+    {
+        is_various_artists(&self.mbid)
+    }
+
+    /// The name of the artist to display to users of the specified locale
+    /// (e. g. `"ja"` or `"en_US"`), which is the best matching alias for the
+    /// locale or the official `name` if there is none.
+    pub fn localized_name(&self, locale: &str) -> &str
+    {
+        best_alias(self.aliases.as_ref().map_or(&[][..], |a| &a[..]), locale)
+            .map(|alias| alias.name.as_str())
+            .unwrap_or(self.name.as_str())
+    }
+
+    /// How similar this artist is to the other one by the overlap of their
+    /// tags, see `tag_similarity`.
+    ///
+    /// Returns `None` if the tags of either artist weren't fetched.
+    pub fn similarity(&self, other: &Artist) -> Option<f32>
+    {
+        match (&self.tags, &other.tags) {
+            (&Some(ref a), &Some(ref b)) => Some(tag_similarity(a, b)),
+            _ => None,
+        }
+    }
+
+    /// Fetch the members of this group, including former ones, in the order
+    /// provided by the server.
+    #[cfg(feature = "client")]
+    pub fn fetch_members(&self, client: &mut Client) -> Result<Vec<Membership>, ClientError>
+    {
+        let relations = client.get_relations::<Artist>(&self.mbid)?;
+        Ok(memberships(&relations, Direction::Backward))
+    }
+
+    /// Fetch the groups this person is or was a member of.
+    #[cfg(feature = "client")]
+    pub fn fetch_bands(&self, client: &mut Client) -> Result<Vec<Membership>, ClientError>
+    {
+        let relations = client.get_relations::<Artist>(&self.mbid)?;
+        Ok(memberships(&relations, Direction::Forward))
+    }
+
+    /// Fetch the links of this artist to other websites, e. g. its official
+    /// homepage or its Discogs page, grouped by the service they point to.
+    #[cfg(feature = "client")]
+    pub fn fetch_external_links(
+        &self,
+        client: &mut Client,
+    ) -> Result<BTreeMap<LinkService, Vec<String>>, ClientError>
+    {
+        let relations = client.get_relations::<Artist>(&self.mbid)?;
+        Ok(external_links(&relations))
+    }
+
+    /// Fetch the introduction of the Wikipedia article about this artist in
+    /// the specified language (e. g. `"en"`), if the artist is linked to a
+    /// Wikidata item which has such an article.
+    #[cfg(feature = "wikipedia")]
+    pub fn fetch_bio(&self, client: &mut Client, language: &str) -> Result<Option<Bio>, ClientError>
+    {
+        let relations = client.get_relations::<Artist>(&self.mbid)?;
+        wikipedia::fetch_bio(client, &relations, language)
+    }
+
+    /// A plain text description of the artist, e. g. for an "about" section.
+    ///
+    /// The annotation is preferred, it is fetched if this artist was looked
+    /// up without `Include::Annotation`. Artists without an annotation are
+    /// described by the Wikipedia article in the specified language (see
+    /// `fetch_bio`) if the `wikipedia` feature is enabled. The source of the
+    /// text is provided for attribution.
+    #[cfg(feature = "client")]
+    pub fn description(
+        &self,
+        client: &mut Client,
+        language: &str,
+    ) -> Result<Option<Description>, ClientError>
+    {
+        let annotation = match self.annotation {
+            Some(ref annotation) => Some(annotation.clone()),
+            None => {
+                client
+                    .get_by_mbid_with_includes::<Artist>(&self.mbid, &[Include::Annotation])?
+                    .annotation
+            }
+        };
+        if let Some(annotation) = annotation {
+            let text = annotation_text(&annotation);
+            if !text.is_empty() {
+                return Ok(Some(Description {
+                    text: text,
+                    source: DescriptionSource::Annotation,
+                }));
+            }
+        }
+
+        self.wikipedia_description(client, language)
+    }
+
+    #[cfg(feature = "wikipedia")]
+    fn wikipedia_description(
+        &self,
+        client: &mut Client,
+        language: &str,
+    ) -> Result<Option<Description>, ClientError>
+    {
+        Ok(self.fetch_bio(client, language)?.map(|bio| {
+            Description {
+                text: bio.extract,
+                source: DescriptionSource::Wikipedia {
+                    title: bio.title,
+                    language: bio.language,
+                    url: bio.url,
+                },
+            }
+        }))
+    }
+
+    #[cfg(all(feature = "client", not(feature = "wikipedia")))]
+    fn wikipedia_description(
+        &self,
+        _client: &mut Client,
+        _language: &str,
+    ) -> Result<Option<Description>, ClientError>
+    {
+        Ok(None)
+    }
+}
+
+/// The relations from a person to a group are forward, so the members of a
+/// group are found by the backward ones.
+fn memberships(relations: &[Relation], direction: Direction) -> Vec<Membership>
+{
+    filter_relations(relations, &[RelType::MemberOf], direction)
+        .into_iter()
+        .filter_map(|r| match r.target {
+            RelationTarget::Artist(ref artist) => Some(Membership {
+                artist: artist.clone(),
+                begin: r.begin.clone(),
+                end: r.end.clone(),
+                ended: r.ended,
+                attributes: r.attributes.iter().map(|a| a.name.clone()).collect(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
 impl Resource for Artist {
     fn get_name() -> &'static str
     {
@@ -124,6 +346,12 @@ impl Resource for Artist {
     {
         "aliases"
     }
+
+    fn mark_requested(&mut self, includes: &[Include])
+    {
+        mark_requested(&mut self.aliases, includes, &[Include::Aliases]);
+        mark_requested(&mut self.tags, includes, &[Include::Tags]);
+    }
 }
 
 #[cfg(test)]
@@ -131,6 +359,7 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[cfg(feature = "client")]
     #[test]
     fn artist_read_xml1()
     {
@@ -141,7 +370,8 @@ mod tests {
         assert_eq!(artist.mbid, mbid);
         assert_eq!(artist.name, "NECRONOMIDOL".to_string());
         assert_eq!(artist.sort_name, "NECRONOMIDOL".to_string());
-        assert_eq!(artist.aliases, Vec::<String>::new());
+        assert_eq!(artist.aliases, Some(Vec::new()));
+        assert_eq!(artist.localized_name("ja"), "NECRONOMIDOL");
 
         assert_eq!(
             artist.begin_date,
@@ -164,6 +394,7 @@ mod tests {
         assert_eq!(artist.isni_code, None);
     }
 
+    #[cfg(feature = "client")]
     #[test]
     fn artist_read_xml2()
     {
@@ -173,7 +404,8 @@ mod tests {
         assert_eq!(artist.mbid, mbid);
         assert_eq!(artist.name, "Lady Gaga".to_string());
         assert_eq!(artist.sort_name, "Lady Gaga".to_string());
-        let mut aliases_sorted = artist.aliases.clone();
+        let mut aliases_sorted: Vec<String> =
+            artist.aliases.unwrap().iter().map(|alias| alias.name.clone()).collect();
         aliases_sorted.sort();
         assert_eq!(
             aliases_sorted,
@@ -202,6 +434,42 @@ mod tests {
         assert_eq!(artist.gender, Some(Gender::Female));
         assert_eq!(artist.ipi_code, Some("00519338344".to_string()));
         assert_eq!(artist.isni_code, Some("0000000120254559".to_string()));
+        assert_eq!(artist.tags, None);
+        assert!(!artist.is_various_artists());
+    }
+
+    #[test]
+    fn various_artists()
+    {
+        let va = Mbid::from_str("89ad4ac3-39f7-470e-963a-56509c546377").unwrap();
+        let other = Mbid::from_str("650e7db6-b795-4eb5-a702-5ea2fc46c848").unwrap();
+
+        assert!(is_various_artists(&va));
+        assert!(!is_various_artists(&other));
     }
 
+
+    #[test]
+    fn band_memberships()
+    {
+        use xpath_reader::XpathStrReader;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><artist id="a74b1b7f-71a5-4011-9441-d0b5e4122711" type="Group"><name>Radiohead</name><relation-list target-type="artist"><relation type="member of band"><target>8bfac288-ccc5-448d-9573-c33ea2aa5c30</target><direction>backward</direction><attribute-list><attribute>original</attribute><attribute>lead vocals</attribute></attribute-list><begin>1985</begin><artist id="8bfac288-ccc5-448d-9573-c33ea2aa5c30" type="Person"><name>Thom Yorke</name><sort-name>Yorke, Thom</sort-name></artist></relation><relation type="collaboration"><target>6b5b6d3a-0c0e-4b4a-a4b7-8cbd0a3e1f84</target><direction>backward</direction><artist id="6b5b6d3a-0c0e-4b4a-a4b7-8cbd0a3e1f84"><name>Somebody</name><sort-name>Somebody</sort-name></artist></relation></relation-list></artist></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let relations: Vec<Relation> = reader.read_vec("//mb:relation-list/mb:relation").unwrap();
+
+        let members = memberships(&relations, Direction::Backward);
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].artist.name, "Thom Yorke".to_string());
+        assert_eq!(members[0].begin, Some(PartialDate::from_str("1985").unwrap()));
+        assert_eq!(members[0].end, None);
+        assert!(!members[0].ended);
+        assert_eq!(
+            members[0].attributes,
+            vec!["original".to_string(), "lead vocals".to_string()]
+        );
+
+        assert_eq!(memberships(&relations, Direction::Forward), Vec::new());
+    }
 }
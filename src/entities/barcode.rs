@@ -0,0 +1,252 @@
+use entities::refs::ReleaseRef;
+
+/// How the barcode of a release found by `Client::find_releases_by_barcode`
+/// differs from the searched one.
+///
+/// Scanned barcodes frequently differ from the ones in the database in
+/// exactly these ways, e. g. because a scanner reports the 12 digit UPC-A as
+/// 13 digit EAN-13 or because a user typed the barcode without its leading
+/// zero or check digit.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BarcodeVariant {
+    /// The barcode is the searched one.
+    AsSearched,
+
+    /// A leading zero was added, e. g. to a UPC-A to get the EAN-13.
+    LeadingZeroAdded,
+
+    /// The leading zero was removed, e. g. from an EAN-13 to get the UPC-A.
+    LeadingZeroRemoved,
+
+    /// The missing check digit was added.
+    CheckDigitAdded,
+
+    /// The wrong check digit was replaced by the correct one.
+    CheckDigitCorrected,
+}
+
+/// A release found by its barcode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BarcodeMatch {
+    /// The found release.
+    pub release: ReleaseRef,
+
+    /// The barcode of the release as stored in the database.
+    pub barcode: String,
+
+    /// How the barcode differs from the searched one.
+    pub variant: BarcodeVariant,
+}
+
+/// Computes the GTIN (UPC, EAN) check digit for the digits of a barcode
+/// without its check digit.
+///
+/// Returns `None` if there are no digits or the string contains other
+/// characters.
+pub fn barcode_check_digit(digits: &str) -> Option<u8>
+{
+    if digits.is_empty() {
+        return None;
+    }
+
+    let mut sum = 0;
+    // The digit next to the check digit is weighted with 3, the one before
+    // with 1 and so on.
+    for (i, c) in digits.chars().rev().enumerate() {
+        let digit = match c.to_digit(10) {
+            Some(d) => d,
+            None => return None,
+        };
+        sum += if i % 2 == 0 { digit * 3 } else { digit };
+    }
+    Some(((10 - sum % 10) % 10) as u8)
+}
+
+/// Returns `true` if the last digit of the barcode is its correct check digit.
+fn has_valid_check_digit(barcode: &str) -> bool
+{
+    let (digits, check) = barcode.split_at(barcode.len() - 1);
+    match (barcode_check_digit(digits), check.parse::<u8>()) {
+        (Some(expected), Ok(check)) => expected == check,
+        _ => false,
+    }
+}
+
+/// The variants of a scanned or typed barcode which are tried when searching
+/// for it, the barcode itself first.
+///
+/// Spaces and dashes are removed. If the barcode consists of anything else
+/// than digits afterwards, only the barcode itself is returned.
+pub fn barcode_variants(barcode: &str) -> Vec<(String, BarcodeVariant)>
+{
+    use self::BarcodeVariant::*;
+
+    let code: String = barcode
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect();
+    if code.is_empty() {
+        return Vec::new();
+    }
+
+    let mut variants = vec![(code.clone(), AsSearched)];
+    if !code.chars().all(|c| c.is_digit(10)) {
+        return variants;
+    }
+
+    let len = code.len();
+    if len == 11 || len == 12 {
+        variants.push((format!("0{}", code), LeadingZeroAdded));
+    }
+    if len == 13 && code.starts_with('0') {
+        variants.push((code[1..].to_string(), LeadingZeroRemoved));
+    }
+    if !has_valid_check_digit(&code) {
+        if len == 8 || len == 12 || len == 13 {
+            let digits = &code[..len - 1];
+            let check = barcode_check_digit(digits).unwrap();
+            variants.push((format!("{}{}", digits, check), CheckDigitCorrected));
+        }
+        if len == 7 || len == 11 || len == 12 {
+            let check = barcode_check_digit(&code).unwrap();
+            variants.push((format!("{}{}", code, check), CheckDigitAdded));
+        }
+    }
+
+    let mut unique: Vec<(String, BarcodeVariant)> = Vec::new();
+    for variant in variants {
+        if !unique.iter().any(|&(ref code, _)| *code == variant.0) {
+            unique.push(variant);
+        }
+    }
+    unique
+}
+
+/// Matches the found releases with their barcodes to the searched variants,
+/// dropping releases whose barcode isn't one of them.
+///
+/// The matches are ordered like the variants, so releases with the searched
+/// barcode come first.
+pub(crate) fn match_barcodes(
+    found: Vec<(ReleaseRef, Option<String>)>,
+    variants: &[(String, BarcodeVariant)],
+) -> Vec<BarcodeMatch>
+{
+    let mut matches: Vec<(usize, BarcodeMatch)> = found
+        .into_iter()
+        .filter_map(|(release, barcode)| {
+            let barcode = match barcode {
+                Some(b) => b,
+                None => return None,
+            };
+            let normalized: String = barcode
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != '-')
+                .collect();
+            variants
+                .iter()
+                .position(|&(ref code, _)| *code == normalized)
+                .map(|i| {
+                    (
+                        i,
+                        BarcodeMatch {
+                            release: release,
+                            barcode: barcode,
+                            variant: variants[i].1,
+                        },
+                    )
+                })
+        })
+        .collect();
+    matches.sort_by_key(|&(i, _)| i);
+    matches.into_iter().map(|(_, m)| m).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::BarcodeVariant::*;
+
+    fn codes(barcode: &str) -> Vec<(String, BarcodeVariant)>
+    {
+        barcode_variants(barcode)
+    }
+
+    fn variant(code: &str, variant: BarcodeVariant) -> (String, BarcodeVariant)
+    {
+        (code.to_string(), variant)
+    }
+
+    #[test]
+    fn check_digit()
+    {
+        assert_eq!(barcode_check_digit("72438802342"), Some(9));
+        assert_eq!(barcode_check_digit("506500208100"), Some(1));
+        assert_eq!(barcode_check_digit("9638507"), Some(4));
+        assert_eq!(barcode_check_digit(""), None);
+        assert_eq!(barcode_check_digit("12a4"), None);
+    }
+
+    #[test]
+    fn variants()
+    {
+        // A valid UPC-A, which is also stored as EAN-13.
+        assert_eq!(
+            codes("724388023429"),
+            vec![variant("724388023429", AsSearched), variant("0724388023429", LeadingZeroAdded)]
+        );
+        // A valid EAN-13 with a leading zero, which could be stored as UPC-A.
+        assert_eq!(
+            codes("0724388023429"),
+            vec![
+                variant("0724388023429", AsSearched),
+                variant("724388023429", LeadingZeroRemoved),
+            ]
+        );
+        // A UPC-A typed without its leading zero.
+        assert_eq!(
+            codes("7 5678 16412 5"),
+            vec![variant("75678164125", AsSearched), variant("075678164125", LeadingZeroAdded)]
+        );
+        // A UPC-A without its check digit.
+        assert!(codes("72438802342").contains(&variant("724388023429", CheckDigitAdded)));
+        // A mistyped check digit.
+        assert!(codes("5065002081-002").contains(&variant("5065002081001", CheckDigitCorrected)));
+        assert_eq!(codes("5065002081001").len(), 1);
+
+        assert_eq!(codes("B00005Q3N9"), vec![variant("B00005Q3N9", AsSearched)]);
+        assert_eq!(codes(" "), vec![]);
+    }
+
+    #[test]
+    fn match_found_releases()
+    {
+        let release = |mbid: &str| ReleaseRef {
+            mbid: mbid.parse().unwrap(),
+            title: "OK Computer".to_string(),
+            date: None,
+            status: None,
+            country: None,
+            formats: Vec::new(),
+        };
+        let found = vec![
+            (release("0b6b4ba0-d36f-47bd-b4ea-6a5b91842d29"), Some("0724385522925".to_string())),
+            (release("61f8b05f-a3b5-49f4-a3a6-8f0d564c1664"), None),
+            (release("b84ee12a-09ef-421b-82de-0441a926375b"), Some("724385522925".to_string())),
+            (release("95cdb0f3-cc23-44a6-8f9b-7ef3ba6f4ab7"), Some("4988006751583".to_string())),
+        ];
+
+        let matches = match_barcodes(found, &codes("724385522925"));
+        let matched: Vec<(String, BarcodeVariant)> = matches
+            .into_iter()
+            .map(|m| (m.release.mbid.to_string(), m.variant))
+            .collect();
+        assert_eq!(
+            matched,
+            vec![
+                variant("b84ee12a-09ef-421b-82de-0441a926375b", AsSearched),
+                variant("0b6b4ba0-d36f-47bd-b4ea-6a5b91842d29", LeadingZeroAdded),
+            ]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+/// The catalog number MusicBrainz stores for releases which were explicitly
+/// issued without one.
+const NO_CATALOG_NUMBER: &'static str = "[none]";
+
+/// Normalizes a catalog number for comparison, e. g. `"cdr-6078"` and
+/// `"CDR 6078"` both become `"CDR6078"`.
+///
+/// Letters are converted to upper case and whitespace as well as the
+/// separators `-`, `.`, `_` and `/` are removed, since these are typed in all
+/// kinds of ways. All other characters are kept.
+pub fn normalize_catalog_number(catalog_number: &str) -> String
+{
+    catalog_number
+        .chars()
+        .filter(|c| !c.is_whitespace() && !"-._/".contains(*c))
+        .flat_map(|c| c.to_uppercase())
+        .collect()
+}
+
+/// The parts of a normalized catalog number, alternating runs of digits and
+/// other characters. Runs of digits are stored without their leading zeros.
+fn catalog_parts(normalized: &str) -> Vec<String>
+{
+    let mut parts: Vec<String> = Vec::new();
+    let mut last_digit = None;
+    for c in normalized.chars() {
+        let digit = c.is_digit(10);
+        if last_digit != Some(digit) {
+            parts.push(String::new());
+            last_digit = Some(digit);
+        }
+        parts.last_mut().unwrap().push(c);
+    }
+
+    for part in parts.iter_mut() {
+        if part.chars().all(|c| c.is_digit(10)) {
+            let trimmed = part.trim_left_matches('0');
+            *part = if trimmed.is_empty() { "0" } else { trimmed }.to_string();
+        }
+    }
+    parts
+}
+
+/// Returns `true` if the two catalog numbers are the same, tolerating the
+/// variations humans commonly introduce when typing them.
+///
+/// Besides the differences removed by `normalize_catalog_number`, the numbers
+/// may differ in leading zeros, so `"SR-012"` matches `"sr12"`. Empty catalog
+/// numbers and MusicBrainz' `"[none]"` never match anything.
+pub fn catalog_numbers_match(a: &str, b: &str) -> bool
+{
+    if a.trim().eq_ignore_ascii_case(NO_CATALOG_NUMBER) ||
+        b.trim().eq_ignore_ascii_case(NO_CATALOG_NUMBER)
+    {
+        return false;
+    }
+
+    let a = normalize_catalog_number(a);
+    let b = normalize_catalog_number(b);
+    if a.is_empty() || b.is_empty() {
+        false
+    } else {
+        a == b || catalog_parts(&a) == catalog_parts(&b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize()
+    {
+        assert_eq!(normalize_catalog_number("CDR 6078"), "CDR6078");
+        assert_eq!(normalize_catalog_number(" cdr-6078 "), "CDR6078");
+        assert_eq!(normalize_catalog_number("VICL-6.0001/2"), "VICL600012");
+        assert_eq!(normalize_catalog_number("ésp_1001"), "ÉSP1001");
+        assert_eq!(normalize_catalog_number("BIRD #4"), "BIRD#4");
+    }
+
+    #[test]
+    fn tolerant_comparison()
+    {
+        assert!(catalog_numbers_match("CDR 6078", "cdr-6078"));
+        assert!(catalog_numbers_match("SR-012", "sr12"));
+        assert!(catalog_numbers_match("WARPCD 092", "warpcd92"));
+        assert!(catalog_numbers_match("0001", "1"));
+
+        assert!(!catalog_numbers_match("CDR 6078", "CDR 6079"));
+        assert!(!catalog_numbers_match("SR-120", "SR-12"));
+        assert!(!catalog_numbers_match("12 SR", "SR 12"));
+        assert!(!catalog_numbers_match("[none]", "[none]"));
+        assert!(!catalog_numbers_match("", " - "));
+    }
+}
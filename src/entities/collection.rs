@@ -0,0 +1,178 @@
+use entities::{Area, AreaRef, Artist, ArtistRef, Event, EventRef, Label, LabelRef, Mbid, Place,
+               PlaceRef, Recording, RecordingRef, Release, ReleaseRef, Resource, Work, WorkRef};
+use entities::helper::{read_attr, read_attr_option};
+use xpath_reader::{FromXml, FromXmlError, XpathReader};
+use xpath_reader::reader::{FromXmlContained, FromXmlElement};
+
+#[cfg(feature = "client")]
+use client::Client;
+#[cfg(feature = "client")]
+use errors::{ClientError, ClientErrorKind};
+
+enum_mb_xml! {
+    /// The type of the entities a collection holds, every collection holds
+    /// entities of only one type.
+    pub enum CollectionEntity {
+        var Area = "area",
+        var Artist = "artist",
+        var Event = "event",
+        var Instrument = "instrument",
+        var Label = "label",
+        var Place = "place",
+        var Recording = "recording",
+        var Release = "release",
+        var ReleaseGroup = "release_group",
+        var Series = "series",
+        var Work = "work",
+    }
+}
+
+/// A list of entities compiled by an editor, e. g. the releases they own.
+///
+/// Additional information can be found in the [MusicBrainz
+/// docs](https://musicbrainz.org/doc/Collections).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Collection {
+    /// MBID of the entity in the MusicBrainz database.
+    pub mbid: Mbid,
+
+    /// The name of the collection.
+    pub name: String,
+
+    /// The name of the editor who compiled the collection.
+    pub editor: String,
+
+    /// The type of the entities in the collection.
+    pub entity_type: CollectionEntity,
+
+    /// The number of entities in the collection.
+    pub count: u32,
+}
+
+impl Collection {
+    /// Fetch all entities in the collection, which must hold entities of type
+    /// `Res`.
+    ///
+    /// The entities are returned as references, e. g. `ArtistRef`s for
+    /// `collection.contents::<Artist>(&mut client)`. Private collections can
+    /// only be read with `Session::collection_contents`.
+    #[cfg(feature = "client")]
+    pub fn contents<Res>(&self, client: &mut Client) -> Result<Vec<Res::Ref>, ClientError>
+    where
+        Res: Collectable,
+    {
+        self.check_holds::<Res>()?;
+        ::client::fetch_collection_contents::<Res>(client, &self.mbid)
+    }
+
+    /// Returns an error if the collection doesn't hold entities of type
+    /// `Res`.
+    #[cfg(feature = "client")]
+    pub(crate) fn check_holds<Res>(&self) -> Result<(), ClientError>
+    where
+        Res: Collectable,
+    {
+        if self.entity_type == Res::collection_entity() {
+            Ok(())
+        } else {
+            Err(
+                ClientErrorKind::CollectionEntityMismatch {
+                    collection: self.entity_type.clone(),
+                    requested: Res::collection_entity(),
+                }.into(),
+            )
+        }
+    }
+}
+
+impl FromXmlContained for Collection {}
+impl FromXml for Collection {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        Ok(Collection {
+            mbid: read_attr(reader, ".//mb:collection", "id")?,
+            name: reader.read(".//mb:collection/mb:name/text()")?,
+            editor: reader.read(".//mb:collection/mb:editor/text()")?,
+            entity_type: read_attr(reader, ".//mb:collection", "entity-type")?,
+            count: read_attr_option(reader, ".//mb:collection/*[@count]", "count")?
+                .unwrap_or(0),
+        })
+    }
+}
+
+impl Resource for Collection {
+    fn get_name() -> &'static str
+    {
+        "collection"
+    }
+
+    fn get_incs() -> &'static str
+    {
+        ""
+    }
+}
+
+/// Entities which can be held by a `Collection`.
+pub trait Collectable: Resource {
+    /// The type the entities in a collection are read as.
+    type Ref: FromXml + FromXmlElement;
+
+    /// The type of the collections holding this entity.
+    fn collection_entity() -> CollectionEntity;
+}
+
+macro_rules! impl_collectable {
+    ( $( $type:ty => $reference:ty, $entity:ident );+ ) => {
+        $(
+            impl Collectable for $type {
+                type Ref = $reference;
+
+                fn collection_entity() -> CollectionEntity
+                {
+                    CollectionEntity::$entity
+                }
+            }
+        )+
+    }
+}
+
+impl_collectable!(
+    Area => AreaRef, Area;
+    Artist => ArtistRef, Artist;
+    Event => EventRef, Event;
+    Label => LabelRef, Label;
+    Place => PlaceRef, Place;
+    Recording => RecordingRef, Recording;
+    Release => ReleaseRef, Release;
+    Work => WorkRef, Work
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::FromXmlStr;
+    use std::str::FromStr;
+
+    #[test]
+    fn read_collection()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><collection type="Artist" entity-type="artist" type-id="0f2f7d6d-2b7b-3b0c-a4a8-0b0a3c9f6bd0" id="9b2b4c88-8d27-4c5c-9f5a-1c0e0c8d0f35"><name>Favourite artists</name><editor>example</editor><artist-list count="42"/></collection></metadata>"#;
+        let collection = Collection::from_xml_str(xml).unwrap();
+
+        assert_eq!(
+            collection.mbid,
+            Mbid::from_str("9b2b4c88-8d27-4c5c-9f5a-1c0e0c8d0f35").unwrap()
+        );
+        assert_eq!(collection.name, "Favourite artists".to_string());
+        assert_eq!(collection.editor, "example".to_string());
+        assert_eq!(collection.entity_type, CollectionEntity::Artist);
+        assert_eq!(collection.count, 42);
+        assert_eq!(Artist::collection_entity(), CollectionEntity::Artist);
+        assert_eq!(
+            "release_group".parse::<CollectionEntity>().unwrap(),
+            CollectionEntity::ReleaseGroup
+        );
+    }
+}
@@ -18,6 +18,10 @@ pub struct PartialDate {
     day: Option<u8>,
 }
 
+/// Former name of `PartialDate`.
+#[deprecated(note = "Use `PartialDate` instead.")]
+pub type Date = PartialDate;
+
 /// Represents a fully specified date.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FullDate {
@@ -26,6 +30,17 @@ pub struct FullDate {
     day: u8,
 }
 
+/// A time of day, as used for the start time of events.
+///
+/// Note that MusicBrainz doesn't store a timezone with the time, it's the
+/// local time at the place where the event happened. So times of different
+/// events can only be compared if they happened in the same timezone.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+}
+
 impl PartialDate {
     pub fn new(year: Option<u16>, month: Option<u8>, day: Option<u8>) -> PartialDate
     {
@@ -159,6 +174,66 @@ impl Display for PartialDate {
     }
 }
 
+impl Time {
+    /// Create a new `Time`, returns `None` if the hour or minute is out of
+    /// range.
+    pub fn new(hour: u8, minute: u8) -> Option<Time>
+    {
+        if hour < 24 && minute < 60 {
+            Some(Time {
+                hour: hour,
+                minute: minute,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn hour(&self) -> u8
+    {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8
+    {
+        self.minute
+    }
+}
+
+impl FromStr for Time {
+    type Err = ParseDateError;
+
+    /// Parses a time in the `HH:MM` format, seconds (`HH:MM:SS`) are accepted
+    /// but ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let ps: Vec<u8> = s.split(':').map(|x| x.parse()).collect::<Result<_, _>>()?;
+        if ps.len() != 2 && ps.len() != 3 {
+            return Err(ParseDateError::WrongNumberOfComponents(ps.len()));
+        }
+        Time::new(ps[0], ps[1]).ok_or(ParseDateError::ComponentOutOfRange)
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error>
+    {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+impl FromXml for Time {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        use xpath_reader::errors::ChainXpathErr;
+        String::from_xml(reader)?.parse().chain_err(|| "Parse Time error").map_err(
+            |e| FromXmlError::from(e),
+        )
+    }
+}
+
 impl FromXml for PartialDate {
     fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
     where
@@ -171,13 +246,16 @@ impl FromXml for PartialDate {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseDateError {
     /// A wrong number of `-` separated components was found in the string.
     WrongNumberOfComponents(usize),
 
     /// Failed parsing a component into the appropriate number type.
     ComponentInvalid(ParseIntError),
+
+    /// A component is out of its valid range, e. g. a minute of `61`.
+    ComponentOutOfRange,
 }
 
 impl Error for ParseDateError {
@@ -187,6 +265,7 @@ impl Error for ParseDateError {
         match *self {
             WrongNumberOfComponents(_) => "wrong number of components",
             ComponentInvalid(_) => "invalid component",
+            ComponentOutOfRange => "component out of range",
         }
     }
 }
@@ -202,6 +281,7 @@ impl Display for ParseDateError {
                 write!(f, "ParseDateError: Wrong number of components: {}", n)
             }
             ComponentInvalid(ref err) => write!(f, "ParseDateError: Component invalid: {:?}", err),
+            ComponentOutOfRange => write!(f, "ParseDateError: Component out of range"),
         }
     }
 }
@@ -213,52 +293,74 @@ impl From<ParseIntError> for ParseDateError {
     }
 }
 
-/*
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const DATE_1: Date = Date::Year { year: 2017 };
-    const DATE_2: Date = Date::Month {
-        year: 2017,
-        month: 4,
-    };
-    const DATE_3: Date = Date::Day {
-        year: 2017,
-        month: 4,
-        day: 15,
-    };
+    fn date_1() -> PartialDate
+    {
+        PartialDate::new(Some(2017), None, None)
+    }
+
+    fn date_2() -> PartialDate
+    {
+        PartialDate::new(Some(2017), Some(4), None)
+    }
+
+    fn date_3() -> PartialDate
+    {
+        PartialDate::new(Some(2017), Some(4), Some(15))
+    }
 
     #[test]
     fn parse_valid()
     {
-        let date1 = Date::from_str("2017").unwrap();
-        let date2 = Date::from_str("2017-4").unwrap();
-        let date3 = Date::from_str("2017-04-15").unwrap();
+        let date1 = PartialDate::from_str("2017").unwrap();
+        let date2 = PartialDate::from_str("2017-4").unwrap();
+        let date3 = PartialDate::from_str("2017-04-15").unwrap();
+
+        assert_eq!(date1, date_1());
+        assert_eq!(date2, date_2());
+        assert_eq!(date3, date_3());
+    }
+
+    #[test]
+    fn parse_time()
+    {
+        assert_eq!(Time::from_str("20:00").unwrap(), Time::new(20, 0).unwrap());
+        assert_eq!(Time::from_str("09:30:00").unwrap(), Time::new(9, 30).unwrap());
+        assert_eq!(Time::from_str("9:05").unwrap().to_string(), "09:05".to_string());
+
+        assert_eq!(Time::from_str("24:00"), Err(ParseDateError::ComponentOutOfRange));
+        assert_eq!(Time::from_str("20"), Err(ParseDateError::WrongNumberOfComponents(1)));
+        assert!(Time::from_str("20:xx").is_err());
+    }
 
-        assert_eq!(date1, DATE_1);
-        assert_eq!(date2, DATE_2);
-        assert_eq!(date3, DATE_3);
+    #[test]
+    fn parse_unknown_components()
+    {
+        let date = PartialDate::from_str("????-04-??").unwrap();
+        assert_eq!(date, PartialDate::new(None, Some(4), None));
     }
 
     #[test]
     fn accessors()
     {
-        assert_eq!(DATE_1.year(), 2017);
-        assert_eq!(DATE_1.month(), 0);
-        assert_eq!(DATE_1.day(), 0);
-        assert_eq!(DATE_2.year(), 2017);
-        assert_eq!(DATE_2.month(), 4);
-        assert_eq!(DATE_2.day(), 0);
-        assert_eq!(DATE_3.year(), 2017);
-        assert_eq!(DATE_3.month(), 4);
-        assert_eq!(DATE_3.day(), 15);
+        assert_eq!(date_1().year(), Some(2017));
+        assert_eq!(date_1().month(), None);
+        assert_eq!(date_1().day(), None);
+        assert_eq!(date_2().year(), Some(2017));
+        assert_eq!(date_2().month(), Some(4));
+        assert_eq!(date_2().day(), None);
+        assert_eq!(date_3().year(), Some(2017));
+        assert_eq!(date_3().month(), Some(4));
+        assert_eq!(date_3().day(), Some(15));
     }
 
     #[test]
     fn wrong_number_comps()
     {
-        let fail = Date::from_str("1-1-1-1");
+        let fail = PartialDate::from_str("1-1-1-1");
         assert_eq!(
             fail.err().unwrap(),
             ParseDateError::WrongNumberOfComponents(4)
@@ -268,9 +370,9 @@ mod tests {
     #[test]
     fn invalid_components()
     {
-        let fail1 = Date::from_str("abc");
-        let fail2 = Date::from_str("2017-abc");
-        let fail3 = Date::from_str("2017-04-abc");
+        let fail1 = PartialDate::from_str("abc");
+        let fail2 = PartialDate::from_str("2017-abc");
+        let fail3 = PartialDate::from_str("2017-04-abc");
 
         let err = ParseDateError::from("abc".parse::<u16>().err().unwrap());
 
@@ -282,8 +384,20 @@ mod tests {
     #[test]
     fn to_string()
     {
-        assert_eq!(DATE_1.to_string(), "2017".to_string());
-        assert_eq!(DATE_2.to_string(), "2017-04".to_string());
-        assert_eq!(DATE_3.to_string(), "2017-04-15".to_string());
+        assert_eq!(date_1().to_string(), "2017-??-??".to_string());
+        assert_eq!(date_2().to_string(), "2017-04-??".to_string());
+        assert_eq!(date_3().to_string(), "2017-04-15".to_string());
+
+        // The string representation can be parsed again.
+        assert_eq!(PartialDate::from_str(&date_1().to_string()).unwrap(), date_1());
+        assert_eq!(PartialDate::from_str(&date_2().to_string()).unwrap(), date_2());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_alias()
+    {
+        let date: Date = "2017-04-15".parse().unwrap();
+        assert_eq!(date, date_3());
     }
-}*/
+}
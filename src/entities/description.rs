@@ -0,0 +1,95 @@
+/// Where the text of a `Description` is taken from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DescriptionSource {
+    /// The annotation of the entity in the MusicBrainz database.
+    Annotation,
+
+    /// The introduction of a Wikipedia article, which has to be attributed
+    /// when displayed.
+    Wikipedia {
+        /// The title of the article.
+        title: String,
+        /// The language code of the Wikipedia, e. g. `"en"`.
+        language: String,
+        /// The url of the article.
+        url: String,
+    },
+}
+
+/// A plain text description of an entity, e. g. for an "about" section, see
+/// `Artist::description`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Description {
+    /// The description as plain text.
+    pub text: String,
+
+    /// Where the text is taken from.
+    pub source: DescriptionSource,
+}
+
+/// Converts an annotation from the wiki markup used by MusicBrainz to plain
+/// text.
+///
+/// Bold and italic markers are removed, headings are reduced to their text
+/// and links to their label (or their url if they have none). Everything
+/// else, e. g. list markers, is kept.
+pub fn annotation_text(annotation: &str) -> String
+{
+    let lines: Vec<String> = annotation
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let line = if line.len() > 1 && line.starts_with('=') && line.ends_with('=') {
+                line.trim_matches('=').trim()
+            } else {
+                line
+            };
+            strip_links(&line.replace("'''", "").replace("''", ""))
+        })
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+/// Replaces links like `[http://example.com|Example]` by their label.
+fn strip_links(line: &str) -> String
+{
+    let mut text = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        let end = match rest[start..].find(']') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let link = &rest[start + 1..end];
+        text.push_str(&rest[..start]);
+        text.push_str(link.splitn(2, '|').last().unwrap_or(link).trim());
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_annotation()
+    {
+        let annotation = "== Biography ==\n\
+                          '''Boris''' is a Japanese band formed in ''1992''.\n\
+                          \n\
+                          See [http://www.borisheavyrocks.com|their website] and \
+                          [http://example.com].";
+        assert_eq!(
+            annotation_text(annotation),
+            "Biography\n\
+             Boris is a Japanese band formed in 1992.\n\
+             \n\
+             See their website and http://example.com."
+                .to_string()
+        );
+        assert_eq!(annotation_text(" \n "), "".to_string());
+        assert_eq!(annotation_text("[unclosed link"), "[unclosed link".to_string());
+    }
+}
@@ -1,8 +1,10 @@
 use xpath_reader::{FromXml, FromXmlError, XpathReader};
 use xpath_reader::reader::{FromXmlContained, FromXmlElement};
 
-use entities::{Mbid, Resource};
-use entities::date::PartialDate;
+use entities::{Include, Mbid, Resource};
+use entities::helper::{mark_requested, read_attr, read_attr_option, read_mb_flag,
+                      read_mb_list};
+use entities::date::{PartialDate, Time};
 
 enum_mb_xml! {
     pub enum EventType {
@@ -28,7 +30,9 @@ pub struct Event {
     pub name: String,
 
     /// Aternative event names.
-    pub aliases: Vec<String>,
+    ///
+    /// `None` if the aliases weren't requested (`Include::Aliases`).
+    pub aliases: Option<Vec<String>>,
 
     /// Describes what type of event this is exactly.
     pub event_type: Option<EventType>,
@@ -39,12 +43,18 @@ pub struct Event {
     /// yet implemented.
     pub setlist: Option<String>,
 
-    /// Begin date of the event.
-    pub begin_date: PartialDate,
+    /// Begin date of the event, `None` if it is unknown.
+    pub begin_date: Option<PartialDate>,
 
     /// End date of the event.
     pub end_date: Option<PartialDate>,
 
+    /// Whether the event is over, which can be known even if `end_date` isn't.
+    pub ended: bool,
+
+    /// The time the event starts, in the local time of the event's location.
+    pub time: Option<Time>,
+
     /// Additional disambiguation if there are multiple `Event`s with the same
     /// name.
     pub disambiguation: Option<String>,
@@ -53,6 +63,61 @@ pub struct Event {
     pub annotation: Option<String>,
 }
 
+impl Event {
+    /// Returns `true` if the event takes place (at least partially) between
+    /// `from` and `to`, both inclusive.
+    ///
+    /// Partial dates cover all of their days, e. g. an event on 2015-12-31
+    /// takes place between `2015` and `2015`. Events without a begin date or
+    /// without a year never match, neither do any events if `from` or `to`
+    /// have no year.
+    pub fn overlaps(&self, from: &PartialDate, to: &PartialDate) -> bool
+    {
+        let begin = match self.begin_date {
+            Some(ref begin) => begin,
+            None => return false,
+        };
+        let end = self.end_date.as_ref().unwrap_or(begin);
+        match (first_day(begin), last_day(end), first_day(from), last_day(to)) {
+            (Some(begin), Some(end), Some(from), Some(to)) => begin <= to && from <= end,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the event takes place (at least partially) during
+    /// the period, e. g. a year or a month.
+    ///
+    /// # Examples
+    /// ```
+    /// # use musicbrainz::entities::{Event, PartialDate};
+    /// fn in_2015(events: Vec<Event>) -> Vec<Event>
+    /// {
+    ///     let year = PartialDate::new(Some(2015), None, None);
+    ///     events.into_iter().filter(|e| e.happens_during(&year)).collect()
+    /// }
+    /// ```
+    pub fn happens_during(&self, period: &PartialDate) -> bool
+    {
+        self.overlaps(period, period)
+    }
+}
+
+/// The first day covered by a partial date as `(year, month, day)`.
+fn first_day(date: &PartialDate) -> Option<(u16, u8, u8)>
+{
+    date.year().map(|year| (year, date.month().unwrap_or(1), date.day().unwrap_or(1)))
+}
+
+/// The last day covered by a partial date as `(year, month, day)`, the day
+/// isn't necessarily valid but compares correctly.
+fn last_day(date: &PartialDate) -> Option<(u16, u8, u8)>
+{
+    date.year().map(|year| match date.month() {
+        Some(month) => (year, month, date.day().unwrap_or(31)),
+        None => (year, 12, 31),
+    })
+}
+
 impl Resource for Event {
     fn get_name() -> &'static str
     {
@@ -63,6 +128,11 @@ impl Resource for Event {
     {
         "aliases+annotation"
     }
+
+    fn mark_requested(&mut self, includes: &[Include])
+    {
+        mark_requested(&mut self.aliases, includes, &[Include::Aliases]);
+    }
 }
 
 impl FromXmlContained for Event {}
@@ -72,15 +142,25 @@ impl FromXml for Event {
         R: XpathReader<'d>,
     {
         Ok(Event {
-            mbid: reader.read(".//mb:event/@id")?,
-            name: reader.read(".//mb:event/mb:name")?,
-            aliases: reader.read_vec(".//mb:event/mb:alias-list/mb:alias/text()")?,
-            event_type: reader.read_option(".//mb:event/@type")?,
-            setlist: reader.read_option(".//mb:event/mb:setlist")?,
-            begin_date: reader.read(".//mb:event/mb:life-span/mb:begin")?,
-            end_date: reader.read_option(".//mb:event/mb:life-span/mb:end")?,
-            disambiguation: reader.read_option(".//mb:event/mb:disambiguation")?,
-            annotation: reader.read_option(".//mb:event/mb:annotation/mb:text/text()")?,
+            mbid: read_attr(reader, entity_path!("event", ""), "id")?,
+            name: reader.read(entity_path!("event", "/mb:name/text()"))?,
+            aliases: read_mb_list(
+                reader,
+                entity_path!("event", "/mb:alias-list"),
+                "/mb:alias/text()",
+            )?,
+            event_type: read_attr_option(reader, entity_path!("event", ""), "type")?,
+            setlist: reader.read_option(entity_path!("event", "/mb:setlist/text()"))?,
+            begin_date: reader.read_option(
+                entity_path!("event", "/mb:life-span/mb:begin/text()"),
+            )?,
+            end_date: reader.read_option(entity_path!("event", "/mb:life-span/mb:end/text()"))?,
+            ended: read_mb_flag(reader, entity_path!("event", "/mb:life-span/mb:ended/text()"))?,
+            time: reader.read_option(entity_path!("event", "/mb:time/text()"))?,
+            disambiguation: reader.read_option(entity_path!("event", "/mb:disambiguation/text()"))?,
+            annotation: reader.read_option(
+                entity_path!("event", "/mb:annotation/mb:text/text()"),
+            )?,
         })
     }
 }
@@ -90,6 +170,7 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[cfg(feature = "client")]
     #[test]
     fn read_1()
     {
@@ -98,15 +179,16 @@ mod tests {
 
         assert_eq!(event.mbid, mbid);
         assert_eq!(event.name, "25. Wave-Gotik-Treffen".to_string());
-        assert_eq!(event.aliases, vec!["WGT 2016".to_string()]);
+        assert_eq!(event.aliases, Some(vec!["WGT 2016".to_string()]));
         assert_eq!(event.event_type, Some(EventType::Festival));
         assert_eq!(event.setlist, None);
-        assert_eq!(event.begin_date, "2016-05-13".parse().unwrap());
+        assert_eq!(event.begin_date, Some("2016-05-13".parse().unwrap()));
         assert_eq!(event.end_date.unwrap(), "2016-05-16".parse().unwrap());
         assert_eq!(event.disambiguation, None);
         assert_eq!(event.annotation.unwrap().len(), 2233);
     }
 
+    #[cfg(feature = "client")]
     #[test]
     fn read_2()
     {
@@ -115,5 +197,34 @@ mod tests {
 
         assert_eq!(event.event_type, Some(EventType::Concert));
         assert_eq!(event.setlist.unwrap().len(), 225);
+        assert_eq!(event.time, None);
+    }
+
+    #[test]
+    fn date_ranges()
+    {
+        use entities::FromXmlStr;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><event id="6e2ab7d5-f340-4c41-99a3-c901733402b4" type="Festival"><name>Countdown Japan 15/16</name><life-span><begin>2015-12-28</begin><end>2016-01-02</end></life-span></event></metadata>"#;
+        let event = Event::from_xml_str(xml).unwrap();
+        let date = |s: &str| PartialDate::from_str(s).unwrap();
+
+        assert!(event.happens_during(&date("2015")));
+        assert!(event.happens_during(&date("2016-01")));
+        assert!(event.happens_during(&date("2015-12-31")));
+        assert!(!event.happens_during(&date("2015-11")));
+        assert!(event.overlaps(&date("2014"), &date("2015-12-28")));
+        assert!(!event.overlaps(&date("2016-01-03"), &date("2017")));
+        assert!(!event.happens_during(&PartialDate::new(None, Some(12), None)));
+    }
+
+    #[test]
+    fn read_time()
+    {
+        use entities::FromXmlStr;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><event id="9754f4dd-6fad-49b7-8f30-940c9af6b776" type="Concert"><name>Lady Gaga at Roseland Ballroom</name><life-span><begin>2014-03-28</begin><end>2014-03-28</end></life-span><time>20:00</time></event></metadata>"#;
+        let event = Event::from_xml_str(xml).unwrap();
+        assert_eq!(event.time, Time::new(20, 0));
     }
 }
@@ -0,0 +1,146 @@
+use entities::Mbid;
+use entities::helper::read_attr;
+use xpath_reader::{FromXml, FromXmlError, XpathReader};
+use xpath_reader::reader::FromXmlElement;
+
+#[cfg(feature = "client")]
+use client::Client;
+#[cfg(feature = "client")]
+use errors::ClientError;
+
+/// A genre, one of the tags MusicBrainz considers canonical genre names.
+///
+/// The list of all genres can be fetched with `Client::genres`, additional
+/// information can be found in the [MusicBrainz
+/// docs](https://musicbrainz.org/doc/Genre).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Genre {
+    /// MBID of the entity in the MusicBrainz database.
+    pub mbid: Mbid,
+
+    /// The name of the genre, always lower case, e. g. `"synth-pop"`.
+    pub name: String,
+
+    /// The disambiguation comment of the genre, if any.
+    pub disambiguation: Option<String>,
+}
+
+impl Genre {
+    /// Maps a free-text tag (e. g. `"Synthpop"`) to the genre it names, using
+    /// the genre list cached by the client.
+    ///
+    /// Returns `None` if the tag doesn't name a genre, see `resolve_genre`
+    /// for how tags are compared.
+    #[cfg(feature = "client")]
+    pub fn resolve(tag: &str, client: &mut Client) -> Result<Option<Genre>, ClientError>
+    {
+        let genres = client.genres()?;
+        Ok(resolve_genre(tag, genres).cloned())
+    }
+}
+
+impl FromXmlElement for Genre {}
+impl FromXml for Genre {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        Ok(Genre {
+            mbid: read_attr(reader, ".", "id")?,
+            name: reader.read("./mb:name/text()")?,
+            disambiguation: reader.read_option("./mb:disambiguation/text()")?,
+        })
+    }
+}
+
+/// Normalizes a genre name or tag for comparison, e. g. `" Hip-Hop"` becomes
+/// `"hip hop"`.
+///
+/// Letters are converted to lower case, `-`, `_` and `/` are replaced by
+/// spaces, `&` is replaced by `and` and consecutive whitespace is collapsed.
+pub fn normalize_genre_name(name: &str) -> String
+{
+    let name = name.to_lowercase().replace('&', " and ");
+    let words: Vec<&str> = name
+        .split(|c: char| c.is_whitespace() || "-_/".contains(c))
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.join(" ")
+}
+
+/// Finds the genre named by a free-text tag in the `genres`.
+///
+/// The names are compared after `normalize_genre_name`, so `"Drum & Bass"`
+/// resolves to `"drum and bass"`. If that doesn't find a genre, spaces are
+/// ignored as well, so `"synthpop"` resolves to `"synth-pop"`.
+pub fn resolve_genre<'g>(tag: &str, genres: &'g [Genre]) -> Option<&'g Genre>
+{
+    let tag = normalize_genre_name(tag);
+    if tag.is_empty() {
+        return None;
+    }
+
+    let exact = genres.iter().find(|genre| normalize_genre_name(&genre.name) == tag);
+    if exact.is_some() {
+        return exact;
+    }
+
+    let compact = tag.replace(' ', "");
+    genres
+        .iter()
+        .find(|genre| normalize_genre_name(&genre.name).replace(' ', "") == compact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use xpath_reader::XpathStrReader;
+    use entities::helper::read_mb_vec;
+
+    fn genre(name: &str) -> Genre
+    {
+        Genre {
+            mbid: Mbid::from_str("89255676-1f14-4dd8-bbad-fca839d6aff4").unwrap(),
+            name: name.to_string(),
+            disambiguation: None,
+        }
+    }
+
+    #[test]
+    fn read_genres()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><genre-list count="2"><genre id="89255676-1f14-4dd8-bbad-fca839d6aff4"><name>electronic</name></genre><genre id="b2d3ad75-3e19-4f1b-8b5d-b9bbd9b70a7d"><name>shoegaze</name><disambiguation>rock subgenre</disambiguation></genre></genre-list></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let genres: Vec<Genre> = read_mb_vec(&reader, "//mb:genre-list/mb:genre").unwrap();
+
+        assert_eq!(genres.len(), 2);
+        assert_eq!(genres[0], genre("electronic"));
+        assert_eq!(genres[1].name, "shoegaze".to_string());
+        assert_eq!(genres[1].disambiguation, Some("rock subgenre".to_string()));
+    }
+
+    #[test]
+    fn normalize()
+    {
+        assert_eq!(normalize_genre_name(" Hip-Hop"), "hip hop");
+        assert_eq!(normalize_genre_name("Drum & Bass"), "drum and bass");
+        assert_eq!(normalize_genre_name("drum_n_bass"), "drum n bass");
+        assert_eq!(normalize_genre_name("Rock/Pop"), "rock pop");
+    }
+
+    #[test]
+    fn resolve()
+    {
+        let genres = vec![genre("shoegaze"), genre("synth-pop"), genre("drum and bass")];
+        let name = |tag: &str| resolve_genre(tag, &genres).map(|g| g.name.as_str());
+
+        assert_eq!(name("shoegaze"), Some("shoegaze"));
+        assert_eq!(name("Shoe Gaze"), Some("shoegaze"));
+        assert_eq!(name("synthpop"), Some("synth-pop"));
+        assert_eq!(name("Drum & Bass"), Some("drum and bass"));
+        assert_eq!(name("seen live"), None);
+        assert_eq!(name(" "), None);
+    }
+}
@@ -1,6 +1,11 @@
 use std::time::Duration;
-use xpath_reader::{FromXmlError, XpathReader};
+use entities::{Include, Language};
+use xpath_reader::{FromXml, FromXmlError, XpathReader};
 
+/// Besides the enum itself this generates implementations of `FromXml`,
+/// `FromStr` and `Display`, all using the canonical MusicBrainz strings, and an
+/// `ALL_VARIANTS` constant listing all variants in declaration order.
+///
 /// Note that the requirement of the `var` (variant) token is rather ugly but
 /// required,
 /// which is a limitation of the current Rust macro implementation.
@@ -47,6 +52,27 @@ macro_rules! enum_mb_xml
             }
         }
 
+        impl $enum {
+            /// All variants of this enum, in declaration order.
+            pub const ALL_VARIANTS: &'static [$enum] = &[ $( $enum::$variant , )+ ];
+        }
+
+        impl ::std::str::FromStr for $enum {
+            type Err = ::errors::ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err>
+            {
+                match s {
+                    $(
+                        $str => Ok($enum::$variant),
+                    )+
+                    s => Err(::errors::ParseError::InvalidData(
+                        format!("Unknown `{}` value: '{}'", stringify!($enum), s),
+                    )),
+                }
+            }
+        }
+
         impl ::std::fmt::Display for $enum {
             fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result
             {
@@ -61,6 +87,44 @@ macro_rules! enum_mb_xml
     }
 }
 
+/// Builds the XPath of `$path` relative to the first `$element` element at or
+/// below the context node.
+///
+/// Entities which are parsed both from lookup documents and from the entries
+/// of search results use this, since the reader points to the document in the
+/// former case and to the element itself in the latter.
+macro_rules! entity_path {
+    ($element:expr, $path:expr) => {
+        concat!("./descendant-or-self::mb:", $element, "[1]", $path)
+    };
+}
+
+/// Implements `HasMbid` and a `Hash` based on the MBID for types with an
+/// `mbid` field.
+///
+/// Hashing only the MBID is consistent with the derived `PartialEq` since
+/// equal values always have equal MBIDs.
+macro_rules! impl_mbid_identity
+{
+    ( $( $type:ty ),+ ) => {
+        $(
+            impl ::entities::HasMbid for $type {
+                fn mbid(&self) -> &::entities::Mbid
+                {
+                    &self.mbid
+                }
+            }
+
+            impl ::std::hash::Hash for $type {
+                fn hash<H: ::std::hash::Hasher>(&self, state: &mut H)
+                {
+                    ::std::hash::Hash::hash(&self.mbid, state)
+                }
+            }
+        )+
+    }
+}
+
 pub fn read_mb_duration<'d, R>(reader: &'d R, path: &str) -> Result<Option<Duration>, FromXmlError>
 where
     R: XpathReader<'d>,
@@ -70,3 +134,221 @@ where
         None => Ok(None),
     }
 }
+
+/// Reads a flag like the `ended` of a life span, which is only present if it is
+/// `true`.
+pub fn read_mb_flag<'d, R>(reader: &'d R, path: &str) -> Result<bool, FromXmlError>
+where
+    R: XpathReader<'d>,
+{
+    let flag: Option<String> = reader.read_option(path)?;
+    Ok(flag.as_ref().map(|s| s.as_str()) == Some("true"))
+}
+
+/// The XPath expression selecting the attribute `attr` (e. g. `"id"` or
+/// `"ext:score"`) of the element at `element`.
+fn attr_path(element: &str, attr: &str) -> String
+{
+    format!("{}/@{}", element.trim_right_matches('/'), attr.trim_left_matches('@'))
+}
+
+/// Reads the attribute `attr` of the element at `element`, e. g.
+/// `read_attr(reader, ".//mb:artist", "id")` for the MBID of an artist.
+pub fn read_attr<'d, R, V>(reader: &'d R, element: &str, attr: &str) -> Result<V, FromXmlError>
+where
+    R: XpathReader<'d>,
+    V: FromXml,
+{
+    reader.read(attr_path(element, attr).as_str())
+}
+
+/// Like `read_attr`, but returns `None` if the element or the attribute is
+/// missing.
+pub fn read_attr_option<'d, R, V>(
+    reader: &'d R,
+    element: &str,
+    attr: &str,
+) -> Result<Option<V>, FromXmlError>
+where
+    R: XpathReader<'d>,
+    V: FromXml,
+{
+    reader.read_option(attr_path(element, attr).as_str())
+}
+
+/// Reads all items matched by the XPath expression, like `read_vec` does.
+///
+/// In contrast to `read_vec` an error while reading one of the items is
+/// annotated with the position of the item and the expression, e. g. "item 37
+/// of .//mb:release-list/mb:release: ...", which is essential to make sense of
+/// errors occurring while parsing long lists.
+pub fn read_mb_vec<'d, R, V>(reader: &'d R, path: &str) -> Result<Vec<V>, FromXmlError>
+where
+    R: XpathReader<'d>,
+    V: FromXml,
+{
+    let count = reader.evaluate(format!("count({})", path).as_str())?.number() as usize;
+
+    (1..count + 1)
+        .map(|i| {
+            reader.read(format!("({})[{}]", path, i).as_str()).map_err(|e| {
+                let msg = match e {
+                    FromXmlError::Absent => "missing value".to_string(),
+                    e => e.to_string(),
+                };
+                FromXmlError::from(format!("item {} of {}: {}", i, path, msg))
+            })
+        })
+        .collect()
+}
+
+/// Reads the items of a list which is only part of the response if it was
+/// requested by an include, e. g. the aliases of an artist.
+///
+/// Returns `None` if the list element at `list` is missing, otherwise the items
+/// at `list` followed by `item` (read as by `read_mb_vec`).
+///
+/// The web service omits some lists if they are empty even if they were
+/// requested, see `mark_requested` for how these are distinguished.
+pub fn read_mb_list<'d, R, V>(
+    reader: &'d R,
+    list: &str,
+    item: &str,
+) -> Result<Option<Vec<V>>, FromXmlError>
+where
+    R: XpathReader<'d>,
+    V: FromXml,
+{
+    let count = reader.evaluate(format!("count({})", list).as_str())?.number();
+    if count == 0. {
+        Ok(None)
+    } else {
+        read_mb_vec(reader, format!("{}{}", list, item).as_str()).map(Some)
+    }
+}
+
+/// An XPath predicate matching release elements of pseudo-releases, e. g. to
+/// read them separately from the other releases of a list.
+pub const PSEUDO_RELEASE: &'static str = "mb:status = 'Pseudo-Release'";
+
+/// The ISO 639-3 code MusicBrainz uses for works without lyrics.
+const NO_LYRICS: &'static str = "zxx";
+
+/// Reads the lyrics languages of the work element at `work` and whether it is
+/// marked as having no lyrics, as used by the full and the search `Work`.
+///
+/// Works with a single language have it both in `language` and in the
+/// `language-list`, older responses only have the former. Codes which aren't
+/// languages, like `"zxx"` (no lyrics) or `"mul"`, are skipped.
+pub fn read_lyrics_languages<'d, R>(
+    reader: &'d R,
+    work: &str,
+) -> Result<(Vec<Language>, bool), FromXmlError>
+where
+    R: XpathReader<'d>,
+{
+    let mut codes: Vec<String> =
+        reader.read_vec(format!("{}/mb:language-list/mb:language/text()", work).as_str())?;
+    if codes.is_empty() {
+        codes = reader.read_vec(format!("{}/mb:language/text()", work).as_str())?;
+    }
+    let languages = codes
+        .iter()
+        .filter(|code| code.as_str() != NO_LYRICS)
+        .filter_map(|code| Language::from_639_3(code).ok())
+        .collect();
+    Ok((languages, codes.iter().any(|code| code == NO_LYRICS)))
+}
+
+/// Marks a list read by `read_mb_list` as fetched if one of the `required`
+/// includes is contained in `includes`, i. e. a missing list becomes empty.
+pub fn mark_requested<T>(list: &mut Option<Vec<T>>, includes: &[Include], required: &[Include])
+{
+    if list.is_none() && required.iter().any(|r| includes.contains(r)) {
+        *list = Some(Vec::new());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::ReleaseRef;
+    use xpath_reader::XpathStrReader;
+
+    const XML: &'static str = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release-list count="2"><release id="289bf4e7-0af5-433c-b5a2-493b863b4b47"><title>Mixtape</title></release><release id="d3d2a860-0093-461d-8d95-b77939c2e944"></release></release-list></metadata>"#;
+
+    #[test]
+    fn enum_round_trip()
+    {
+        use entities::{AreaType, ReleaseStatus};
+        use std::str::FromStr;
+
+        for variant in ReleaseStatus::ALL_VARIANTS {
+            assert_eq!(
+                &ReleaseStatus::from_str(variant.to_string().as_str()).unwrap(),
+                variant
+            );
+        }
+
+        assert_eq!(AreaType::ALL_VARIANTS[0], AreaType::Country);
+        assert_eq!(AreaType::from_str("City").unwrap(), AreaType::City);
+        assert!(AreaType::from_str("city").is_err());
+        assert!(AreaType::from_str("").is_err());
+    }
+
+    #[test]
+    fn read_attributes()
+    {
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(XML, &context).unwrap();
+
+        assert_eq!(attr_path(".//mb:release/", "@id"), ".//mb:release/@id".to_string());
+        let count: u32 = read_attr(&reader, "//mb:release-list", "count").unwrap();
+        assert_eq!(count, 2);
+        let offset: Option<u32> = read_attr_option(&reader, "//mb:release-list", "offset").unwrap();
+        assert_eq!(offset, None);
+    }
+
+    #[test]
+    fn read_vec_with_context()
+    {
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(XML, &context).unwrap();
+
+        let first: Vec<ReleaseRef> =
+            read_mb_vec(&reader, "//mb:release-list/mb:release[mb:title]").unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].title, "Mixtape".to_string());
+
+        let none: Vec<ReleaseRef> = read_mb_vec(&reader, "//mb:recording-list/mb:recording")
+            .unwrap();
+        assert_eq!(none.len(), 0);
+
+        let err = read_mb_vec::<_, ReleaseRef>(&reader, "//mb:release-list/mb:release")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains(
+            "item 2 of //mb:release-list/mb:release",
+        ));
+    }
+
+    #[test]
+    fn read_optional_list()
+    {
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(XML, &context).unwrap();
+
+        let releases: Option<Vec<ReleaseRef>> =
+            read_mb_list(&reader, "//mb:release-list", "/mb:release[mb:title]").unwrap();
+        assert_eq!(releases.unwrap().len(), 1);
+
+        let mut recordings: Option<Vec<ReleaseRef>> =
+            read_mb_list(&reader, "//mb:recording-list", "/mb:recording").unwrap();
+        assert_eq!(recordings, None);
+
+        mark_requested(&mut recordings, &[Include::Aliases], &[Include::Recordings]);
+        assert_eq!(recordings, None);
+        mark_requested(&mut recordings, &[Include::Recordings], &[Include::Recordings]);
+        assert_eq!(recordings, Some(Vec::new()));
+    }
+}
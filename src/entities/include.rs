@@ -0,0 +1,297 @@
+use std::error::Error;
+use std::fmt;
+
+/// Additional data which can be requested when looking up an entity, these are
+/// the values of the `inc` parameter of the web service.
+///
+/// Not all includes are valid for all entities, consult the [MusicBrainz
+/// docs](https://musicbrainz.org/doc/Development/XML_Web_Service/Version_2#Lookups)
+/// for details.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Include {
+    Aliases,
+    Annotation,
+    ArtistCredits,
+    Artists,
+    DiscIds,
+    Isrcs,
+    Labels,
+    /// The mediums of a release with their formats and track counts, but
+    /// without the tracks themselves.
+    ///
+    /// This results in a much lighter document than `Recordings`, the
+    /// `ReleaseMedium`s will report `is_partial()`.
+    Media,
+    /// The mediums of a release including their track lists.
+    Recordings,
+    ReleaseGroups,
+    Releases,
+    Tags,
+    Ratings,
+    /// The tags the authenticated user applied, requires authentication.
+    UserTags,
+    /// The rating of the authenticated user, requires authentication.
+    UserRatings,
+    Works,
+}
+
+impl Include {
+    /// The value of the include as used in the `inc` parameter.
+    pub fn as_str(&self) -> &'static str
+    {
+        match *self {
+            Include::Aliases => "aliases",
+            Include::Annotation => "annotation",
+            Include::ArtistCredits => "artist-credits",
+            Include::Artists => "artists",
+            Include::DiscIds => "discids",
+            Include::Isrcs => "isrcs",
+            Include::Labels => "labels",
+            Include::Media => "media",
+            Include::Recordings => "recordings",
+            Include::ReleaseGroups => "release-groups",
+            Include::Releases => "releases",
+            Include::Tags => "tags",
+            Include::Ratings => "ratings",
+            Include::UserTags => "user-tags",
+            Include::UserRatings => "user-ratings",
+            Include::Works => "works",
+        }
+    }
+
+    /// Returns `true` if the include requires authentication, requests with
+    /// it fail otherwise.
+    pub fn is_privileged(&self) -> bool
+    {
+        match *self {
+            Include::UserTags | Include::UserRatings => true,
+            _ => false,
+        }
+    }
+
+    /// Joins the includes into the value of an `inc` parameter.
+    pub(crate) fn join(includes: &[Include]) -> String
+    {
+        includes.iter().map(|i| i.as_str()).collect::<Vec<_>>().join("+")
+    }
+
+    /// Splits the value of an `inc` parameter into the includes, the reverse
+    /// of `join`. Unknown values (e. g. `"url-rels"`) are skipped.
+    pub(crate) fn split(incs: &str) -> Vec<Include>
+    {
+        use self::Include::*;
+        let all = [
+            Aliases,
+            Annotation,
+            ArtistCredits,
+            Artists,
+            DiscIds,
+            Isrcs,
+            Labels,
+            Media,
+            Recordings,
+            ReleaseGroups,
+            Releases,
+            Tags,
+            Ratings,
+            UserTags,
+            UserRatings,
+            Works,
+        ];
+        incs.split('+')
+            .filter_map(|inc| all.iter().find(|i| i.as_str() == inc).cloned())
+            .collect()
+    }
+
+    /// Checks that the includes can be requested together in a lookup of the
+    /// entity with the name `entity` (e. g. `"artist"`).
+    ///
+    /// The web service rejects invalid combinations with a response which
+    /// doesn't tell what exactly is wrong, so these are checked before making
+    /// the request. Usually `Resource::check_includes` is more convenient.
+    pub fn validate(entity: &'static str, includes: &[Include]) -> Result<(), InvalidInclude>
+    {
+        for &include in includes {
+            match requirements(entity, include) {
+                None => return Err(InvalidInclude::NotAvailable(entity, include)),
+                Some(required) => {
+                    if !required.is_empty() && !required.iter().any(|r| includes.contains(r)) {
+                        return Err(InvalidInclude::Requires(include, required));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns which includes are required for the include to be valid in a
+/// lookup of `entity` (one of them is sufficient), or `None` if the include
+/// isn't available at all.
+fn requirements(entity: &str, include: Include) -> Option<&'static [Include]>
+{
+    use self::Include::*;
+
+    const NONE: &'static [Include] = &[];
+    const RECORDINGS: &'static [Include] = &[Recordings];
+    const RELEASES: &'static [Include] = &[Releases];
+    const RELEASE_LIKE: &'static [Include] = &[Recordings, Releases, ReleaseGroups];
+
+    match (entity, include) {
+        (_, Aliases) | (_, Annotation) | (_, Tags) | (_, UserTags) => Some(NONE),
+
+        (_, Ratings) | (_, UserRatings) => match entity {
+            "artist" | "event" | "label" | "recording" | "release-group" | "work" => Some(NONE),
+            _ => None,
+        },
+
+        ("artist", Recordings) |
+        ("artist", Releases) |
+        ("artist", ReleaseGroups) |
+        ("artist", Works) |
+        ("label", Releases) |
+        ("recording", Artists) |
+        ("recording", Releases) |
+        ("recording", ArtistCredits) |
+        ("recording", Isrcs) |
+        ("release", Artists) |
+        ("release", Labels) |
+        ("release", Recordings) |
+        ("release", ReleaseGroups) |
+        ("release", ArtistCredits) |
+        ("release", DiscIds) |
+        ("release", Media) |
+        ("release-group", Artists) |
+        ("release-group", Releases) |
+        ("release-group", ArtistCredits) => Some(NONE),
+
+        ("artist", Isrcs) | ("release", Isrcs) => Some(RECORDINGS),
+        ("artist", ArtistCredits) => Some(RELEASE_LIKE),
+        ("label", ArtistCredits) => Some(RELEASES),
+
+        ("artist", DiscIds) |
+        ("artist", Media) |
+        ("label", DiscIds) |
+        ("label", Media) |
+        ("recording", DiscIds) |
+        ("recording", Media) |
+        ("release-group", DiscIds) |
+        ("release-group", Media) => Some(RELEASES),
+
+        _ => None,
+    }
+}
+
+/// An include which can't be requested in a lookup, see `Include::validate`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvalidInclude {
+    /// The include isn't available for lookups of the entity type.
+    NotAvailable(&'static str, Include),
+
+    /// The include is only valid if one of the other includes is requested
+    /// too, e. g. `Isrcs` in a release lookup requires `Recordings`.
+    Requires(Include, &'static [Include]),
+}
+
+impl fmt::Display for InvalidInclude {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match *self {
+            InvalidInclude::NotAvailable(entity, include) => {
+                write!(f, "include {} is not available for {}", include.as_str(), entity)
+            }
+            InvalidInclude::Requires(include, required) => {
+                let names: Vec<_> = required.iter().map(|r| r.as_str()).collect();
+                write!(f, "include {} requires one of: {}", include.as_str(), names.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for InvalidInclude {
+    fn description(&self) -> &str
+    {
+        match *self {
+            InvalidInclude::NotAvailable(..) => "include not available",
+            InvalidInclude::Requires(..) => "include requires other includes",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::{Mbid, Release, Resource};
+    use std::str::FromStr;
+
+    #[test]
+    fn join_includes()
+    {
+        assert_eq!(Include::join(&[]), "".to_string());
+        assert_eq!(
+            Include::join(&[Include::Media, Include::ArtistCredits]),
+            "media+artist-credits".to_string()
+        );
+    }
+
+    #[test]
+    fn split_includes()
+    {
+        assert_eq!(Include::split(""), Vec::new());
+        assert_eq!(
+            Include::split("aliases+url-rels+artist-credits"),
+            vec![Include::Aliases, Include::ArtistCredits]
+        );
+        assert_eq!(
+            Include::split("tags+user-tags"),
+            vec![Include::Tags, Include::UserTags]
+        );
+        assert!(Include::UserTags.is_privileged());
+        assert!(!Include::Tags.is_privileged());
+    }
+
+    #[test]
+    fn validate_includes()
+    {
+        use self::Include::*;
+
+        Include::validate("release", &[Recordings, Isrcs, ArtistCredits]).unwrap();
+        Include::validate("artist", &[Aliases, Ratings]).unwrap();
+        Include::validate("artist", &[Media, Releases]).unwrap();
+        Include::validate("area", &[]).unwrap();
+
+        assert_eq!(
+            Include::validate("release", &[Isrcs, ArtistCredits]),
+            Err(InvalidInclude::Requires(Isrcs, &[Recordings]))
+        );
+        assert_eq!(
+            Include::validate("area", &[Aliases, Recordings]),
+            Err(InvalidInclude::NotAvailable("area", Recordings))
+        );
+        assert_eq!(
+            Include::validate("release", &[Ratings]).unwrap_err().to_string(),
+            "include ratings is not available for release".to_string()
+        );
+        assert_eq!(
+            Include::validate("artist", &[ArtistCredits]).unwrap_err().to_string(),
+            "include artist-credits requires one of: recordings, releases, release-groups"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn url_with_includes()
+    {
+        let base = "https://musicbrainz.org/ws/2";
+        let mbid = Mbid::from_str("ed118c5f-d940-4b52-a37b-b1a205374abe").unwrap();
+        assert_eq!(
+            Release::get_url_with_includes_at(base, &mbid, &[Include::Media]),
+            "https://musicbrainz.org/ws/2/release/ed118c5f-d940-4b52-a37b-b1a205374abe?inc=media"
+                .to_string()
+        );
+        assert_eq!(
+            Release::get_url_with_includes_at(base, &mbid, &[]),
+            "https://musicbrainz.org/ws/2/release/ed118c5f-d940-4b52-a37b-b1a205374abe".to_string()
+        );
+    }
+}
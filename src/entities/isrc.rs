@@ -0,0 +1,175 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use xpath_reader::{FromXml, FromXmlError, XpathReader};
+
+/// International Standard Recording Code, identifying a recording.
+///
+/// An ISRC consists of a two letter country code, a three character
+/// alphanumeric registrant code, the last two digits of the year of reference
+/// and a five digit designation code, e. g. `USIR19701296`.
+///
+/// Parsing also accepts the hyphenated form (`US-IR1-97-01296`) and lowercase
+/// letters, the value is always stored in the compact uppercase form. This
+/// way obviously invalid codes are rejected locally before they are ever sent
+/// to the server.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Isrc {
+    code: String,
+}
+
+impl Isrc {
+    /// The ISO 3166-1 alpha-2 country code, e. g. `"US"`.
+    pub fn country_code(&self) -> &str
+    {
+        &self.code[0..2]
+    }
+
+    /// The code of the registrant which assigned the ISRC.
+    pub fn registrant_code(&self) -> &str
+    {
+        &self.code[2..5]
+    }
+
+    /// The last two digits of the year the ISRC was assigned.
+    pub fn year(&self) -> u8
+    {
+        self.code[5..7].parse().unwrap()
+    }
+
+    /// The number assigned to the recording by the registrant.
+    pub fn designation_code(&self) -> u32
+    {
+        self.code[7..12].parse().unwrap()
+    }
+
+    /// The compact form of the code, e. g. `"USIR19701296"`.
+    pub fn as_str(&self) -> &str
+    {
+        self.code.as_str()
+    }
+}
+
+/// The reason an ISRC is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseIsrcError {
+    /// The code doesn't consist of exactly 12 characters (not counting
+    /// hyphens).
+    WrongLength(usize),
+
+    /// The country code doesn't consist of two letters.
+    InvalidCountryCode,
+
+    /// The registrant code doesn't consist of three alphanumeric characters.
+    InvalidRegistrantCode,
+
+    /// The year doesn't consist of two digits.
+    InvalidYear,
+
+    /// The designation code doesn't consist of five digits.
+    InvalidDesignationCode,
+}
+
+impl Display for ParseIsrcError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result
+    {
+        use self::ParseIsrcError::*;
+        match *self {
+            WrongLength(n) => write!(f, "ParseIsrcError: Expected 12 characters, found {}", n),
+            InvalidCountryCode => write!(f, "ParseIsrcError: Invalid country code"),
+            InvalidRegistrantCode => write!(f, "ParseIsrcError: Invalid registrant code"),
+            InvalidYear => write!(f, "ParseIsrcError: Invalid year"),
+            InvalidDesignationCode => write!(f, "ParseIsrcError: Invalid designation code"),
+        }
+    }
+}
+
+impl Error for ParseIsrcError {
+    fn description(&self) -> &str
+    {
+        use self::ParseIsrcError::*;
+        match *self {
+            WrongLength(_) => "wrong length",
+            InvalidCountryCode => "invalid country code",
+            InvalidRegistrantCode => "invalid registrant code",
+            InvalidYear => "invalid year",
+            InvalidDesignationCode => "invalid designation code",
+        }
+    }
+}
+
+impl FromStr for Isrc {
+    type Err = ParseIsrcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let code = s.trim().chars().filter(|c| *c != '-').collect::<String>().to_uppercase();
+
+        let chars: Vec<char> = code.chars().collect();
+        if chars.len() != 12 {
+            return Err(ParseIsrcError::WrongLength(chars.len()));
+        }
+        if !chars[0..2].iter().all(|c| c.is_ascii_uppercase()) {
+            return Err(ParseIsrcError::InvalidCountryCode);
+        }
+        if !chars[2..5].iter().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) {
+            return Err(ParseIsrcError::InvalidRegistrantCode);
+        }
+        if !chars[5..7].iter().all(|c| c.is_ascii_digit()) {
+            return Err(ParseIsrcError::InvalidYear);
+        }
+        if !chars[7..12].iter().all(|c| c.is_ascii_digit()) {
+            return Err(ParseIsrcError::InvalidDesignationCode);
+        }
+
+        Ok(Isrc { code: code })
+    }
+}
+
+impl Display for Isrc {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl FromXml for Isrc {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        use xpath_reader::errors::ChainXpathErr;
+        String::from_xml(reader)?.parse().chain_err(|| "Parse ISRC error").map_err(
+            |e| FromXmlError::from(e),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid()
+    {
+        let isrc = Isrc::from_str("USIR19701296").unwrap();
+        assert_eq!(isrc.country_code(), "US");
+        assert_eq!(isrc.registrant_code(), "IR1");
+        assert_eq!(isrc.year(), 97);
+        assert_eq!(isrc.designation_code(), 1296);
+        assert_eq!(isrc.to_string(), "USIR19701296".to_string());
+
+        assert_eq!(Isrc::from_str("us-ir1-97-01296").unwrap(), isrc);
+    }
+
+    #[test]
+    fn parse_invalid()
+    {
+        assert_eq!(Isrc::from_str("USIR1970129"), Err(ParseIsrcError::WrongLength(11)));
+        assert_eq!(Isrc::from_str("U1IR19701296"), Err(ParseIsrcError::InvalidCountryCode));
+        assert_eq!(Isrc::from_str("USI_19701296"), Err(ParseIsrcError::InvalidRegistrantCode));
+        assert_eq!(Isrc::from_str("USIR1X701296"), Err(ParseIsrcError::InvalidYear));
+        assert_eq!(Isrc::from_str("USIR1970129X"), Err(ParseIsrcError::InvalidDesignationCode));
+        assert_eq!(Isrc::from_str("ÜSIR19701296"), Err(ParseIsrcError::InvalidCountryCode));
+    }
+}
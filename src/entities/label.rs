@@ -1,8 +1,15 @@
 use xpath_reader::{FromXml, FromXmlError, XpathReader};
 use xpath_reader::reader::{FromXmlContained, FromXmlElement};
 
-use entities::{Mbid, Resource};
+#[cfg(feature = "client")]
+use client::Client;
+use entities::{Include, LabelRef, Mbid, Resource};
+use entities::helper::{mark_requested, read_attr, read_attr_option, read_mb_flag,
+                      read_mb_list};
 use entities::date::PartialDate;
+use entities::relationships::{filter_relations, Direction, RelType, Relation, RelationTarget};
+#[cfg(feature = "client")]
+use errors::ClientError;
 
 /// A label entity in the MusicBrainz database.
 /// There is quite some controversy in the music industry what a 'label'
@@ -11,6 +18,7 @@ use entities::date::PartialDate;
 /// For a complete disambiguation see the `LabelType` enum. The labels in
 /// MusicBrainz are mostly
 /// imprints.
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Label {
     /// MBID of the entity in the MusicBrainz database.
     pub mbid: Mbid,
@@ -29,7 +37,9 @@ pub struct Label {
     /// Variants of the name mainly used as search help.
     /// These can be variants, spellings of names, missing titles and common
     /// misspellings.
-    pub aliases: Vec<String>,
+    ///
+    /// `None` if the aliases weren't requested (`Include::Aliases`).
+    pub aliases: Option<Vec<String>>,
 
     /// LC code of the label, as issued by the IFPI.
     pub label_code: Option<String>,
@@ -55,6 +65,57 @@ pub struct Label {
     /// The date when this label ceased to exist or its last release ever was
     /// released.
     pub end_date: Option<PartialDate>,
+
+    /// Whether the label ceased to exist, which can be known even if `end_date`
+    /// isn't.
+    pub ended: bool,
+}
+
+#[cfg(feature = "client")]
+impl Label {
+    /// Fetch the labels owning this label or of which this label is an
+    /// imprint, e. g. to roll up releases to the parent company.
+    ///
+    /// Only the direct parents are returned, use `Client::walk` to find the
+    /// complete family tree.
+    pub fn fetch_parent_labels(&self, client: &mut Client) -> Result<Vec<LabelRef>, ClientError>
+    {
+        let relations = client.get_relations::<Label>(&self.mbid)?;
+        Ok(parent_labels(&relations))
+    }
+
+    /// Fetch the imprints of this label.
+    pub fn fetch_imprints(&self, client: &mut Client) -> Result<Vec<LabelRef>, ClientError>
+    {
+        let relations = client.get_relations::<Label>(&self.mbid)?;
+        Ok(imprints(&relations))
+    }
+}
+
+/// The parent labels are the sources of the ownership and imprint relations.
+fn parent_labels(relations: &[Relation]) -> Vec<LabelRef>
+{
+    target_labels(filter_relations(
+        relations,
+        &[RelType::LabelOwnership, RelType::Imprint],
+        Direction::Backward,
+    ))
+}
+
+fn imprints(relations: &[Relation]) -> Vec<LabelRef>
+{
+    target_labels(filter_relations(relations, &[RelType::Imprint], Direction::Forward))
+}
+
+fn target_labels(relations: Vec<&Relation>) -> Vec<LabelRef>
+{
+    relations
+        .into_iter()
+        .filter_map(|r| match r.target {
+            RelationTarget::Label(ref label) => Some(label.clone()),
+            _ => None,
+        })
+        .collect()
 }
 
 impl Resource for Label {
@@ -67,6 +128,11 @@ impl Resource for Label {
     {
         "aliases"
     }
+
+    fn mark_requested(&mut self, includes: &[Include])
+    {
+        mark_requested(&mut self.aliases, includes, &[Include::Aliases]);
+    }
 }
 
 impl FromXmlContained for Label {}
@@ -76,18 +142,19 @@ impl FromXml for Label {
         R: XpathReader<'d>,
     {
         Ok(Label {
-            mbid: reader.read(".//mb:label/@id")?,
+            mbid: read_attr(reader, ".//mb:label", "id")?,
             name: reader.read(".//mb:label/mb:name/text()")?,
             sort_name: reader.read(".//mb:label/mb:sort-name/text()")?,
             disambiguation: reader.read_option(".//mb:label/mb:disambiguation/text()")?,
-            aliases: reader.read_vec(".//mb:label/mb:alias-list/mb:alias/text()")?,
+            aliases: read_mb_list(reader, ".//mb:label/mb:alias-list", "/mb:alias/text()")?,
             label_code: reader.read_option(".//mb:label/mb:label-code/text()")?,
-            label_type: reader.read_option(".//mb:label/@type")?,
+            label_type: read_attr_option(reader, ".//mb:label", "type")?,
             country: reader.read_option(".//mb:label/mb:country/text()")?,
             ipi_code: reader.read_option(".//mb:label/mb:ipi/text()")?,
-            isni_code: reader.read_option(".//mb:label/mb:isni-list/mb-isni/text()")?,
+            isni_code: reader.read_option(".//mb:label/mb:isni-list/mb:isni/text()")?,
             begin_date: reader.read_option(".//mb:label/mb:life-span/mb:begin/text()")?,
             end_date: reader.read_option(".//mb:label/mb:life-span/mb:end/text()")?,
+            ended: read_mb_flag(reader, ".//mb:label/mb:life-span/mb:ended/text()")?,
         })
     }
 }
@@ -129,6 +196,7 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[cfg(feature = "client")]
     #[test]
     fn label_read_xml1()
     {
@@ -144,12 +212,12 @@ mod tests {
         );
         assert_eq!(
             label.aliases,
-            vec![
+            Some(vec![
                 "EMI".to_string(),
                 "EMI Records (UK)".to_string(),
                 "EMI Records Ltd".to_string(),
                 "EMI UK".to_string(),
-            ]
+            ])
         );
         assert_eq!(label.label_code, Some("542".to_string()));
         assert_eq!(label.label_type, Some(LabelType::ProductionOriginal));
@@ -163,6 +231,7 @@ mod tests {
         assert_eq!(label.end_date, None);
     }
 
+    #[cfg(feature = "client")]
     #[test]
     fn read_aliases()
     {
@@ -174,9 +243,40 @@ mod tests {
             "エイベックス・トラックス".to_string(),
         ];
         expected.sort();
-        let mut actual = label.aliases.clone();
+        let mut actual = label.aliases.clone().unwrap();
         actual.sort();
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn read_isni()
+    {
+        use entities::FromXmlStr;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><label type="Original Production" id="df7d1c7f-ef95-425f-8eef-445b3d7bcbd9"><name>Parlophone</name><sort-name>Parlophone</sort-name><label-code>299</label-code><ipi>00173517959</ipi><isni-list><isni>0000000107584255</isni></isni-list></label></metadata>"#;
+        let label = Label::from_xml_str(xml).unwrap();
+
+        assert_eq!(label.ipi_code, Some("00173517959".to_string()));
+        assert_eq!(label.isni_code, Some("0000000107584255".to_string()));
+    }
+
+    #[test]
+    fn label_family()
+    {
+        use xpath_reader::XpathStrReader;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><label id="df7d1c7f-ef95-425f-8eef-445b3d7bcbd9"><name>Parlophone</name><relation-list target-type="label"><relation type="label ownership"><target>c029628b-6633-439e-bcee-ed02e8a338f7</target><direction>backward</direction><label id="c029628b-6633-439e-bcee-ed02e8a338f7"><name>EMI</name><sort-name>EMI</sort-name></label></relation><relation type="imprint"><target>168f48c8-057e-4974-9600-aa9956d21e1a</target><label id="168f48c8-057e-4974-9600-aa9956d21e1a"><name>avex trax</name><sort-name>avex trax</sort-name></label></relation></relation-list></label></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let relations: Vec<Relation> = reader.read_vec("//mb:relation-list/mb:relation").unwrap();
+
+        let parents = parent_labels(&relations);
+        assert_eq!(parents.len(), 1);
+        assert_eq!(parents[0].name, "EMI".to_string());
+
+        let imprints = imprints(&relations);
+        assert_eq!(imprints.len(), 1);
+        assert_eq!(imprints[0].name, "avex trax".to_string());
+    }
 }
@@ -16,7 +16,7 @@ impl Language {
     {
         Ok(Language {
             inner: IsoLang::from_639_1(code).ok_or_else(|| {
-                ParseError::from(format!("Invalid ISO 639-1 code: {}", code))
+                ParseError::InvalidData(format!("Invalid ISO 639-1 code: {}", code))
             })?,
         })
     }
@@ -28,7 +28,7 @@ impl Language {
     {
         Ok(Language {
             inner: IsoLang::from_639_3(code).ok_or_else(|| {
-                ParseError::from(format!("Invalid ISO 639-3 code: {}", code))
+                ParseError::InvalidData(format!("Invalid ISO 639-3 code: {}", code))
             })?,
         })
     }
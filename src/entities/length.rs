@@ -0,0 +1,171 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+/// The reason a duration string is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseDurationError {
+    /// The string is empty.
+    Empty,
+
+    /// The string has more than three components (hours, minutes, seconds).
+    TooManyComponents(usize),
+
+    /// A component isn't a number.
+    InvalidNumber(String),
+
+    /// The minutes or seconds following another component are 60 or more, or
+    /// the leading component is too large for the total to be represented.
+    OutOfRange(u64),
+}
+
+impl Display for ParseDurationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result
+    {
+        use self::ParseDurationError::*;
+        match *self {
+            Empty => write!(f, "ParseDurationError: Empty duration"),
+            TooManyComponents(n) => {
+                write!(f, "ParseDurationError: Expected at most 3 components, found {}", n)
+            }
+            InvalidNumber(ref s) => write!(f, "ParseDurationError: Invalid number '{}'", s),
+            OutOfRange(n) => write!(f, "ParseDurationError: {} is out of range", n),
+        }
+    }
+}
+
+impl Error for ParseDurationError {
+    fn description(&self) -> &str
+    {
+        use self::ParseDurationError::*;
+        match *self {
+            Empty => "empty duration",
+            TooManyComponents(_) => "too many components",
+            InvalidNumber(_) => "invalid number",
+            OutOfRange(_) => "component out of range",
+        }
+    }
+}
+
+/// Parses a duration the way humans write lengths of tracks, e. g. `"4:12"`
+/// or `"1:02:33"`.
+///
+/// The components are separated by colons, the last one being the seconds,
+/// which may have a fraction (`"4:12.5"`). A single number is taken as
+/// seconds. The leading component may be arbitrarily large (`"75:00"`), while
+/// the following ones have to be below 60.
+pub fn parse_duration(s: &str) -> Result<Duration, ParseDurationError>
+{
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ParseDurationError::Empty);
+    }
+
+    let parts: Vec<&str> = s.split(':').collect();
+    if parts.len() > 3 {
+        return Err(ParseDurationError::TooManyComponents(parts.len()));
+    }
+
+    let (seconds, millis) = {
+        let last = parts[parts.len() - 1];
+        let (whole, fraction) = match last.find('.') {
+            Some(i) => (&last[..i], &last[i + 1..]),
+            None => (last, ""),
+        };
+        let millis = if fraction.is_empty() {
+            0
+        } else {
+            let digits: String = format!("{:0<3}", fraction).chars().take(3).collect();
+            parse_number(&digits, last)?
+        };
+        (parse_number(whole, last)?, millis)
+    };
+
+    let mut total: u64 = 0;
+    let mut leading = 0;
+    for (i, part) in parts.iter().enumerate() {
+        let value = if i == parts.len() - 1 {
+            seconds
+        } else {
+            parse_number(part, part)?
+        };
+        if i == 0 {
+            leading = value;
+        } else if value >= 60 {
+            return Err(ParseDurationError::OutOfRange(value));
+        }
+        total = total
+            .checked_mul(60)
+            .and_then(|total| total.checked_add(value))
+            .ok_or(ParseDurationError::OutOfRange(leading))?;
+    }
+    Ok(Duration::from_secs(total) + Duration::from_millis(millis))
+}
+
+fn parse_number(digits: &str, component: &str) -> Result<u64, ParseDurationError>
+{
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(10)) {
+        return Err(ParseDurationError::InvalidNumber(component.to_string()));
+    }
+    digits
+        .parse()
+        .map_err(|_| ParseDurationError::InvalidNumber(component.to_string()))
+}
+
+/// Formats a duration like MusicBrainz displays lengths of tracks, e. g.
+/// `"4:12"` or `"1:02:33"`.
+///
+/// The duration is rounded to whole seconds, hours are only shown if there
+/// are any.
+pub fn format_duration(duration: &Duration) -> String
+{
+    let millis = duration.subsec_nanos() / 1_000_000;
+    let secs = duration.as_secs() + if millis >= 500 { 1 } else { 0 };
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::ParseDurationError::*;
+
+    #[test]
+    fn parse()
+    {
+        assert_eq!(parse_duration("4:12"), Ok(Duration::from_secs(252)));
+        assert_eq!(parse_duration(" 1:02:33 "), Ok(Duration::from_secs(3753)));
+        assert_eq!(parse_duration("0:07"), Ok(Duration::from_secs(7)));
+        assert_eq!(parse_duration("75:00"), Ok(Duration::from_secs(4500)));
+        assert_eq!(parse_duration("252"), Ok(Duration::from_secs(252)));
+        assert_eq!(parse_duration("4:12.5"), Ok(Duration::from_millis(252500)));
+
+        assert_eq!(parse_duration(""), Err(Empty));
+        assert_eq!(parse_duration("1:2:3:4"), Err(TooManyComponents(4)));
+        assert_eq!(parse_duration("4:75"), Err(OutOfRange(75)));
+        assert_eq!(parse_duration("4:ab"), Err(InvalidNumber("ab".to_string())));
+        assert_eq!(parse_duration("-4:12"), Err(InvalidNumber("-4".to_string())));
+        assert_eq!(parse_duration("4:"), Err(InvalidNumber("".to_string())));
+        assert_eq!(
+            parse_duration("10000000000000000:00:00"),
+            Err(OutOfRange(10000000000000000))
+        );
+    }
+
+    #[test]
+    fn format()
+    {
+        assert_eq!(format_duration(&Duration::from_secs(252)), "4:12".to_string());
+        assert_eq!(format_duration(&Duration::from_secs(3753)), "1:02:33".to_string());
+        assert_eq!(format_duration(&Duration::from_secs(7)), "0:07".to_string());
+        assert_eq!(format_duration(&Duration::from_millis(232499)), "3:52".to_string());
+        assert_eq!(format_duration(&Duration::from_millis(232500)), "3:53".to_string());
+
+        let parsed = parse_duration(&format_duration(&Duration::from_secs(4500))).unwrap();
+        assert_eq!(parsed, Duration::from_secs(4500));
+    }
+}
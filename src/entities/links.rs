@@ -0,0 +1,337 @@
+//! Classification of the external links of entities, which are stored as
+//! relationships to urls.
+
+use std::collections::BTreeMap;
+
+use entities::relationships::{Relation, RelationTarget, RelType};
+
+/// A service an external link points to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum LinkService {
+    /// The official homepage of the entity.
+    OfficialSite,
+    Wikidata,
+    Wikipedia,
+    Discogs,
+    Bandcamp,
+    Spotify,
+    /// Any other website.
+    Other,
+}
+
+impl LinkService {
+    /// Classify a url by its host.
+    ///
+    /// Official homepages can't be recognized by their url, which is why they
+    /// are only classified as `OfficialSite` by `external_links`.
+    pub fn classify(url: &str) -> LinkService
+    {
+        let host = host(url);
+        if host == "wikidata.org" {
+            LinkService::Wikidata
+        } else if host == "wikipedia.org" || host.ends_with(".wikipedia.org") {
+            LinkService::Wikipedia
+        } else if host == "discogs.com" {
+            LinkService::Discogs
+        } else if host == "bandcamp.com" || host.ends_with(".bandcamp.com") {
+            LinkService::Bandcamp
+        } else if host == "spotify.com" || host.ends_with(".spotify.com") {
+            LinkService::Spotify
+        } else {
+            LinkService::Other
+        }
+    }
+}
+
+/// The kind of entity a Discogs ID refers to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum DiscogsKind {
+    Artist,
+    Label,
+    /// The master release grouping the versions of a release, which
+    /// corresponds to a release group.
+    Master,
+    Release,
+}
+
+/// The ID of an entity in the Discogs database.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct DiscogsId {
+    pub kind: DiscogsKind,
+    pub id: u64,
+}
+
+impl DiscogsId {
+    /// Extract the ID from a Discogs url, e. g.
+    /// `https://www.discogs.com/release/1234567-Some-Title`.
+    ///
+    /// Returns `None` if the url doesn't point to an artist, label, master or
+    /// release page of Discogs.
+    pub fn from_url(url: &str) -> Option<DiscogsId>
+    {
+        if LinkService::classify(url) != LinkService::Discogs {
+            return None;
+        }
+
+        // Older urls have a slug in front of the kind, e. g.
+        // `/Radiohead-OK-Computer/release/83182`, newer ones behind the ID.
+        let segments = path_segments(url);
+        segments
+            .windows(2)
+            .filter_map(|pair| {
+                let kind = match pair[0] {
+                    "artist" => DiscogsKind::Artist,
+                    "label" => DiscogsKind::Label,
+                    "master" => DiscogsKind::Master,
+                    "release" => DiscogsKind::Release,
+                    _ => return None,
+                };
+                let digits = pair[1].split('-').next().unwrap_or("");
+                digits.parse().ok().map(|id| DiscogsId { kind: kind, id: id })
+            })
+            .next()
+    }
+}
+
+/// The kind of entity a Spotify ID refers to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SpotifyKind {
+    Album,
+    Artist,
+    Playlist,
+    Track,
+}
+
+/// The ID of an entity in the Spotify catalogue.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SpotifyId {
+    pub kind: SpotifyKind,
+    /// The base 62 ID, e. g. `"6dVIqQ8qmQ5GBnJ9shOYGE"`.
+    pub id: String,
+}
+
+impl SpotifyId {
+    /// Extract the ID from a Spotify url, e. g.
+    /// `https://open.spotify.com/album/6dVIqQ8qmQ5GBnJ9shOYGE`.
+    ///
+    /// Returns `None` if the url doesn't point to an album, artist, playlist
+    /// or track of Spotify.
+    pub fn from_url(url: &str) -> Option<SpotifyId>
+    {
+        if LinkService::classify(url) != LinkService::Spotify {
+            return None;
+        }
+
+        // Localized urls have a prefix like `/intl-de/` in front of the kind.
+        let segments = path_segments(url);
+        segments
+            .windows(2)
+            .filter_map(|pair| {
+                let kind = match pair[0] {
+                    "album" => SpotifyKind::Album,
+                    "artist" => SpotifyKind::Artist,
+                    "playlist" => SpotifyKind::Playlist,
+                    "track" => SpotifyKind::Track,
+                    _ => return None,
+                };
+                if !pair[1].is_empty() && pair[1].chars().all(|c| c.is_ascii_alphanumeric()) {
+                    Some(SpotifyId {
+                        kind: kind,
+                        id: pair[1].to_string(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .next()
+    }
+}
+
+/// The position of the host in a url.
+fn host_range(url: &str) -> (usize, usize)
+{
+    let start = url.find("://").map(|i| i + 3).unwrap_or(0);
+    let end = url[start..]
+        .find(|c: char| c == '/' || c == '?' || c == '#' || c == ':')
+        .map(|i| start + i)
+        .unwrap_or(url.len());
+    (start, end)
+}
+
+/// The lowercase host of a url without a leading `www.`.
+fn host(url: &str) -> String
+{
+    let (start, end) = host_range(url);
+    let host = url[start..end].to_lowercase();
+    if host.starts_with("www.") {
+        host[4..].to_string()
+    } else {
+        host
+    }
+}
+
+/// The non-empty segments of the path of a url.
+fn path_segments(url: &str) -> Vec<&str>
+{
+    let (_, end) = host_range(url);
+    let rest = &url[end..];
+    let path = match rest.find(|c: char| c == '?' || c == '#') {
+        Some(i) => &rest[..i],
+        None => rest,
+    };
+    path.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+/// The urls among the relations, grouped by the service they point to, in
+/// document order.
+pub fn external_links(relations: &[Relation]) -> BTreeMap<LinkService, Vec<String>>
+{
+    let mut links = BTreeMap::new();
+    for relation in relations {
+        if let RelationTarget::Url(ref url) = relation.target {
+            let service = if relation.is(&RelType::OfficialHomepage) {
+                LinkService::OfficialSite
+            } else {
+                LinkService::classify(url)
+            };
+            links.entry(service).or_insert_with(Vec::new).push(url.clone());
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xpath_reader::XpathStrReader;
+
+    #[test]
+    fn classify_urls()
+    {
+        assert_eq!(
+            LinkService::classify("https://www.wikidata.org/wiki/Q44190"),
+            LinkService::Wikidata
+        );
+        assert_eq!(
+            LinkService::classify("https://en.wikipedia.org/wiki/Radiohead"),
+            LinkService::Wikipedia
+        );
+        assert_eq!(
+            LinkService::classify("https://www.discogs.com/artist/3840"),
+            LinkService::Discogs
+        );
+        assert_eq!(
+            LinkService::classify("https://radiohead.bandcamp.com/"),
+            LinkService::Bandcamp
+        );
+        assert_eq!(
+            LinkService::classify("https://open.spotify.com/artist/4Z8W4fKeB5YxbusRsdQVPb"),
+            LinkService::Spotify
+        );
+        assert_eq!(
+            LinkService::classify("https://www.radiohead.com/"),
+            LinkService::Other
+        );
+        // Hosts only containing the name of a service aren't recognized.
+        assert_eq!(
+            LinkService::classify("https://notdiscogs.com/discogs.com"),
+            LinkService::Other
+        );
+    }
+
+    #[test]
+    fn discogs_ids()
+    {
+        assert_eq!(
+            DiscogsId::from_url("https://www.discogs.com/artist/3840-Radiohead"),
+            Some(DiscogsId {
+                kind: DiscogsKind::Artist,
+                id: 3840,
+            })
+        );
+        assert_eq!(
+            DiscogsId::from_url("https://www.discogs.com/master/21491"),
+            Some(DiscogsId {
+                kind: DiscogsKind::Master,
+                id: 21491,
+            })
+        );
+        assert_eq!(
+            DiscogsId::from_url("http://www.discogs.com/Radiohead-OK-Computer/release/83182"),
+            Some(DiscogsId {
+                kind: DiscogsKind::Release,
+                id: 83182,
+            })
+        );
+        assert_eq!(DiscogsId::from_url("https://www.discogs.com/user/someone"), None);
+        assert_eq!(DiscogsId::from_url("https://example.com/release/83182"), None);
+    }
+
+    #[test]
+    fn spotify_ids()
+    {
+        assert_eq!(
+            SpotifyId::from_url("https://open.spotify.com/album/6dVIqQ8qmQ5GBnJ9shOYGE?si=abc"),
+            Some(SpotifyId {
+                kind: SpotifyKind::Album,
+                id: "6dVIqQ8qmQ5GBnJ9shOYGE".to_string(),
+            })
+        );
+        assert_eq!(
+            SpotifyId::from_url("https://open.spotify.com/intl-de/artist/4Z8W4fKeB5YxbusRsdQVPb"),
+            Some(SpotifyId {
+                kind: SpotifyKind::Artist,
+                id: "4Z8W4fKeB5YxbusRsdQVPb".to_string(),
+            })
+        );
+        assert_eq!(SpotifyId::from_url("https://open.spotify.com/user/someone"), None);
+        assert_eq!(
+            SpotifyId::from_url("https://www.discogs.com/album/6dVIqQ8qmQ5GBnJ9shOYGE"),
+            None
+        );
+    }
+
+    #[test]
+    fn group_external_links()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><artist id="a74b1b7f-71a5-4011-9441-d0b5e4122711" type="Group"><name>Radiohead</name><relation-list target-type="url"><relation type="official homepage" type-id="fe33d22f-c3b0-4d68-bd53-a856badf2b15"><target>https://www.radiohead.com/</target></relation><relation type="discogs" type-id="04a5b104-a4c2-4bac-99a1-7b837c37d9e4"><target>https://www.discogs.com/artist/3840</target></relation><relation type="wikidata" type-id="689870a4-a1e4-4912-b17f-7b2664215698"><target>https://www.wikidata.org/wiki/Q44190</target></relation><relation type="social network" type-id="99429741-f3f6-484b-84f8-23af51991770"><target>https://twitter.com/radiohead</target></relation></relation-list><relation-list target-type="artist"><relation type="member of band" type-id="5be4c609-9afa-4ea0-910b-12ffb71e3821"><target>8bfac288-ccc5-448d-9573-c33ea2aa5c30</target><direction>backward</direction><artist id="8bfac288-ccc5-448d-9573-c33ea2aa5c30" type="Person"><name>Thom Yorke</name><sort-name>Yorke, Thom</sort-name></artist></relation></relation-list></artist></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let relations: Vec<Relation> = reader.read_vec("//mb:relation-list/mb:relation").unwrap();
+
+        let links = external_links(&relations);
+        assert_eq!(
+            links.keys().cloned().collect::<Vec<_>>(),
+            vec![
+                LinkService::OfficialSite,
+                LinkService::Wikidata,
+                LinkService::Discogs,
+                LinkService::Other,
+            ]
+        );
+        assert_eq!(
+            links[&LinkService::OfficialSite],
+            vec!["https://www.radiohead.com/".to_string()]
+        );
+        assert_eq!(
+            links[&LinkService::Other],
+            vec!["https://twitter.com/radiohead".to_string()]
+        );
+    }
+
+    #[test]
+    fn label_homepage()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><label id="df7d1c7f-ef95-425f-8eef-445b3d7bcbd9"><name>Parlophone</name><relation-list target-type="url"><relation type="official homepage" type-id="fe81f6ee-9f2e-4d04-abb2-7fb0d6a8b4c8"><target>https://www.parlophone.co.uk/</target></relation></relation-list></label></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let relations: Vec<Relation> = reader.read_vec("//mb:relation-list/mb:relation").unwrap();
+
+        let links = external_links(&relations);
+        assert_eq!(
+            links[&LinkService::OfficialSite],
+            vec!["https://www.parlophone.co.uk/".to_string()]
+        );
+        assert_eq!(links.get(&LinkService::Other), None);
+    }
+}
@@ -4,7 +4,7 @@ use uuid::{self, Uuid};
 use xpath_reader::{FromXml, FromXmlError, XpathReader};
 
 /// Identifier for entities in the MusicBrainz database.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Mbid {
     uuid: Uuid,
 }
@@ -2,21 +2,70 @@
 
 pub use std::time::Duration;
 
+use endpoint::Endpoint;
+use errors::ParseError;
+use xpath_reader::XpathStrReader;
+use xpath_reader::reader::FromXmlContained;
+
 #[macro_use]
-mod helper;
+pub(crate) mod helper;
 
 mod date;
-pub use self::date::{FullDate, ParseDateError, PartialDate};
+pub use self::date::{FullDate, ParseDateError, PartialDate, Time};
+#[allow(deprecated)]
+pub use self::date::Date;
+
+mod barcode;
+pub use self::barcode::{barcode_check_digit, barcode_variants, BarcodeMatch, BarcodeVariant};
+pub(crate) use self::barcode::match_barcodes;
+
+mod catalog;
+pub use self::catalog::{catalog_numbers_match, normalize_catalog_number};
+
+mod description;
+pub use self::description::{annotation_text, Description, DescriptionSource};
+
+mod length;
+pub use self::length::{format_duration, parse_duration, ParseDurationError};
+
+mod isrc;
+pub use self::isrc::{Isrc, ParseIsrcError};
+
+mod alias;
+pub use self::alias::Alias;
+
+mod sort;
+pub use self::sort::{cmp_sort_names, SortName};
+
+mod rating;
+pub use self::rating::Rating;
+
+mod tag;
+pub use self::tag::{tag_similarity, Tag};
+pub(crate) use self::tag::top_tags;
+
+mod include;
+pub use self::include::{Include, InvalidInclude};
 
 mod lang;
 pub use self::lang::Language;
 
 pub mod refs;
-pub use self::refs::{AreaRef, ArtistRef, LabelRef, RecordingRef, ReleaseRef};
+pub use self::refs::{AreaRef, ArtistRef, EventRef, LabelRef, PlaceRef, RecordingRef, ReleaseRef,
+                     SeriesRef, WorkRef};
+
+pub mod relationships;
+pub use self::relationships::{Direction, Relation, RelationAttribute, RelationTarget, RelType};
+
+mod links;
+pub use self::links::{external_links, DiscogsId, DiscogsKind, LinkService, SpotifyId,
+                      SpotifyKind};
 
 mod area;
 mod artist;
+mod collection;
 mod event;
+mod genre;
 mod label;
 // mod medium;
 mod place;
@@ -26,22 +75,72 @@ mod release_group;
 mod series;
 // mod track
 // mod url
-// mod work
+mod work;
 pub use self::area::{Area, AreaType};
-pub use self::artist::{Artist, ArtistType, Gender};
+pub use self::artist::{Artist, ArtistType, Gender, Membership};
+pub use self::collection::{Collectable, Collection, CollectionEntity};
 pub use self::event::{Event, EventType};
-pub use self::label::Label;
+pub use self::genre::{normalize_genre_name, resolve_genre, Genre};
+pub use self::label::{Label, LabelType};
 pub use self::place::{Coordinates, Place, PlaceType};
 pub use self::recording::Recording;
-pub use self::release::{LabelInfo, Release, ReleaseMedium, ReleaseStatus, ReleaseTrack};
-pub use self::release_group::{ReleaseGroup, ReleaseGroupPrimaryType, ReleaseGroupSecondaryType,
-                              ReleaseGroupType};
+pub use self::release::{LabelInfo, Release, ReleaseEvent, ReleaseMedium, ReleaseStatus,
+                        ReleaseTrack, TotalLength};
+pub use self::release_group::{Availability, ReleaseGroup, ReleaseGroupPrimaryType,
+                              ReleaseGroupSecondaryType, ReleaseGroupType, ReleasePreference};
 // TODO it's pretty useless as of now.
 // pub use self::series::Series;
+pub use self::series::SeriesType;
+pub use self::work::{Work, WriterCredit};
 
 mod mbid;
 pub use self::mbid::Mbid;
 
+/// Entities and reference types which are identified by an MBID.
+///
+/// All of these types also implement `Hash` based on their MBID, so they can
+/// be used in `HashSet`s and as keys of `HashMap`s, e. g. to deduplicate
+/// entities during bulk operations.
+pub trait HasMbid {
+    /// The MBID of the entity in the MusicBrainz database.
+    fn mbid(&self) -> &Mbid;
+
+    /// Returns `true` if both values refer to the same entity in the database.
+    ///
+    /// In contrast to `==` this only compares the MBIDs, so it can be used to
+    /// compare values which were retrieved at different times or with
+    /// different amounts of information, e. g. an `Artist` and an `ArtistRef`.
+    fn same_entity<O: HasMbid>(&self, other: &O) -> bool
+    {
+        self.mbid() == other.mbid()
+    }
+}
+
+impl_mbid_identity!(
+    Area,
+    Artist,
+    Collection,
+    Event,
+    Genre,
+    Label,
+    Place,
+    Recording,
+    Release,
+    ReleaseGroup,
+    ReleaseTrack,
+    series::Series,
+    Work,
+    AreaRef,
+    ArtistRef,
+    EventRef,
+    LabelRef,
+    PlaceRef,
+    RecordingRef,
+    ReleaseRef,
+    SeriesRef,
+    WorkRef
+);
+
 // TODO: Convert get_name and base_url into associated consts once these land
 // in stable rust.
 /// A Resource is any entity which can be directly retrieved from MusicBrainz.
@@ -59,26 +158,151 @@ pub trait Resource {
     /// parsing from.
     fn get_url(mbid: &Mbid) -> String
     {
-        format!(
-            "https://musicbrainz.org/ws/2/{}/{}?inc={}",
+        Self::get_url_at(::util::DEFAULT_BASE_URL, mbid)
+    }
+
+    /// Like `get_url` but for the web service at `base`, e. g.
+    /// `"https://test.musicbrainz.org/ws/2"`.
+    fn get_url_at(base: &str, mbid: &Mbid) -> String
+    {
+        Endpoint::from_base_url(base).lookup_url(Self::get_name(), mbid, Some(Self::get_incs()))
+    }
+
+    /// Like `get_url_at` but requesting the specified includes instead of the
+    /// default ones of `get_incs`.
+    fn get_url_with_includes_at(base: &str, mbid: &Mbid, includes: &[Include]) -> String
+    {
+        let includes = if includes.is_empty() {
+            None
+        } else {
+            Some(Include::join(includes))
+        };
+        Endpoint::from_base_url(base).lookup_url(
             Self::get_name(),
             mbid,
-            Self::get_incs()
+            includes.as_ref().map(|i| i.as_str()),
         )
     }
 
+    /// Called by the `Client` after parsing a lookup requested with the
+    /// includes, marks the lists depending on them as fetched.
+    ///
+    /// The web service omits some lists if they are empty, so parsing alone
+    /// can't tell whether these were requested.
+    fn mark_requested(&mut self, _includes: &[Include])
+    {
+    }
+
+    /// Checks that the includes can be requested together in a lookup of this
+    /// entity, see `Include::validate`.
+    fn check_includes(includes: &[Include]) -> Result<(), InvalidInclude>
+    {
+        Include::validate(Self::get_name(), includes)
+    }
+
     /// Base url of the entity, e. g. `"https://musicbrainz.org/ws/2/artist/"`.
     ///
     /// These are used for building search requests.
     fn base_url() -> String
     {
-        format!("https://musicbrainz.org/ws/2/{}/", Self::get_name())
+        Self::base_url_at(::util::DEFAULT_BASE_URL)
+    }
+
+    /// Like `base_url` but for the web service at `base`.
+    fn base_url_at(base: &str) -> String
+    {
+        Endpoint::from_base_url(base).entity_url(Self::get_name())
     }
 }
 
-// TODO pub struct Work {}
+/// Entities which can be parsed from a complete XML document as returned by
+/// the web service.
+///
+/// This is implemented for all full entities, it's the entry point to use for
+/// documents obtained by other means than the `Client`, e. g. from a cache.
+pub trait FromXmlStr: Sized {
+    /// Parse the entity from the XML document.
+    ///
+    /// Malformed or unexpected input results in an error, never in a panic.
+    fn from_xml_str(xml: &str) -> Result<Self, ParseError>;
+}
+
+impl<T> FromXmlStr for T
+where
+    T: FromXmlContained,
+{
+    fn from_xml_str(xml: &str) -> Result<Self, ParseError>
+    {
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context)?;
+        Ok(T::from_xml(&reader)?)
+    }
+}
 
 // TODO pub struct Url {}
 
 // TODO: rating, tag, collection
-// TODO: discid, isrc, iswc
+// TODO: discid, iswc
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    fn artist_ref(mbid: &str, name: &str) -> ArtistRef
+    {
+        ArtistRef {
+            mbid: Mbid::from_str(mbid).unwrap(),
+            name: name.to_string(),
+            sort_name: name.to_string(),
+            artist_type: None,
+            disambiguation: None,
+            aliases: Vec::new(),
+            credited_as: None,
+            join_phrase: None,
+        }
+    }
+
+    #[test]
+    fn dedup_by_mbid()
+    {
+        let a = artist_ref("650e7db6-b795-4eb5-a702-5ea2fc46c848", "Lady Gaga");
+        let b = artist_ref("90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e", "NECRONOMIDOL");
+
+        let mut set = HashSet::new();
+        set.insert(a.clone());
+        set.insert(b.clone());
+        set.insert(a.clone());
+        assert_eq!(set.len(), 2);
+
+        let mbids: HashSet<Mbid> = vec![a.mbid.clone(), b.mbid.clone(), a.mbid.clone()]
+            .into_iter()
+            .collect();
+        assert_eq!(mbids.len(), 2);
+    }
+
+    #[test]
+    fn from_xml_str()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><area type="Country" id="2db42837-c832-3c27-b4a3-08198f75693c"><name>Japan</name><sort-name>Japan</sort-name></area></metadata>"#;
+        let area = Area::from_xml_str(xml).unwrap();
+        assert_eq!(area.name, "Japan".to_string());
+
+        assert!(Area::from_xml_str("").is_err());
+        assert!(Area::from_xml_str("<metadata><area").is_err());
+        assert!(Release::from_xml_str(xml).is_err());
+    }
+
+    #[test]
+    fn same_entity()
+    {
+        let a = artist_ref("650e7db6-b795-4eb5-a702-5ea2fc46c848", "Lady Gaga");
+        let renamed = artist_ref("650e7db6-b795-4eb5-a702-5ea2fc46c848", "Lady Ga Ga");
+        let b = artist_ref("90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e", "NECRONOMIDOL");
+
+        assert!(a != renamed);
+        assert!(a.same_entity(&renamed));
+        assert!(!a.same_entity(&b));
+    }
+}
@@ -1,4 +1,6 @@
-use entities::{Mbid, PartialDate, Resource};
+use entities::{Include, Mbid, PartialDate, Resource};
+use entities::helper::{mark_requested, read_attr, read_attr_option, read_mb_flag,
+                      read_mb_list};
 use entities::refs::AreaRef;
 use xpath_reader::{FromXml, FromXmlError, XpathReader};
 use xpath_reader::reader::{FromXmlContained, FromXmlElement};
@@ -69,8 +71,14 @@ pub struct Place {
     /// When the `Place` closed down.
     pub end: Option<PartialDate>,
 
+    /// Whether the `Place` closed down, which can be known even if `end`
+    /// isn't.
+    pub ended: bool,
+
     /// Alternative versions of this `Place`'s name.
-    pub aliases: Vec<String>,
+    ///
+    /// `None` if the aliases weren't requested (`Include::Aliases`).
+    pub aliases: Option<Vec<String>>,
 
     /// Additional disambiguation if there are multiple places with the same
     /// name.
@@ -88,16 +96,17 @@ impl FromXml for Place {
     {
         Ok(Place {
             address: reader.read_option(".//mb:place/mb:address/text()")?,
-            aliases: reader.read_vec(".//mb:place/mb:aliases/text()")?,
+            aliases: read_mb_list(reader, ".//mb:place/mb:alias-list", "/mb:alias/text()")?,
             annotation: reader.read_option(".//mb:place/mb:annotation/text()")?,
             area: reader.read_option(".//mb:place/mb:area")?,
             begin: reader.read_option(".//mb:place/mb:life-span/mb:begin/text()")?,
             coordinates: reader.read_option(".//mb:place/mb:coordinates")?,
             disambiguation: reader.read_option(".//mb:place/mb:disambiguation/text()")?,
             end: reader.read_option(".//mb:place/mb:life-span/mb:end/text()")?,
-            mbid: reader.read(".//mb:place/@id")?,
+            ended: read_mb_flag(reader, ".//mb:place/mb:life-span/mb:ended/text()")?,
+            mbid: read_attr(reader, ".//mb:place", "id")?,
             name: reader.read(".//mb:place/mb:name/text()")?,
-            place_type: reader.read_option(".//mb:place/@type")?,
+            place_type: read_attr_option(reader, ".//mb:place", "type")?,
         })
     }
 }
@@ -112,6 +121,11 @@ impl Resource for Place {
     {
         "annotation+aliases"
     }
+
+    fn mark_requested(&mut self, includes: &[Include])
+    {
+        mark_requested(&mut self.aliases, includes, &[Include::Aliases]);
+    }
 }
 
 #[cfg(test)]
@@ -119,6 +133,7 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[cfg(feature = "client")]
     #[test]
     fn place_read_1()
     {
@@ -146,15 +161,34 @@ mod tests {
                 mbid: Mbid::from_str("716234d3-b8ed-45ac-8983-e7219eb85956").unwrap(),
                 name: "Chipping Norton".to_string(),
                 sort_name: "Chipping Norton".to_string(),
+                area_type: None,
                 iso_3166: None,
+                disambiguation: None,
             })
         );
         assert_eq!(p.begin, PartialDate::from_str("1971").ok());
         assert_eq!(p.end, PartialDate::from_str("1999-10").ok());
-        assert_eq!(p.aliases, Vec::<String>::new());
+        assert_eq!(p.aliases, Some(Vec::new()));
         assert_eq!(p.disambiguation, None);
         assert_eq!(p.annotation, None);
     }
 
+    #[test]
+    fn read_aliases()
+    {
+        use entities::FromXmlStr;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><place type="Venue" id="4352063b-a833-421b-a420-e7fb295dece0"><name>Royal Albert Hall</name><address>Kensington Gore, London SW7 2AP, UK</address><alias-list count="2"><alias sort-name="Albert Hall">Albert Hall</alias><alias sort-name="Royal Albert Hall of Arts and Sciences">Royal Albert Hall of Arts and Sciences</alias></alias-list></place></metadata>"#;
+        let place = Place::from_xml_str(xml).unwrap();
+
+        assert_eq!(
+            place.aliases,
+            Some(vec![
+                "Albert Hall".to_string(),
+                "Royal Albert Hall of Arts and Sciences".to_string(),
+            ])
+        );
+    }
+
     // TODO more expansive example testing all fields
 }
@@ -0,0 +1,92 @@
+use entities::helper::read_attr_option;
+use xpath_reader::{FromXml, FromXmlError, XpathReader};
+use xpath_reader::reader::FromXmlElement;
+
+/// The community rating of an entity.
+///
+/// MusicBrainz users rate entities with 1 to 5 stars, internally these are
+/// stored as percentages (20 per star) which is also how the average is
+/// represented here, so ratings can be compared and sorted exactly.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Rating {
+    /// The average rating in percent (`0` to `100`), `None` if nobody rated
+    /// the entity yet.
+    pub value: Option<u8>,
+
+    /// The number of users who rated the entity.
+    pub votes_count: u32,
+}
+
+impl Rating {
+    /// The average rating in stars (`0.0` to `5.0`) as displayed on the
+    /// website.
+    pub fn stars(&self) -> Option<f32>
+    {
+        self.value.map(|v| v as f32 / 20.)
+    }
+}
+
+impl FromXmlElement for Rating {}
+impl FromXml for Rating {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        use xpath_reader::errors::ChainXpathErr;
+
+        let stars: Option<String> = reader.read_option("./text()")?;
+        let value = match stars {
+            Some(s) => {
+                let stars: f32 = s.trim().parse().chain_err(|| "Parse rating error").map_err(
+                    |e| FromXmlError::from(e),
+                )?;
+                Some((stars * 20.).round().max(0.).min(100.) as u8)
+            }
+            None => None,
+        };
+
+        Ok(Rating {
+            value: value,
+            votes_count: read_attr_option(reader, ".", "votes-count")?.unwrap_or(0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xpath_reader::XpathStrReader;
+
+    fn read_rating(xml: &str) -> Rating
+    {
+        let xml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#">{}</metadata>"#,
+            xml
+        );
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml.as_str(), &context).unwrap();
+        reader.read("//mb:rating").unwrap()
+    }
+
+    #[test]
+    fn read_rating_with_votes()
+    {
+        let rating = read_rating(r#"<rating votes-count="7">4.35</rating>"#);
+        assert_eq!(
+            rating,
+            Rating {
+                value: Some(87),
+                votes_count: 7,
+            }
+        );
+        assert_eq!(rating.stars(), Some(4.35));
+    }
+
+    #[test]
+    fn read_rating_without_votes()
+    {
+        let rating = read_rating(r#"<rating votes-count="0"/>"#);
+        assert_eq!(rating, Rating::default());
+        assert_eq!(rating.stars(), None);
+    }
+}
@@ -2,13 +2,14 @@ use std::time::Duration;
 use xpath_reader::{FromXml, FromXmlError, XpathReader};
 use xpath_reader::reader::FromXmlContained;
 
-use entities::{Mbid, Resource};
+use entities::{Include, Isrc, Mbid, Resource};
+use entities::helper::{mark_requested, read_attr, read_attr_option, read_mb_list};
 use entities::refs::ArtistRef;
 
 /// Represents a unique audio that has been used to produce at least one
 /// released track through
 /// copying or mastering.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Recording {
     /// MBID of the entity in the MusicBrainz database.
     pub mbid: Mbid,
@@ -17,14 +18,17 @@ pub struct Recording {
     pub title: String,
 
     /// The artists that the recording is primarily credited to.
-    pub artists: Vec<ArtistRef>,
+    ///
+    /// `None` if the artists weren't requested (`Include::Artists` or
+    /// `Include::ArtistCredits`).
+    pub artists: Option<Vec<ArtistRef>>,
 
     /// Approximation of the length of the recording, calculated from the
     /// tracks using it.
     pub duration: Option<Duration>,
 
     /// ISRC (International Standard Recording Code) assigned to the recording.
-    pub isrc_code: Option<String>,
+    pub isrc_code: Option<Isrc>,
 
     /// Disambiguation comment.
     pub disambiguation: Option<String>,
@@ -40,14 +44,18 @@ impl FromXml for Recording {
         R: XpathReader<'d>,
     {
         Ok(Recording {
-            mbid: reader.read(".//mb:recording/@id")?,
+            mbid: read_attr(reader, ".//mb:recording", "id")?,
             title: reader.read(".//mb:recording/mb:title/text()")?,
-            artists: reader.read_vec(".//mb:recording/mb:artist-credit/mb:name-credit")?,
+            artists: read_mb_list(
+                reader,
+                ".//mb:recording/mb:artist-credit",
+                "/mb:name-credit",
+            )?,
             duration: ::entities::helper::read_mb_duration(
                 reader,
                 ".//mb:recording/mb:length/text()",
             )?,
-            isrc_code: reader.read_option(".//mb:recording/mb:isrc-list/mb:isrc/@id")?,
+            isrc_code: read_attr_option(reader, ".//mb:recording/mb:isrc-list/mb:isrc", "id")?,
             disambiguation: reader.read_option(".//mb:recording/mb:disambiguation/text()")?,
             annotation: reader.read_option(".//mb:recording/mb:annotation/text()")?,
         })
@@ -64,6 +72,15 @@ impl Resource for Recording {
     {
         "artists+annotation+isrcs"
     }
+
+    fn mark_requested(&mut self, includes: &[Include])
+    {
+        mark_requested(
+            &mut self.artists,
+            includes,
+            &[Include::Artists, Include::ArtistCredits],
+        );
+    }
 }
 
 #[cfg(test)]
@@ -71,6 +88,7 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[cfg(feature = "client")]
     #[test]
     fn read_xml1()
     {
@@ -85,15 +103,20 @@ mod tests {
         assert_eq!(recording.duration, Some(Duration::from_millis(499000)));
         assert_eq!(
             recording.artists,
-            vec![
+            Some(vec![
                 ArtistRef {
                     mbid: Mbid::from_str("b7ffd2af-418f-4be2-bdd1-22f8b48613da").unwrap(),
                     name: "Nine Inch Nails".to_string(),
                     sort_name: "Nine Inch Nails".to_string(),
+                    artist_type: None,
+                    disambiguation: None,
+                    aliases: Vec::new(),
+                    credited_as: None,
+                    join_phrase: None,
                 },
-            ]
+            ])
         );
-        assert_eq!(recording.isrc_code, Some("USIR19701296".to_string()));
+        assert_eq!(recording.isrc_code, Some(Isrc::from_str("USIR19701296").unwrap()));
         assert_eq!(recording.annotation, None);
         assert_eq!(recording.disambiguation, None);
     }
@@ -3,28 +3,109 @@
 //!
 //! These types only contain some basic data but reference a full entity in the
 //! MusicBrainz
-//! database which can be retrieved.
+//! database which can be retrieved using their `fetch_full` methods.
 
 // TODO: Better documentation in this file.
-// TODO: When writing the API interfacing code, provide some form of helpers so
-// the full referenced
-// types corresponding to these ref types can be easily retrieved from
-// the server.
 
 use std::time::Duration;
 use xpath_reader::{FromXml, FromXmlError, XpathReader};
 use xpath_reader::reader::FromXmlElement;
 
-use entities::Mbid;
+#[cfg(feature = "client")]
+use client::Client;
+use entities::{Alias, Area, AreaType, ArtistType, EventType, Mbid, PlaceType, SeriesType};
+#[cfg(feature = "client")]
+use entities::{Artist, Event, Label, Place, Recording, Release};
+#[cfg(feature = "client")]
+use errors::ClientError;
+use entities::alias::is_known_as;
 use entities::date::PartialDate;
+use entities::helper::{read_attr, read_attr_option};
 use entities::release::ReleaseStatus;
 
+/// Implements `fetch_full` for reference types, looking up the referenced full
+/// entity by its MBID.
+macro_rules! impl_fetch_full
+{
+    ( $( $ref_type:ident => $full_type:ident ),+ ) => {
+        $(
+            impl $ref_type {
+                /// Fetch the full entity referenced by this value from the server.
+                pub fn fetch_full(&self, client: &mut Client) -> Result<$full_type, ClientError>
+                {
+                    client.get_by_mbid(&self.mbid)
+                }
+            }
+        )+
+    }
+}
+
+#[cfg(feature = "client")]
+impl_fetch_full!(
+    ArtistRef => Artist,
+    EventRef => Event,
+    LabelRef => Label,
+    PlaceRef => Place,
+    RecordingRef => Recording,
+    ReleaseRef => Release
+);
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AreaRef {
     pub mbid: Mbid,
     pub name: String,
     pub sort_name: String,
+    /// The type of the area, only provided by newer versions of the web
+    /// service.
+    pub area_type: Option<AreaType>,
     pub iso_3166: Option<String>,
+    pub disambiguation: Option<String>,
+}
+
+impl AreaRef {
+    /// Returns the full `Area` if all of its data is already contained in this
+    /// reference, which is the case if the type of the area was provided.
+    ///
+    /// This saves a lookup of the area, e. g. to find out which country an
+    /// artist is from.
+    pub fn to_full(&self) -> Option<Area>
+    {
+        self.area_type.as_ref().map(|area_type| {
+            Area {
+                mbid: self.mbid.clone(),
+                name: self.name.clone(),
+                sort_name: self.sort_name.clone(),
+                area_type: area_type.clone(),
+                iso_3166: self.iso_3166.clone(),
+                disambiguation: self.disambiguation.clone(),
+            }
+        })
+    }
+
+    /// Returns the full `Area`, fetching it from the server only if the data
+    /// contained in this reference is incomplete.
+    #[cfg(feature = "client")]
+    pub fn fetch_full(&self, client: &mut Client) -> Result<Area, ClientError>
+    {
+        match self.to_full() {
+            Some(area) => Ok(area),
+            None => client.get_by_mbid(&self.mbid),
+        }
+    }
+}
+
+impl From<Area> for AreaRef {
+    fn from(area: Area) -> Self
+    {
+        AreaRef {
+            mbid: area.mbid,
+            name: area.name,
+            sort_name: area.sort_name,
+            area_type: Some(area.area_type),
+            iso_3166: area.iso_3166,
+            disambiguation: area.disambiguation,
+        }
+    }
 }
 
 impl FromXmlElement for AreaRef {}
@@ -34,10 +115,12 @@ impl FromXml for AreaRef {
         R: XpathReader<'d>,
     {
         Ok(AreaRef {
-            mbid: reader.read(".//@id")?,
+            mbid: read_attr(reader, entity_path!("area", ""), "id")?,
             name: reader.read(".//mb:name/text()")?,
             sort_name: reader.read(".//mb:sort-name/text()")?,
+            area_type: read_attr_option(reader, ".", "type")?,
             iso_3166: reader.read_option(".//mb:iso-3166-1-code-list/mb:iso-3166-1-code/text()")?,
+            disambiguation: reader.read_option("./mb:disambiguation/text()")?,
         })
     }
 }
@@ -51,6 +134,25 @@ pub struct ArtistRef {
     pub mbid: Mbid,
     pub name: String,
     pub sort_name: String,
+
+    /// The type of the artist, if it was included in the response.
+    pub artist_type: Option<ArtistType>,
+
+    /// The disambiguation comment of the artist, if it was included in the
+    /// response.
+    pub disambiguation: Option<String>,
+
+    /// The aliases of the artist, if they were included in the response.
+    pub aliases: Vec<Alias>,
+
+    /// The name the artist is credited as, if it differs from `name`.
+    ///
+    /// Only provided for the artists of an artist credit.
+    pub credited_as: Option<String>,
+
+    /// The phrase joining the artist to the next one of an artist credit, e. g.
+    /// `" feat. "`.
+    pub join_phrase: Option<String>,
 }
 
 impl FromXmlElement for ArtistRef {}
@@ -59,20 +161,56 @@ impl FromXml for ArtistRef {
     where
         R: XpathReader<'d>,
     {
+        // The reader might either point to an `artist` element or to a
+        // `name-credit` element containing it, which has its own `name` only if
+        // the artist is credited under another name.
+        let name = match reader.read_option("./mb:artist/mb:name/text()")? {
+            Some(name) => name,
+            None => reader.read("./mb:name/text()")?,
+        };
+
         Ok(ArtistRef {
-            mbid: reader.read(".//@id")?,
-            name: reader.read(".//mb:name/text()")?,
+            mbid: read_attr(reader, entity_path!("artist", ""), "id")?,
+            name: name,
             sort_name: reader.read(".//mb:sort-name/text()")?,
+            artist_type: read_attr_option(reader, entity_path!("artist", ""), "type")?,
+            disambiguation: reader.read_option(
+                "./mb:disambiguation/text() | ./mb:artist/mb:disambiguation/text()",
+            )?,
+            aliases: reader.read_vec(
+                "./mb:alias-list/mb:alias | ./mb:artist/mb:alias-list/mb:alias",
+            )?,
+            credited_as: reader.read_option("./mb:artist/../mb:name/text()")?,
+            join_phrase: read_attr_option(reader, ".", "joinphrase")?,
         })
     }
 }
 
+impl ArtistRef {
+    /// Returns `true` if this refers to the special purpose artist *Various
+    /// Artists*.
+    pub fn is_various_artists(&self) -> bool
+    {
+        ::entities::artist::is_various_artists(&self.mbid)
+    }
+
+    /// Returns `true` if `name` is the name, sort name or one of the aliases
+    /// (including search hints) of the artist, ignoring case.
+    pub fn is_known_as(&self, name: &str) -> bool
+    {
+        is_known_as(&self.name, &self.sort_name, &self.aliases, name)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LabelRef {
     pub mbid: Mbid,
     pub name: String,
     pub sort_name: String,
     pub label_code: Option<String>,
+
+    /// The aliases of the label, if they were included in the response.
+    pub aliases: Vec<Alias>,
 }
 
 impl FromXmlElement for LabelRef {}
@@ -82,14 +220,24 @@ impl FromXml for LabelRef {
         R: XpathReader<'d>,
     {
         Ok(LabelRef {
-            mbid: reader.read(".//@id")?,
+            mbid: read_attr(reader, entity_path!("label", ""), "id")?,
             name: reader.read(".//mb:name/text()")?,
             sort_name: reader.read(".//mb:sort-name/text()")?,
             label_code: reader.read_option(".//mb:label-code/text()")?,
+            aliases: reader.read_vec(".//mb:alias-list/mb:alias")?,
         })
     }
 }
 
+impl LabelRef {
+    /// Returns `true` if `name` is the name, sort name or one of the aliases
+    /// (including search hints) of the label, ignoring case.
+    pub fn is_known_as(&self, name: &str) -> bool
+    {
+        is_known_as(&self.name, &self.sort_name, &self.aliases, name)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RecordingRef {
     pub mbid: Mbid,
@@ -104,7 +252,7 @@ impl FromXml for RecordingRef {
         R: XpathReader<'d>,
     {
         Ok(RecordingRef {
-            mbid: reader.read(".//@id")?,
+            mbid: read_attr(reader, entity_path!("recording", ""), "id")?,
             title: reader.read(".//mb:title/text()")?,
             length: ::entities::helper::read_mb_duration(reader, ".//mb:length/text()")?,
         })
@@ -118,6 +266,21 @@ pub struct ReleaseRef {
     pub date: Option<PartialDate>,
     pub status: Option<ReleaseStatus>,
     pub country: Option<String>,
+
+    /// The formats of the mediums of the release, e. g. `"CD"`.
+    ///
+    /// Only available if the media of the release were included, otherwise
+    /// this is empty.
+    pub formats: Vec<String>,
+}
+
+impl ReleaseRef {
+    /// Returns `true` if this is a pseudo-release, e. g. a translated or
+    /// transliterated track list of another release.
+    pub fn is_pseudo_release(&self) -> bool
+    {
+        self.status == Some(ReleaseStatus::PseudoRelease)
+    }
 }
 
 impl FromXmlElement for ReleaseRef {}
@@ -127,11 +290,275 @@ impl FromXml for ReleaseRef {
         R: XpathReader<'d>,
     {
         Ok(ReleaseRef {
-            mbid: reader.read(".//@id")?,
+            mbid: read_attr(reader, entity_path!("release", ""), "id")?,
             title: reader.read(".//mb:title/text()")?,
             date: reader.read_option(".//mb:date/text()")?,
             status: reader.read_option(".//mb:status/text()")?,
             country: reader.read_option(".//mb:country/text()")?,
+            formats: reader.read_vec(".//mb:medium-list/mb:medium/mb:format/text()")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventRef {
+    pub mbid: Mbid,
+    pub name: String,
+    pub event_type: Option<EventType>,
+    pub disambiguation: Option<String>,
+}
+
+impl FromXmlElement for EventRef {}
+impl FromXml for EventRef {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        Ok(EventRef {
+            mbid: read_attr(reader, ".", "id")?,
+            name: reader.read("./mb:name/text()")?,
+            event_type: read_attr_option(reader, ".", "type")?,
+            disambiguation: reader.read_option("./mb:disambiguation/text()")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlaceRef {
+    pub mbid: Mbid,
+    pub name: String,
+    pub place_type: Option<PlaceType>,
+    pub address: Option<String>,
+    pub disambiguation: Option<String>,
+}
+
+impl FromXmlElement for PlaceRef {}
+impl FromXml for PlaceRef {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        Ok(PlaceRef {
+            mbid: read_attr(reader, ".", "id")?,
+            name: reader.read("./mb:name/text()")?,
+            place_type: read_attr_option(reader, ".", "type")?,
+            address: reader.read_option("./mb:address/text()")?,
+            disambiguation: reader.read_option("./mb:disambiguation/text()")?,
         })
     }
 }
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeriesRef {
+    pub mbid: Mbid,
+    pub name: String,
+    pub series_type: Option<SeriesType>,
+    pub disambiguation: Option<String>,
+}
+
+impl FromXmlElement for SeriesRef {}
+impl FromXml for SeriesRef {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        Ok(SeriesRef {
+            mbid: read_attr(reader, ".", "id")?,
+            name: reader.read("./mb:name/text()")?,
+            series_type: read_attr_option(reader, ".", "type")?,
+            disambiguation: reader.read_option("./mb:disambiguation/text()")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkRef {
+    pub mbid: Mbid,
+    pub title: String,
+    pub disambiguation: Option<String>,
+}
+
+impl FromXmlElement for WorkRef {}
+impl FromXml for WorkRef {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        Ok(WorkRef {
+            mbid: read_attr(reader, ".", "id")?,
+            title: reader.read("./mb:title/text()")?,
+            disambiguation: reader.read_option("./mb:disambiguation/text()")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use xpath_reader::XpathStrReader;
+
+    fn read_ref<T: FromXml>(xml: &str, path: &str) -> T
+    {
+        let xml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#">{}</metadata>"#,
+            xml
+        );
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml.as_str(), &context).unwrap();
+        reader.read(path).unwrap()
+    }
+
+    #[test]
+    fn artist_ref_credited_name()
+    {
+        let artist: ArtistRef = read_ref(
+            r#"<name-credit joinphrase=" feat. "><name>Jay Z</name><artist id="f82bcf78-5b69-4622-a5ef-73800768d9ac"><name>JAY‐Z</name><sort-name>JAY‐Z</sort-name></artist></name-credit>"#,
+            "//mb:name-credit",
+        );
+        assert_eq!(artist.name, "JAY‐Z".to_string());
+        assert_eq!(artist.credited_as, Some("Jay Z".to_string()));
+        assert_eq!(artist.join_phrase, Some(" feat. ".to_string()));
+
+        let artist: ArtistRef = read_ref(
+            r#"<artist id="f82bcf78-5b69-4622-a5ef-73800768d9ac"><name>JAY‐Z</name><sort-name>JAY‐Z</sort-name></artist>"#,
+            "//mb:artist",
+        );
+        assert_eq!(artist.name, "JAY‐Z".to_string());
+        assert_eq!(artist.credited_as, None);
+        assert_eq!(artist.join_phrase, None);
+    }
+
+    #[test]
+    fn release_ref_missing_optional_fields()
+    {
+        let release: ReleaseRef = read_ref(
+            r#"<release id="ed118c5f-d940-4b52-a37b-b1a205374abe"><title>Creep</title></release>"#,
+            "//mb:release",
+        );
+        assert_eq!(
+            release,
+            ReleaseRef {
+                mbid: Mbid::from_str("ed118c5f-d940-4b52-a37b-b1a205374abe").unwrap(),
+                title: "Creep".to_string(),
+                date: None,
+                status: None,
+                country: None,
+                formats: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn release_ref_list_partially_missing_fields()
+    {
+        // Release lists inside release groups often only provide some of the
+        // optional fields for each release.
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release-list count="2">
+            <release id="ed118c5f-d940-4b52-a37b-b1a205374abe"><title>Creep</title><status>Official</status><date>1992-09-21</date><country>GB</country></release>
+            <release id="2c9b47a2-d4d1-4b3c-8f0c-4e9b2a6c9f3e"><title>Creep</title><date>1993</date></release>
+        </release-list></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let releases: Vec<ReleaseRef> = reader.read_vec("//mb:release-list/mb:release").unwrap();
+
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[0].status, Some(ReleaseStatus::Official));
+        assert_eq!(releases[0].country, Some("GB".to_string()));
+        assert_eq!(releases[1].date, Some(PartialDate::from_str("1993").unwrap()));
+        assert_eq!(releases[1].status, None);
+        assert_eq!(releases[1].country, None);
+    }
+
+    #[test]
+    fn area_ref_to_full()
+    {
+        let area: AreaRef = read_ref(
+            r#"<area type="Country" id="2db42837-c832-3c27-b4a3-08198f75693c"><name>Japan</name><sort-name>Japan</sort-name><iso-3166-1-code-list><iso-3166-1-code>JP</iso-3166-1-code></iso-3166-1-code-list></area>"#,
+            "//mb:area",
+        );
+        assert_eq!(
+            area.to_full(),
+            Some(Area {
+                mbid: Mbid::from_str("2db42837-c832-3c27-b4a3-08198f75693c").unwrap(),
+                name: "Japan".to_string(),
+                sort_name: "Japan".to_string(),
+                area_type: AreaType::Country,
+                iso_3166: Some("JP".to_string()),
+                disambiguation: None,
+            })
+        );
+
+        let area: AreaRef = read_ref(
+            r#"<area id="2db42837-c832-3c27-b4a3-08198f75693c"><name>Japan</name><sort-name>Japan</sort-name></area>"#,
+            "//mb:area",
+        );
+        assert_eq!(area.area_type, None);
+        assert_eq!(area.to_full(), None);
+    }
+
+    #[test]
+    fn event_ref()
+    {
+        let event: EventRef = read_ref(
+            r#"<event type="Festival" id="6e2ab7d5-f340-4c41-99a3-c901733402b4"><name>25. Wave-Gotik-Treffen</name></event>"#,
+            "//mb:event",
+        );
+        assert_eq!(
+            event,
+            EventRef {
+                mbid: Mbid::from_str("6e2ab7d5-f340-4c41-99a3-c901733402b4").unwrap(),
+                name: "25. Wave-Gotik-Treffen".to_string(),
+                event_type: Some(EventType::Festival),
+                disambiguation: None,
+            }
+        );
+    }
+
+    #[test]
+    fn place_ref()
+    {
+        let place: PlaceRef = read_ref(
+            r#"<place type="Studio" id="d1ab65f8-d082-492a-bd70-ce375548dabf"><name>Chipping Norton Recording Studios</name><address>28–30 New Street, Chipping Norton</address></place>"#,
+            "//mb:place",
+        );
+        assert_eq!(
+            place,
+            PlaceRef {
+                mbid: Mbid::from_str("d1ab65f8-d082-492a-bd70-ce375548dabf").unwrap(),
+                name: "Chipping Norton Recording Studios".to_string(),
+                place_type: Some(PlaceType::Studio),
+                address: Some("28–30 New Street, Chipping Norton".to_string()),
+                disambiguation: None,
+            }
+        );
+    }
+
+    #[test]
+    fn series_ref()
+    {
+        let series: SeriesRef = read_ref(
+            r#"<series type="Catalogue" id="d977f7fd-96c9-4e3e-83b5-eb484a9e6582"><name>Bach-Werke-Verzeichnis</name></series>"#,
+            "//mb:series",
+        );
+        assert_eq!(series.name, "Bach-Werke-Verzeichnis".to_string());
+        assert_eq!(series.series_type, Some(SeriesType::Catalogue));
+    }
+
+    #[test]
+    fn work_ref()
+    {
+        let work: WorkRef = read_ref(
+            r#"<work id="1a16b2cb-0e43-3b9b-a5a7-5bcd0ccb1a6f"><title>Creep</title><disambiguation>Radiohead song</disambiguation></work>"#,
+            "//mb:work",
+        );
+        assert_eq!(
+            work,
+            WorkRef {
+                mbid: Mbid::from_str("1a16b2cb-0e43-3b9b-a5a7-5bcd0ccb1a6f").unwrap(),
+                title: "Creep".to_string(),
+                disambiguation: Some("Radiohead song".to_string()),
+            }
+        );
+    }
+}
@@ -0,0 +1,553 @@
+//! Relationships link entities of the MusicBrainz database to each other, e. g.
+//! an `Artist` being a member of a band or a `Label` being the imprint of
+//! another label.
+//!
+//! Additional information can be found in the [MusicBrainz
+//! docs](https://musicbrainz.org/doc/Relationships)
+
+use xpath_reader::{FromXml, FromXmlError, XpathReader};
+use xpath_reader::reader::FromXmlElement;
+
+use entities::helper::{read_attr, read_attr_option, read_mb_vec};
+use entities::{AreaRef, ArtistRef, EventRef, LabelRef, Mbid, PartialDate, PlaceRef, RecordingRef,
+               ReleaseRef, SeriesRef, WorkRef};
+
+/// The `inc` parameter value requesting the relationships to all kinds of
+/// entities.
+pub(crate) const RELATION_INCS: &'static str = "area-rels+artist-rels+event-rels+label-rels+\
+                                                place-rels+recording-rels+release-rels+\
+                                                release-group-rels+series-rels+url-rels+work-rels";
+
+enum_mb_xml! {
+    /// Some of the most common relationship types.
+    ///
+    /// Relationships can be of many more types, which is why `Relation` stores
+    /// the type as a string, these can be compared using `Relation::is`.
+    ///
+    /// The MBIDs of the types are provided by the constants in `types`.
+    pub enum RelType {
+        /// An artist being a member of a group.
+        var MemberOf = "member of band",
+        /// An artist being part of a short term collaboration.
+        var Collaboration = "collaboration",
+        /// A performance name being used by a person.
+        var IsPerson = "is person",
+        /// A group being a subgroup of another group.
+        var Subgroup = "subgroup",
+        /// A person being the parent of another person.
+        var Parent = "parent",
+        /// Two persons being siblings.
+        var Sibling = "sibling",
+        /// Two persons being married.
+        var Married = "married",
+        /// A label owning another label.
+        var LabelOwnership = "label ownership",
+        /// A label being renamed to another label.
+        var LabelRename = "label rename",
+        /// A label being an imprint of another label.
+        var Imprint = "imprint",
+        /// An artist having composed the music of a work.
+        var Composer = "composer",
+        /// An artist having written the lyrics of a work.
+        var Lyricist = "lyricist",
+        /// An artist having written the text of an opera or similar work.
+        var Librettist = "librettist",
+        /// An artist having written a work, if it's unknown whether the music
+        /// or the lyrics.
+        var Writer = "writer",
+        /// A pseudo-release providing a translated or transliterated track list
+        /// of a release.
+        var TranslTracklisting = "transl-tracklisting",
+        /// An artist having performed a recording in a way not covered by the
+        /// more specific types.
+        var Performer = "performer",
+        /// An artist having performed the vocals of a recording.
+        var Vocal = "vocal",
+        /// An artist having played an instrument on a recording.
+        var Instrument = "instrument",
+        /// The official homepage of an artist.
+        var OfficialHomepage = "official homepage",
+        /// The Wikidata item of an artist.
+        var Wikidata = "wikidata",
+        /// The Wikipedia article of an artist.
+        var Wikipedia = "wikipedia",
+        /// The Discogs page of an artist.
+        var Discogs = "discogs",
+        /// The Bandcamp page of an artist.
+        var Bandcamp = "bandcamp",
+    }
+}
+
+impl RelType {
+    /// The constant in `types` for this relationship type, `None` if its MBID
+    /// isn't provided.
+    pub fn relationship_type(&self) -> Option<types::RelationshipType>
+    {
+        types::ALL.iter().find(|t| t.rel_type == *self).cloned()
+    }
+}
+
+/// The direction of a relationship, relative to the entity it was read from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    /// The entity is the source of the relationship, e. g. the band of which
+    /// the target is a member.
+    Forward,
+    /// The entity is the target of the relationship, e. g. the member of the
+    /// band which is the target.
+    Backward,
+}
+
+impl FromXmlElement for Direction {}
+impl FromXml for Direction {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        match String::from_xml(reader)?.as_str() {
+            "forward" | "" => Ok(Direction::Forward),
+            "backward" => Ok(Direction::Backward),
+            s => Err(format!("Unknown `Direction` value: '{}'", s).into()),
+        }
+    }
+}
+
+/// The entity a relationship points to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RelationTarget {
+    Area(AreaRef),
+    Artist(ArtistRef),
+    Event(EventRef),
+    Label(LabelRef),
+    Place(PlaceRef),
+    Recording(RecordingRef),
+    Release(ReleaseRef),
+    Series(SeriesRef),
+    Work(WorkRef),
+    /// An external url, e. g. the homepage of an artist.
+    Url(String),
+    /// A target of a type which isn't supported yet.
+    Other {
+        target_type: String,
+        mbid: Mbid,
+    },
+}
+
+impl RelationTarget {
+    /// The MBID of the target, if it is an entity of the database.
+    pub fn mbid(&self) -> Option<&Mbid>
+    {
+        match *self {
+            RelationTarget::Area(ref r) => Some(&r.mbid),
+            RelationTarget::Artist(ref r) => Some(&r.mbid),
+            RelationTarget::Event(ref r) => Some(&r.mbid),
+            RelationTarget::Label(ref r) => Some(&r.mbid),
+            RelationTarget::Place(ref r) => Some(&r.mbid),
+            RelationTarget::Recording(ref r) => Some(&r.mbid),
+            RelationTarget::Release(ref r) => Some(&r.mbid),
+            RelationTarget::Series(ref r) => Some(&r.mbid),
+            RelationTarget::Work(ref r) => Some(&r.mbid),
+            RelationTarget::Url(_) => None,
+            RelationTarget::Other { ref mbid, .. } => Some(mbid),
+        }
+    }
+}
+
+/// An attribute further describing a relationship, e. g. the instrument played
+/// by a member of a band or the number of a release in a series.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelationAttribute {
+    /// The name of the attribute, e. g. `"lead vocals"` or `"number"`.
+    pub name: String,
+
+    /// The value of the attribute, only provided for attributes which take a
+    /// value, e. g. `"Op. 1"` for a `"number"`.
+    pub value: Option<String>,
+}
+
+impl FromXmlElement for RelationAttribute {}
+impl FromXml for RelationAttribute {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        Ok(RelationAttribute {
+            name: reader.read("./text()")?,
+            value: read_attr_option(reader, ".", "value")?,
+        })
+    }
+}
+
+/// A relationship of an entity to another entity or an url.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Relation {
+    /// The name of the relationship type, e. g. `"member of band"`.
+    pub relation_type: String,
+
+    /// The MBID of the relationship type.
+    pub type_id: Option<Mbid>,
+
+    /// The direction of the relationship.
+    pub direction: Direction,
+
+    /// The entity the relationship points to.
+    pub target: RelationTarget,
+
+    /// The date the relationship began.
+    pub begin: Option<PartialDate>,
+
+    /// The date the relationship ended.
+    pub end: Option<PartialDate>,
+
+    /// Whether the relationship has ended.
+    pub ended: bool,
+
+    /// Attributes further describing the relationship, e. g. the instrument
+    /// played by a member of a band.
+    pub attributes: Vec<RelationAttribute>,
+
+    /// The position of the target in an ordered list of relationships, e. g.
+    /// the releases which are part of a series.
+    ///
+    /// Sort by this key to present the relationships in their intended order.
+    pub ordering_key: Option<u32>,
+}
+
+impl Relation {
+    /// Returns `true` if the relationship is of the specified type.
+    ///
+    /// The constants in `types` only provide the MBID of the type between one
+    /// pair of entity types, e. g. `OFFICIAL_HOMEPAGE` is the one of artists.
+    /// So the MBIDs are only compared if the type id is one of these, otherwise
+    /// the names, which also matches e. g. the official homepage of a label.
+    pub fn is(&self, rel_type: &RelType) -> bool
+    {
+        match self.type_id.as_ref().and_then(types::by_id) {
+            Some(known) => known.rel_type == *rel_type,
+            None => self.relation_type == rel_type.to_string(),
+        }
+    }
+
+    /// Returns `true` if the relationship is of the specified type.
+    ///
+    /// In contrast to `is` this compares the MBIDs of the types if the type
+    /// id was provided, since the same name can be used by the types of
+    /// different pairs of entity types, e. g. `"performer"`.
+    pub fn is_type(&self, rel_type: &types::RelationshipType) -> bool
+    {
+        match self.type_id {
+            Some(ref id) => *id == rel_type.mbid(),
+            None => self.relation_type == rel_type.name(),
+        }
+    }
+
+    /// The number of the target in a series, e. g. `"Vol. 3"`, if provided.
+    ///
+    /// In contrast to `ordering_key` this is the number as it is printed and
+    /// might not be numeric at all.
+    pub fn series_number(&self) -> Option<&str>
+    {
+        self.attributes
+            .iter()
+            .find(|a| a.name == "number")
+            .and_then(|a| a.value.as_ref())
+            .map(|v| v.as_str())
+    }
+}
+
+/// Returns the relations of one of the specified types with the specified
+/// direction, in document order.
+pub(crate) fn filter_relations<'a>(
+    relations: &'a [Relation],
+    types: &[RelType],
+    direction: Direction,
+) -> Vec<&'a Relation>
+{
+    relations
+        .iter()
+        .filter(|r| r.direction == direction && types.iter().any(|t| r.is(t)))
+        .collect()
+}
+
+/// Constants for the types of some of the most common relationships, with
+/// their MBIDs.
+///
+/// The names are the ones of the respective `RelType`. The full list is
+/// available in the [MusicBrainz docs](https://musicbrainz.org/relationships).
+pub mod types {
+    use std::str::FromStr;
+
+    use entities::Mbid;
+    use super::RelType;
+
+    /// The type of a relationship between two kinds of entities.
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct RelationshipType {
+        /// The type, providing the name found in `Relation::relation_type`.
+        pub rel_type: RelType,
+
+        /// The MBID of the type, as found in `Relation::type_id`.
+        pub id: &'static str,
+    }
+
+    impl RelationshipType {
+        /// The name of the type, e. g. `"member of band"`.
+        pub fn name(&self) -> String
+        {
+            self.rel_type.to_string()
+        }
+
+        /// The MBID of the type.
+        pub fn mbid(&self) -> Mbid
+        {
+            Mbid::from_str(self.id).unwrap()
+        }
+    }
+
+    macro_rules! rel_types {
+        ( $( $(#[$attr:meta])* const $rel:ident = ($variant:ident, $mbid:expr); )+ ) => {
+            $(
+                $(#[$attr])*
+                pub const $rel: RelationshipType = RelationshipType {
+                    rel_type: RelType::$variant,
+                    id: $mbid,
+                };
+            )+
+
+            /// All relationship types for which a constant is provided.
+            pub const ALL: &'static [RelationshipType] = &[ $( $rel ),+ ];
+        }
+    }
+
+    rel_types! {
+        /// An artist being a member of a group.
+        const MEMBER_OF_BAND = (MemberOf, "5be4c609-9afa-4ea0-910b-12ffb71e3821");
+        /// An artist being part of a short term collaboration.
+        const COLLABORATION = (Collaboration, "75c09861-6857-4ec0-9729-84eefde7fc86");
+        /// A performance name being used by a person.
+        const IS_PERSON = (IsPerson, "dd9886f2-1dfe-4270-97db-283f6839a666");
+        /// A group being a subgroup of another group.
+        const SUBGROUP = (Subgroup, "7802f96b-d995-4ce9-8f70-6366faad758e");
+        /// A person being the parent of another person.
+        const PARENT = (Parent, "9421ca84-934f-49fe-9e66-dea242430406");
+        /// Two persons being married.
+        const MARRIED = (Married, "b2bf7a5d-2da6-4742-baf4-e38d8a7ad029");
+        /// An artist having performed a recording in a way not covered by the
+        /// more specific types.
+        const PERFORMER = (Performer, "628a9658-f54c-4142-b0c0-95f031b544da");
+        /// An artist having performed the vocals of a recording.
+        const VOCAL = (Vocal, "0fdbe3c6-7700-4a31-ae54-b53f06ae1cfa");
+        /// An artist having played an instrument on a recording.
+        const INSTRUMENT = (Instrument, "59054b12-01ac-43ee-a618-285fd397e461");
+        /// An artist having composed the music of a work.
+        const COMPOSER = (Composer, "d59d99ea-23d4-4a80-b066-edca32ee158f");
+        /// An artist having written the lyrics of a work.
+        const LYRICIST = (Lyricist, "3e48faba-ec01-47fd-8e89-30e81161661c");
+        /// An artist having written the text of an opera or similar work.
+        const LIBRETTIST = (Librettist, "7474ab81-486f-40b5-8685-3a4f8ea624cb");
+        /// An artist having written a work, if it's unknown whether the music
+        /// or the lyrics.
+        const WRITER = (Writer, "a255bca1-b157-4518-9108-7b147dc3fc68");
+        /// A pseudo-release providing a translated or transliterated track list
+        /// of a release.
+        const TRANSL_TRACKLISTING =
+            (TranslTracklisting, "fc399d47-23a7-4c28-bfcf-0607a562b644");
+        /// The official homepage of an artist.
+        const OFFICIAL_HOMEPAGE =
+            (OfficialHomepage, "fe33d22f-c3b0-4d68-bd53-a856badf2b15");
+        /// The Wikidata item of an artist.
+        const WIKIDATA = (Wikidata, "689870a4-a1e4-4912-b17f-7b2664215698");
+        /// The Wikipedia article of an artist.
+        const WIKIPEDIA = (Wikipedia, "29651736-fa6d-48e4-aadc-a557c6add1cb");
+        /// The Discogs page of an artist.
+        const DISCOGS = (Discogs, "04a5b104-a4c2-4bac-99a1-7b837c37d9e4");
+        /// The Bandcamp page of an artist.
+        const BANDCAMP = (Bandcamp, "c550166e-0548-4a18-b1d4-e2ae423a3e88");
+    }
+
+    /// Look up the relationship type with the specified MBID among the types
+    /// for which a constant is provided.
+    pub fn by_id(id: &Mbid) -> Option<RelationshipType>
+    {
+        ALL.iter().find(|t| t.mbid() == *id).cloned()
+    }
+}
+
+impl FromXmlElement for Relation {}
+impl FromXml for Relation {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        let target_type: String = read_attr(reader, "..", "target-type")?;
+        let target = match target_type.as_str() {
+            "area" => RelationTarget::Area(reader.read("./mb:area")?),
+            "artist" => RelationTarget::Artist(reader.read("./mb:artist")?),
+            "event" => RelationTarget::Event(reader.read("./mb:event")?),
+            "label" => RelationTarget::Label(reader.read("./mb:label")?),
+            "place" => RelationTarget::Place(reader.read("./mb:place")?),
+            "recording" => RelationTarget::Recording(reader.read("./mb:recording")?),
+            "release" => RelationTarget::Release(reader.read("./mb:release")?),
+            "series" => RelationTarget::Series(reader.read("./mb:series")?),
+            "work" => RelationTarget::Work(reader.read("./mb:work")?),
+            "url" => RelationTarget::Url(reader.read("./mb:target/text()")?),
+            other => RelationTarget::Other {
+                target_type: other.to_string(),
+                mbid: reader.read("./mb:target/text()")?,
+            },
+        };
+
+        let ended: Option<String> = reader.read_option("./mb:ended/text()")?;
+
+        Ok(Relation {
+            relation_type: read_attr(reader, ".", "type")?,
+            type_id: read_attr_option(reader, ".", "type-id")?,
+            direction: reader.read_option("./mb:direction/text()")?.unwrap_or(
+                Direction::Forward,
+            ),
+            target: target,
+            begin: reader.read_option("./mb:begin/text()")?,
+            end: reader.read_option("./mb:end/text()")?,
+            ended: ended.as_ref().map(|s| s.as_str()) == Some("true"),
+            attributes: read_mb_vec(reader, "./mb:attribute-list/mb:attribute")?,
+            ordering_key: reader.read_option("./mb:ordering-key/text()")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use xpath_reader::XpathStrReader;
+
+    const XML: &'static str = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><artist id="a74b1b7f-71a5-4011-9441-d0b5e4122711" type="Group"><name>Radiohead</name><sort-name>Radiohead</sort-name><relation-list target-type="artist"><relation type="member of band" type-id="5be4c609-9afa-4ea0-910b-12ffb71e3821"><target>8bfac288-ccc5-448d-9573-c33ea2aa5c30</target><direction>backward</direction><attribute-list><attribute>lead vocals</attribute></attribute-list><begin>1985</begin><artist id="8bfac288-ccc5-448d-9573-c33ea2aa5c30" type="Person"><name>Thom Yorke</name><sort-name>Yorke, Thom</sort-name></artist></relation></relation-list><relation-list target-type="url"><relation type="official homepage" type-id="fe33d22f-c3b0-4d68-bd53-a856badf2b15"><target>https://www.radiohead.com/</target></relation></relation-list></artist></metadata>"#;
+
+    fn read_relations() -> Vec<Relation>
+    {
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(XML, &context).unwrap();
+        reader.read_vec("//mb:relation-list/mb:relation").unwrap()
+    }
+
+    #[test]
+    fn read_relations_artist()
+    {
+        let relations = read_relations();
+        assert_eq!(relations.len(), 2);
+
+        let member = &relations[0];
+        assert!(member.is(&RelType::MemberOf));
+        assert_eq!(
+            member.type_id,
+            Some(Mbid::from_str("5be4c609-9afa-4ea0-910b-12ffb71e3821").unwrap())
+        );
+        assert_eq!(member.direction, Direction::Backward);
+        assert_eq!(member.begin, Some(PartialDate::from_str("1985").unwrap()));
+        assert_eq!(member.end, None);
+        assert!(!member.ended);
+        assert_eq!(
+            member.attributes,
+            vec![
+                RelationAttribute {
+                    name: "lead vocals".to_string(),
+                    value: None,
+                },
+            ]
+        );
+        assert_eq!(member.ordering_key, None);
+        assert_eq!(member.series_number(), None);
+        match member.target {
+            RelationTarget::Artist(ref artist) => {
+                assert_eq!(artist.name, "Thom Yorke".to_string());
+                assert_eq!(
+                    artist.mbid,
+                    Mbid::from_str("8bfac288-ccc5-448d-9573-c33ea2aa5c30").unwrap()
+                );
+            }
+            ref t => panic!("unexpected target: {:?}", t),
+        }
+    }
+
+    #[test]
+    fn relationship_types()
+    {
+        let relations = read_relations();
+        assert!(relations[0].is_type(&types::MEMBER_OF_BAND));
+        assert!(!relations[0].is_type(&types::COLLABORATION));
+        assert!(relations[1].is_type(&types::OFFICIAL_HOMEPAGE));
+
+        // Without a type id the name is compared.
+        let mut homepage = relations[1].clone();
+        homepage.type_id = None;
+        assert!(homepage.is_type(&types::OFFICIAL_HOMEPAGE));
+
+        assert_eq!(
+            types::by_id(&Mbid::from_str("5be4c609-9afa-4ea0-910b-12ffb71e3821").unwrap()),
+            Some(types::MEMBER_OF_BAND)
+        );
+        assert_eq!(
+            types::by_id(&Mbid::from_str("b0d44366-cdf0-3acb-bee6-0f65a77a6ef0").unwrap()),
+            None
+        );
+        for rel_type in types::ALL {
+            rel_type.mbid();
+            assert_eq!(rel_type.rel_type.relationship_type().as_ref(), Some(rel_type));
+        }
+        assert_eq!(types::MEMBER_OF_BAND.name(), "member of band".to_string());
+        assert!(relations[0].is(&RelType::MemberOf));
+        assert_eq!(RelType::Imprint.relationship_type(), None);
+    }
+
+    #[test]
+    fn relationship_types_of_other_entities()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><label id="df7d1c7f-ef95-425f-8eef-445b3d7bcbd9"><name>Parlophone</name><relation-list target-type="url"><relation type="official homepage" type-id="fe81f6ee-9f2e-4d04-abb2-7fb0d6a8b4c8"><target>https://www.parlophone.co.uk/</target></relation><relation type="discogs" type-id="5b987f87-25bc-4a2d-b3f1-3618795b8207"><target>https://www.discogs.com/label/2294</target></relation></relation-list><relation-list target-type="recording"><relation type="performer" type-id="628a9658-f54c-4142-b0c0-95f031b544da"><target>70595637-9310-45f2-a266-58f8de4874a7</target><recording id="70595637-9310-45f2-a266-58f8de4874a7"><title>Creep</title></recording></relation></relation-list></label></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let relations: Vec<Relation> = reader.read_vec("//mb:relation-list/mb:relation").unwrap();
+
+        // The label-url types have other MBIDs than the artist-url constants.
+        assert!(relations[0].is(&RelType::OfficialHomepage));
+        assert!(!relations[0].is_type(&types::OFFICIAL_HOMEPAGE));
+        assert!(relations[1].is(&RelType::Discogs));
+        assert!(!relations[1].is(&RelType::OfficialHomepage));
+
+        // Known MBIDs are still compared.
+        assert!(relations[2].is(&RelType::Performer));
+        assert!(!relations[2].is(&RelType::Vocal));
+    }
+
+    #[test]
+    fn read_relations_url()
+    {
+        let relations = read_relations();
+        let homepage = &relations[1];
+        assert_eq!(homepage.relation_type, "official homepage".to_string());
+        assert_eq!(homepage.direction, Direction::Forward);
+        assert_eq!(
+            homepage.target,
+            RelationTarget::Url("https://www.radiohead.com/".to_string())
+        );
+        assert_eq!(homepage.target.mbid(), None);
+    }
+
+    #[test]
+    fn read_relations_series()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><series id="d977f7fd-96c9-4e3e-83b5-eb484a9e6582" type="Catalogue"><name>Bach-Werke-Verzeichnis</name><relation-list target-type="work"><relation type="part of" type-id="b0d44366-cdf0-3acb-bee6-0f65a77a6ef0"><target>1a16b2cb-0e43-3b9b-a5a7-5bcd0ccb1a6f</target><ordering-key>2</ordering-key><direction>backward</direction><attribute-list><attribute value="BWV 2">number</attribute></attribute-list><work id="1a16b2cb-0e43-3b9b-a5a7-5bcd0ccb1a6f"><title>Ach Gott, vom Himmel sieh darein, BWV 2</title></work></relation></relation-list></series></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let relations: Vec<Relation> = reader.read_vec("//mb:relation-list/mb:relation").unwrap();
+
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].ordering_key, Some(2));
+        assert_eq!(relations[0].series_number(), Some("BWV 2"));
+        match relations[0].target {
+            RelationTarget::Work(ref work) => {
+                assert_eq!(work.title, "Ach Gott, vom Himmel sieh darein, BWV 2".to_string())
+            }
+            ref t => panic!("unexpected target: {:?}", t),
+        }
+    }
+}
@@ -2,9 +2,15 @@ use std::time::Duration;
 use xpath_reader::{FromXml, FromXmlError, XpathReader};
 use xpath_reader::reader::{FromXmlContained, FromXmlElement};
 
-use entities::{Language, Mbid, Resource};
+#[cfg(feature = "client")]
+use client::Client;
+use entities::{catalog_numbers_match, Include, Isrc, Language, Mbid, Resource};
+#[cfg(feature = "client")]
+use errors::ClientError;
+use entities::helper::{mark_requested, read_attr, read_attr_option, read_mb_list, read_mb_vec};
 use entities::date::PartialDate;
-use entities::refs::{ArtistRef, LabelRef, RecordingRef};
+use entities::refs::{AreaRef, ArtistRef, LabelRef, RecordingRef, ReleaseRef};
+use entities::relationships::{filter_relations, Direction, RelType, Relation, RelationTarget};
 
 /// Describes a single track, `Releases` consist of multiple `ReleaseTrack`s.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -32,6 +38,18 @@ pub struct ReleaseTrack {
 
     /// The recording used for the track.
     pub recording: RecordingRef,
+
+    /// The artists the track is credited to.
+    ///
+    /// Only available if the release was fetched with
+    /// `Include::ArtistCredits`, otherwise this is empty.
+    pub artists: Vec<ArtistRef>,
+
+    /// The ISRCs of the recording used for the track.
+    ///
+    /// Only available if the release was fetched with `Include::Isrcs`,
+    /// otherwise this is empty.
+    pub isrcs: Vec<Isrc>,
 }
 
 impl FromXmlElement for ReleaseTrack {}
@@ -40,13 +58,22 @@ impl FromXml for ReleaseTrack {
     where
         R: XpathReader<'d>,
     {
+        // Tracks usually have their own credit, but fall back to the one of the
+        // recording if they don't.
+        let mut artists = read_mb_vec(reader, "./mb:artist-credit/mb:name-credit")?;
+        if artists.is_empty() {
+            artists = read_mb_vec(reader, "./mb:recording/mb:artist-credit/mb:name-credit")?;
+        }
+
         Ok(ReleaseTrack {
-            mbid: reader.read(".//@id")?,
+            mbid: read_attr(reader, ".", "id")?,
             position: reader.read(".//mb:position/text()")?,
             number: reader.read(".//mb:number/text()")?,
             title: reader.read(".//mb:title/text()")?,
             length: ::entities::helper::read_mb_duration(reader, ".//mb:length/text()")?,
             recording: reader.read(".//mb:recording")?,
+            artists: artists,
+            isrcs: read_mb_vec(reader, "./mb:recording/mb:isrc-list/mb:isrc/@id")?,
         })
     }
 }
@@ -70,15 +97,27 @@ enum_mb_xml! {
 pub struct ReleaseMedium {
     /// The medium's position number providing a total order between all
     /// mediums of one `Release`.
-    position: u16,
+    pub position: u16,
 
     /// The format of this `ReleaseMedium`.
     ///
     /// TODO: Parse into `ReleaseMediumFormat` enum.
-    format: Option<String>,
+    pub format: Option<String>,
 
     /// The tracks stored on this medium.
-    tracks: Vec<ReleaseTrack>,
+    ///
+    /// If the server only returned a part of the track list, this contains the
+    /// `tracks.len()` tracks starting at `track_offset`, check `is_partial`.
+    /// `None` if the tracks weren't requested (`Include::Recordings`), e. g.
+    /// if only `Include::Media` was requested.
+    pub tracks: Option<Vec<ReleaseTrack>>,
+
+    /// The offset of the first contained track in the full track list.
+    pub track_offset: u32,
+
+    /// The total number of tracks on this medium, which can be larger than
+    /// the number of contained tracks.
+    pub track_count: u32,
 }
 
 impl FromXmlElement for ReleaseMedium {}
@@ -87,14 +126,124 @@ impl FromXml for ReleaseMedium {
     where
         R: XpathReader<'d>,
     {
+        let tracks: Option<Vec<ReleaseTrack>> =
+            read_mb_list(reader, ".//mb:track-list", "/mb:track")?;
+        let track_count = read_attr_option(reader, ".//mb:track-list", "count")?.unwrap_or(
+            tracks.as_ref().map_or(0, |t| t.len() as u32),
+        );
+        // Without `Include::Recordings` the track list only contains the count.
+        let tracks = if track_count > 0 && tracks.as_ref().map_or(false, |t| t.is_empty()) {
+            None
+        } else {
+            tracks
+        };
+
         Ok(ReleaseMedium {
             position: reader.read(".//mb:position/text()")?,
             format: reader.read_option(".//mb:format/text()")?,
-            tracks: reader.read_vec(".//mb:track-list/mb:track")?,
+            track_offset: read_attr_option(reader, ".//mb:track-list", "offset")?.unwrap_or(0),
+            track_count: track_count,
+            tracks: tracks,
         })
     }
 }
 
+/// The total length of a `ReleaseMedium` or `Release`, as returned by their
+/// `total_length` methods.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TotalLength {
+    /// The sum of the lengths of all tracks with a known length.
+    pub length: Duration,
+
+    /// `false` if the length of at least one track is unknown or not all tracks
+    /// were returned by the server, so `length` is shorter than the actual
+    /// length.
+    pub complete: bool,
+}
+
+impl TotalLength {
+    fn of_tracks(tracks: &[ReleaseTrack], partial: bool) -> Self
+    {
+        tracks.iter().fold(
+            TotalLength {
+                length: Duration::from_secs(0),
+                complete: !partial,
+            },
+            |total, track| match track.length {
+                Some(length) => TotalLength {
+                    length: total.length + length,
+                    complete: total.complete,
+                },
+                None => TotalLength {
+                    length: total.length,
+                    complete: false,
+                },
+            },
+        )
+    }
+}
+
+impl ReleaseMedium {
+    /// The sum of the lengths of all tracks of this medium.
+    ///
+    /// Tracks of unknown length are skipped, in which case the result is
+    /// marked as not `complete`.
+    pub fn total_length(&self) -> TotalLength
+    {
+        TotalLength::of_tracks(self.track_list(), self.is_partial())
+    }
+
+    /// Returns `true` if `tracks` doesn't contain all tracks of the medium,
+    /// i. e. the server truncated the track list or the tracks weren't
+    /// requested.
+    pub fn is_partial(&self) -> bool
+    {
+        self.track_offset > 0 || (self.track_list().len() as u32) < self.track_count
+    }
+
+    /// The contained tracks, empty if they weren't requested.
+    fn track_list(&self) -> &[ReleaseTrack]
+    {
+        match self.tracks {
+            Some(ref tracks) => tracks.as_slice(),
+            None => &[],
+        }
+    }
+
+    /// Compare the lengths of the tracks of this medium with the provided
+    /// lengths, e. g. the lengths of the files of a local album.
+    ///
+    /// Returns a score between `0.0` and `1.0` which is the fraction of tracks
+    /// whose length differs at most `tolerance` from the corresponding provided
+    /// length. Tracks of unknown length never match. If the number of lengths
+    /// differs from the number of tracks the score is `0.0`.
+    pub fn match_track_lengths(&self, lengths: &[Duration], tolerance: Duration) -> f32
+    {
+        let tracks = self.track_list();
+        if lengths.len() != tracks.len() || lengths.is_empty() {
+            return 0.;
+        }
+
+        let matches = tracks
+            .iter()
+            .zip(lengths.iter())
+            .filter(|&(track, length)| match track.length {
+                Some(track_length) => {
+                    let diff = if track_length > *length {
+                        track_length - *length
+                    } else {
+                        *length - track_length
+                    };
+                    diff <= tolerance
+                }
+                None => false,
+            })
+            .count();
+
+        matches as f32 / lengths.len() as f32
+    }
+}
+
 enum_mb_xml! {
     pub enum ReleaseStatus {
         /// Release officially sanctioned by the artist and/or their record company.
@@ -139,7 +288,7 @@ impl FromXml for LabelInfo {
     {
         Ok(LabelInfo {
             label: {
-                let id: Option<String> = reader.read_option(".//@id")?;
+                let id: Option<String> = read_attr_option(reader, "./mb:label", "id")?;
                 match id {
                     Some(_) => Some(reader.read(".")?),
                     None => None,
@@ -150,6 +299,60 @@ impl FromXml for LabelInfo {
     }
 }
 
+impl LabelInfo {
+    /// Returns `true` if the catalog number matches the one of this label
+    /// info, tolerating differences in case, spacing, separators and leading
+    /// zeros, see `catalog_numbers_match`.
+    ///
+    /// This is useful to match catalog numbers typed by humans, e. g. in the
+    /// tags of a rip.
+    pub fn matches_catalog_number(&self, catalog_number: &str) -> bool
+    {
+        self.catalog_number
+            .as_ref()
+            .map_or(false, |own| catalog_numbers_match(own, catalog_number))
+    }
+}
+
+/// The issuing of a `Release` in one area at one date.
+///
+/// A release can have multiple release events, e. g. if it was released at
+/// different dates in different countries.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseEvent {
+    /// The date the release was issued in the area.
+    pub date: Option<PartialDate>,
+
+    /// The area the release was issued in, usually a country.
+    pub area: Option<AreaRef>,
+}
+
+impl FromXmlElement for ReleaseEvent {}
+impl FromXml for ReleaseEvent {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        Ok(ReleaseEvent {
+            date: reader.read_option("./mb:date/text()")?,
+            area: reader.read_option("./mb:area")?,
+        })
+    }
+}
+
+/// Returns the earliest date of the events with a known year.
+///
+/// Unknown months and days are considered earlier than known ones, e. g.
+/// `2004` is earlier than `2004-06-09`.
+fn earliest_date(events: &[ReleaseEvent]) -> Option<&PartialDate>
+{
+    events
+        .iter()
+        .filter_map(|event| event.date.as_ref())
+        .filter(|date| date.year().is_some())
+        .min_by_key(|date| (date.year(), date.month(), date.day()))
+}
+
 /// A `Release` is any publication of one or more tracks.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Release {
@@ -160,7 +363,10 @@ pub struct Release {
     pub title: String,
 
     /// The artists that the release is primarily credited to.
-    pub artists: Vec<ArtistRef>,
+    ///
+    /// `None` if the artists weren't requested (`Include::Artists` or
+    /// `Include::ArtistCredits`).
+    pub artists: Option<Vec<ArtistRef>>,
 
     /// The date the release was issued.
     pub date: Option<PartialDate>,
@@ -168,8 +374,16 @@ pub struct Release {
     /// The country the release was issued in.
     pub country: Option<String>,
 
+    /// All release events of the release in document order, each pairing a
+    /// date with an area.
+    ///
+    /// `date` and `country` only provide the first of these.
+    pub release_events: Vec<ReleaseEvent>,
+
     /// The labels which issued this release.
-    pub labels: Vec<LabelInfo>,
+    ///
+    /// `None` if the labels weren't requested (`Include::Labels`).
+    pub labels: Option<Vec<LabelInfo>>,
 
     /// Barcode of the release, if it has one.
     pub barcode: Option<String>,
@@ -196,7 +410,13 @@ pub struct Release {
     pub annotation: Option<String>,
 
     /// The mediums (disks) of the release.
-    pub mediums: Vec<ReleaseMedium>,
+    ///
+    /// `None` if the mediums weren't requested (`Include::Media` or
+    /// `Include::Recordings`).
+    pub mediums: Option<Vec<ReleaseMedium>>,
+
+    /// Whether the Cover Art Archive has artwork for this release.
+    pub cover_art: bool,
 }
 
 impl FromXmlContained for Release {}
@@ -205,19 +425,27 @@ impl FromXml for Release {
     where
         R: XpathReader<'d>,
     {
+        let cover_art: Option<String> =
+            reader.read_option(".//mb:release/mb:cover-art-archive/mb:artwork/text()")?;
+
         Ok(Release {
             annotation: reader.read_option(".//mb:release/mb:annotation/mb:text/text()")?,
-            artists: reader.read_vec(".//mb:release/mb:artist-credit/mb:name-credit")?,
+            artists: read_mb_list(reader, ".//mb:release/mb:artist-credit", "/mb:name-credit")?,
             barcode: reader.read_option(".//mb:release/mb:barcode/text()")?,
             country: reader.read_option(".//mb:release/mb:country/text()")?,
+            cover_art: cover_art.as_ref().map(|a| a.as_str()) == Some("true"),
             date: reader.read_option(".//mb:release/mb:date/text()")?,
             disambiguation: reader.read_option(".//mb:release/mb:disambiguation/text()")?,
-            labels: reader.read_vec(".//mb:release/mb:label-info-list/mb:label-info")?,
+            labels: read_mb_list(reader, ".//mb:release/mb:label-info-list", "/mb:label-info")?,
             language:
                 reader.read_option(".//mb:release/mb:text-representation/mb:language/text()")?,
-            mbid: reader.read(".//mb:release/@id")?,
-            mediums: reader.read_vec(".//mb:release/mb:medium-list/mb:medium")?,
+            mbid: read_attr(reader, ".//mb:release", "id")?,
+            mediums: read_mb_list(reader, ".//mb:release/mb:medium-list", "/mb:medium")?,
             packaging: reader.read_option(".//mb:release/mb:packaging/text()")?,
+            release_events: read_mb_vec(
+                reader,
+                ".//mb:release/mb:release-event-list/mb:release-event",
+            )?,
             script: reader.read_option(".//mb:release/mb:text-representation/mb:script/text()")?,
             status: reader.read_option(".//mb:release/mb:status/text()")?,
             title: reader.read(".//mb:release/mb:title/text()")?,
@@ -225,6 +453,239 @@ impl FromXml for Release {
     }
 }
 
+impl Release {
+    /// Fetch the release with everything needed to tag its tracks: besides the
+    /// default data this includes the artist credits of the tracks and the
+    /// ISRCs of their recordings.
+    #[cfg(feature = "client")]
+    pub fn fetch_for_tagging(client: &mut Client, mbid: &Mbid) -> Result<Release, ClientError>
+    {
+        client.get_by_mbid_with_includes(
+            mbid,
+            &[
+                Include::Aliases,
+                Include::Annotation,
+                Include::Artists,
+                Include::Labels,
+                Include::Recordings,
+                Include::Isrcs,
+                Include::ArtistCredits,
+            ],
+        )
+    }
+
+    /// The label info with the catalog number, see
+    /// `LabelInfo::matches_catalog_number`.
+    ///
+    /// Returns `None` if there is no such label info or the labels weren't
+    /// requested.
+    pub fn label_info_by_catalog_number(&self, catalog_number: &str) -> Option<&LabelInfo>
+    {
+        self.labels.as_ref().and_then(|labels| {
+            labels.iter().find(|info| info.matches_catalog_number(catalog_number))
+        })
+    }
+
+    /// The earliest date the release was issued at in any area.
+    pub fn earliest_release_date(&self) -> Option<&PartialDate>
+    {
+        earliest_date(&self.release_events).or(self.date.as_ref())
+    }
+
+    /// Returns `true` if this is a pseudo-release, e. g. a translated or
+    /// transliterated track list of another release.
+    ///
+    /// Pseudo-releases don't correspond to anything which was actually
+    /// released, so most applications want to ignore them.
+    pub fn is_pseudo_release(&self) -> bool
+    {
+        self.status == Some(ReleaseStatus::PseudoRelease)
+    }
+
+    /// Returns `true` if the release is credited to *Various Artists*.
+    ///
+    /// When tagging the tracks of such a release the "album artist" should be
+    /// set to *Various Artists* while the tracks keep their own artists.
+    pub fn is_compilation(&self) -> bool
+    {
+        self.artists
+            .as_ref()
+            .map_or(false, |artists| artists.iter().any(|a| a.is_various_artists()))
+    }
+
+    /// The sum of the lengths of all tracks on all mediums of this release.
+    ///
+    /// Tracks of unknown length are skipped, in which case the result is
+    /// marked as not `complete`.
+    pub fn total_length(&self) -> TotalLength
+    {
+        self.medium_list().iter().map(|medium| medium.total_length()).fold(
+            TotalLength {
+                length: Duration::from_secs(0),
+                complete: true,
+            },
+            |total, medium| TotalLength {
+                length: total.length + medium.length,
+                complete: total.complete && medium.complete,
+            },
+        )
+    }
+
+    /// The total number of tracks on all mediums of this release.
+    pub fn track_count(&self) -> usize
+    {
+        self.medium_list().iter().map(|medium| medium.track_list().len()).sum()
+    }
+
+    /// The mediums of this release, empty if they weren't requested.
+    fn medium_list(&self) -> &[ReleaseMedium]
+    {
+        match self.mediums {
+            Some(ref mediums) => mediums.as_slice(),
+            None => &[],
+        }
+    }
+
+    /// All tracks of this release in order, paired with the position of the
+    /// medium they are contained in.
+    pub fn all_tracks(&self) -> Vec<(u16, &ReleaseTrack)>
+    {
+        self.medium_list()
+            .iter()
+            .flat_map(|medium| {
+                medium.track_list().iter().map(move |track| (medium.position, track))
+            })
+            .collect()
+    }
+
+    /// The number of a track when numbering the tracks of all mediums of this
+    /// release continuously, e. g. `12` for the second track of the second
+    /// medium if the first medium has ten tracks.
+    ///
+    /// Returns `None` if there is no such medium or track.
+    pub fn continuous_track_number(&self, medium: u16, track: u16) -> Option<u32>
+    {
+        let track_count = match self.medium_list().iter().find(|m| m.position == medium) {
+            Some(m) => m.track_count,
+            None => return None,
+        };
+        if track < 1 || track as u32 > track_count {
+            return None;
+        }
+
+        // Like in `track_for_continuous_number` the mediums are counted in the
+        // order of their positions, not the one of the response.
+        let preceding: u32 = self.medium_list()
+            .iter()
+            .filter(|m| m.position < medium)
+            .map(|m| m.track_count)
+            .sum();
+        Some(preceding + track as u32)
+    }
+
+    /// The positions of the medium and the track on it of the track with the
+    /// specified continuous number, the inverse of `continuous_track_number`.
+    pub fn track_for_continuous_number(&self, number: u32) -> Option<(u16, u16)>
+    {
+        let mut mediums: Vec<&ReleaseMedium> = self.medium_list().iter().collect();
+        mediums.sort_by_key(|m| m.position);
+
+        let mut preceding = 0;
+        for m in mediums {
+            if number > preceding && number <= preceding + m.track_count {
+                return Some((m.position, (number - preceding) as u16));
+            }
+            preceding += m.track_count;
+        }
+        None
+    }
+
+    /// Find the first track of this release using the recording with the
+    /// specified MBID.
+    pub fn find_track_by_recording(&self, recording: &Mbid) -> Option<&ReleaseTrack>
+    {
+        self.medium_list()
+            .iter()
+            .flat_map(|medium| medium.track_list().iter())
+            .find(|track| track.recording.mbid == *recording)
+    }
+
+    /// Fetch the pseudo-releases providing a translated or transliterated
+    /// track list of this release.
+    #[cfg(feature = "client")]
+    pub fn fetch_track_list_versions(
+        &self,
+        client: &mut Client,
+    ) -> Result<Vec<ReleaseRef>, ClientError>
+    {
+        let relations = client.get_relations::<Release>(&self.mbid)?;
+        Ok(track_list_versions(&relations))
+    }
+
+    /// Fetch the version of this release with the track list written in the
+    /// script with the ISO 15924 code `script` (e. g. `"Latn"`) for tagging,
+    /// e. g. to display Latin script titles of a Japanese release.
+    ///
+    /// Returns this release if it already is written in the script, otherwise
+    /// the first pseudo-release written in the script or `None` if there is
+    /// none. Every pseudo-release costs one request.
+    #[cfg(feature = "client")]
+    pub fn fetch_in_script(
+        &self,
+        client: &mut Client,
+        script: &str,
+    ) -> Result<Option<Release>, ClientError>
+    {
+        if self.script.as_ref().map(|s| s.as_str()) == Some(script) {
+            return Ok(Some(self.clone()));
+        }
+
+        for version in self.fetch_track_list_versions(client)? {
+            let release = Release::fetch_for_tagging(client, &version.mbid)?;
+            if release.script.as_ref().map(|s| s.as_str()) == Some(script) {
+                return Ok(Some(release));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The titles of the tracks of this release as listed in `version`, e. g.
+    /// a transliteration returned by `fetch_in_script`, in the order of
+    /// `all_tracks`.
+    ///
+    /// Tracks are paired by the positions of their medium and of themselves on
+    /// it. Tracks missing from `version` keep their own title.
+    pub fn track_titles_in<'a>(&'a self, version: &'a Release) -> Vec<&'a str>
+    {
+        self.all_tracks()
+            .into_iter()
+            .map(|(medium, track)| {
+                version
+                    .medium_list()
+                    .iter()
+                    .filter(|m| m.position == medium)
+                    .flat_map(|m| m.track_list().iter())
+                    .find(|t| t.position == track.position)
+                    .unwrap_or(track)
+                    .title
+                    .as_str()
+            })
+            .collect()
+    }
+}
+
+/// The pseudo-releases with the track list of a release among its relations.
+fn track_list_versions(relations: &[Relation]) -> Vec<ReleaseRef>
+{
+    filter_relations(relations, &[RelType::TranslTracklisting], Direction::Forward)
+        .into_iter()
+        .filter_map(|r| match r.target {
+            RelationTarget::Release(ref release) => Some(release.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
 impl Resource for Release {
     fn get_name() -> &'static str
     {
@@ -235,13 +696,47 @@ impl Resource for Release {
     {
         "aliases+annotation+artists+labels+recordings"
     }
+
+    fn mark_requested(&mut self, includes: &[Include])
+    {
+        mark_requested(
+            &mut self.artists,
+            includes,
+            &[Include::Artists, Include::ArtistCredits],
+        );
+        mark_requested(&mut self.labels, includes, &[Include::Labels]);
+        mark_requested(
+            &mut self.mediums,
+            includes,
+            &[Include::Media, Include::Recordings],
+        );
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use entities::Alias;
     use std::str::FromStr;
 
+    /// The aliases of the fixtures, which all have the name as sort name.
+    fn aliases(names: &[&str]) -> Vec<Alias>
+    {
+        names
+            .iter()
+            .map(|name| {
+                Alias {
+                    name: name.to_string(),
+                    sort_name: Some(name.to_string()),
+                    locale: None,
+                    primary: false,
+                    alias_type: None,
+                }
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "client")]
     #[test]
     fn release_read_xml1()
     {
@@ -252,13 +747,20 @@ mod tests {
         assert_eq!(release.title, "Creep".to_string());
         assert_eq!(
             release.artists,
-            vec![
+            Some(vec![
                 ArtistRef {
                     mbid: Mbid::from_str("a74b1b7f-71a5-4011-9441-d0b5e4122711").unwrap(),
                     name: "Radiohead".to_string(),
                     sort_name: "Radiohead".to_string(),
+                    artist_type: None,
+                    disambiguation: None,
+                    aliases: aliases(
+                        &["Radio head", "Radiohead & Thom Yorke", "r/head", "レディオヘッド"],
+                    ),
+                    credited_as: None,
+                    join_phrase: None,
                 },
-            ]
+            ])
         );
         assert_eq!(
             release.date,
@@ -266,28 +768,54 @@ mod tests {
         );
         assert_eq!(release.country, Some("GB".to_string()));
         assert_eq!(
-            release.labels,
+            release.release_events,
             vec![
+                ReleaseEvent {
+                    date: Some(PartialDate::from_str("1992-09-21").unwrap()),
+                    area: Some(AreaRef {
+                        mbid: Mbid::from_str("8a754a16-0027-3a29-b6d7-2b40ea0481ed").unwrap(),
+                        name: "United Kingdom".to_string(),
+                        sort_name: "United Kingdom".to_string(),
+                        area_type: None,
+                        iso_3166: Some("GB".to_string()),
+                        disambiguation: None,
+                    }),
+                },
+            ]
+        );
+        assert_eq!(
+            release.earliest_release_date(),
+            Some(&PartialDate::from_str("1992-09-21").unwrap())
+        );
+        assert_eq!(
+            release.labels,
+            Some(vec![
                 LabelInfo {
                     label: Some(LabelRef {
                         mbid: Mbid::from_str("df7d1c7f-ef95-425f-8eef-445b3d7bcbd9").unwrap(),
                         name: "Parlophone".to_string(),
                         sort_name: "Parlophone".to_string(),
                         label_code: Some("299".to_string()),
+                        aliases: aliases(&["Parlaphone", "Parlophone Records"]),
                     }),
                     catalog_number: Some("CDR 6078".to_string()),
                 },
-            ]
+            ])
         );
+        assert!(release.label_info_by_catalog_number("cdr6078").is_some());
+        assert!(release.label_info_by_catalog_number("CDR 6079").is_none());
         assert_eq!(release.barcode, Some("724388023429".to_string()));
         assert_eq!(release.status, Some(ReleaseStatus::Official));
         assert_eq!(release.language, Some(Language::from_639_3("eng").unwrap()));
         assert_eq!(release.script, Some("Latn".to_string()));
         // TODO: check disambiguation
         // assert_eq!(release.disambiguation,
-        assert_eq!(release.mediums.len(), 1);
+        assert_eq!(release.mediums.as_ref().unwrap().len(), 1);
+        assert!(release.cover_art);
+        assert!(!release.is_compilation());
     }
 
+    #[cfg(feature = "client")]
     #[test]
     fn release_read_xml2()
     {
@@ -298,13 +826,14 @@ mod tests {
         assert_eq!(release.packaging, Some("Jewel Case".to_string()));
         assert_eq!(
             release.labels,
-            vec![
+            Some(vec![
                 LabelInfo {
                     label: Some(LabelRef {
                         mbid: Mbid::from_str("376d9b4d-8cdd-44be-bc0f-ed5dfd2d2340").unwrap(),
                         name: "Cherrytree Records".to_string(),
                         sort_name: "Cherrytree Records".to_string(),
                         label_code: None,
+                        aliases: Vec::new(),
                     }),
                     catalog_number: Some("0251766489".to_string()),
                 },
@@ -314,6 +843,11 @@ mod tests {
                         name: "Interscope Records".to_string(),
                         sort_name: "Interscope Records".to_string(),
                         label_code: Some("6406".to_string()),
+                        aliases: aliases(&[
+                            "Flip/Interscope Records",
+                            "Interscape Records",
+                            "Nothing/Interscope",
+                        ]),
                     }),
                     catalog_number: Some("0251766489".to_string()),
                 },
@@ -323,6 +857,7 @@ mod tests {
                         name: "Konlive".to_string(),
                         sort_name: "Konlive".to_string(),
                         label_code: None,
+                        aliases: aliases(&["Kon Live"]),
                     }),
                     catalog_number: Some("0251766489".to_string()),
                 },
@@ -332,6 +867,7 @@ mod tests {
                         name: "Streamline Records".to_string(),
                         sort_name: "Streamline Records".to_string(),
                         label_code: None,
+                        aliases: Vec::new(),
                     }),
                     catalog_number: Some("0251766489".to_string()),
                 },
@@ -341,27 +877,37 @@ mod tests {
                         name: "Universal Music Canada".to_string(),
                         sort_name: "Universal Music Canada".to_string(),
                         label_code: None,
+                        aliases: aliases(
+                            &["Universal Music (Canada)", "Universal Music Canada in."],
+                        ),
                     }),
                     catalog_number: Some("0251766489".to_string()),
                 },
-            ]
+            ])
         );
-        assert_eq!(release.mediums.len(), 1);
+        assert_eq!(release.mediums.as_ref().unwrap().len(), 1);
+        let artists = release.artists.unwrap();
+        assert!(artists[0].is_known_as("Lady Ga Ga"));
+        assert!(artists[0].is_known_as("Germanotta, Stefani Joanne Angelina"));
+        let labels = release.labels.unwrap();
+        assert!(labels[1].label.as_ref().unwrap().is_known_as("interscape records"));
     }
 
+    #[cfg(feature = "client")]
     #[test]
     fn read_tracks()
     {
         let mbid = Mbid::from_str("d1881a4c-0188-4f0f-a2e7-4e7849aec109").unwrap();
         let release: Release = ::util::test_utils::fetch_entity(&mbid).unwrap();
 
-        let mediums = release.mediums;
+        let mediums = release.mediums.unwrap();
         assert_eq!(mediums.len(), 1);
         let medium = mediums.get(0).unwrap();
         assert_eq!(medium.position, 1);
-        assert_eq!(medium.tracks.len(), 3);
+        let tracks = medium.tracks.as_ref().unwrap();
+        assert_eq!(tracks.len(), 3);
         assert_eq!(
-            medium.tracks[0],
+            tracks[0],
             ReleaseTrack {
                 mbid: Mbid::from_str("ac898be7-2965-4d17-9ac8-48d45852d73c").unwrap(),
                 position: 1,
@@ -373,10 +919,12 @@ mod tests {
                     title: "Puella Tenebrarum".to_string(),
                     length: Some(Duration::from_millis(232000)),
                 },
+                artists: Vec::new(),
+                isrcs: Vec::new(),
             }
         );
         assert_eq!(
-            medium.tracks[1],
+            tracks[1],
             ReleaseTrack {
                 mbid: Mbid::from_str("21648b0b-deaf-4b93-a257-5fc18363b25d").unwrap(),
                 position: 2,
@@ -388,10 +936,12 @@ mod tests {
                     title: "Lamina Maledictum".to_string(),
                     length: Some(Duration::from_millis(258000)),
                 },
+                artists: Vec::new(),
+                isrcs: Vec::new(),
             }
         );
         assert_eq!(
-            medium.tracks[2],
+            tracks[2],
             ReleaseTrack {
                 mbid: Mbid::from_str("e57b3990-eb36-476e-beac-583e0bbe6f87").unwrap(),
                 position: 3,
@@ -403,50 +953,341 @@ mod tests {
                     title: "Sarnath".to_string(),
                     length: Some(Duration::from_millis(228000)),
                 },
+                artists: Vec::new(),
+                isrcs: Vec::new(),
             }
         );
     }
 
+    #[test]
+    fn read_track_credits_and_isrcs()
+    {
+        use entities::FromXmlStr;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release id="ed118c5f-d940-4b52-a37b-b1a205374abe"><title>Test</title><medium-list count="1"><medium><position>1</position><track-list count="2" offset="0"><track id="ac898be7-2965-4d17-9ac8-48d45852d73c"><position>1</position><number>1</number><title>With Credit</title><artist-credit><name-credit><artist id="90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e" type="Group"><name>NECRONOMIDOL</name><sort-name>NECRONOMIDOL</sort-name></artist></name-credit></artist-credit><recording id="fd6f4cd8-9cff-43da-8cd7-3351357b6f5a"><title>With Credit</title><isrc-list count="2"><isrc id="JPB601700101"/><isrc id="JPB601700102"/></isrc-list></recording></track><track id="21648b0b-deaf-4b93-a257-5fc18363b25d"><position>2</position><number>2</number><title>Recording Credit</title><recording id="0eeb0621-8013-4c0e-8e49-ddfd78d56051"><title>Recording Credit</title><artist-credit><name-credit><artist id="650e7db6-b795-4eb5-a702-5ea2fc46c848"><name>Lady Gaga</name><sort-name>Lady Gaga</sort-name></artist></name-credit></artist-credit></recording></track></track-list></medium></medium-list></release></metadata>"#;
+        let release = Release::from_xml_str(xml).unwrap();
+        let tracks = release.mediums.as_ref().unwrap()[0].tracks.as_ref().unwrap();
+
+        assert_eq!(tracks[0].artists.len(), 1);
+        assert_eq!(tracks[0].artists[0].name, "NECRONOMIDOL".to_string());
+        let isrcs: Vec<&str> = tracks[0].isrcs.iter().map(|isrc| isrc.as_str()).collect();
+        assert_eq!(isrcs, vec!["JPB601700101", "JPB601700102"]);
+
+        assert_eq!(tracks[1].artists.len(), 1);
+        assert_eq!(tracks[1].artists[0].name, "Lady Gaga".to_string());
+        assert!(tracks[1].isrcs.is_empty());
+    }
+
+    #[test]
+    fn transliterated_track_titles()
+    {
+        use entities::FromXmlStr;
+        use xpath_reader::XpathStrReader;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release id="739de9cd-7e81-4bb0-9fdb-0feb7ea709c7"><title>霊魂消滅</title><text-representation><language>jpn</language><script>Jpan</script></text-representation><relation-list target-type="release"><relation type="transl-tracklisting"><target>d3d2a860-0093-461d-8d95-b77939c2e944</target><attribute-list><attribute>transliterated</attribute></attribute-list><release id="d3d2a860-0093-461d-8d95-b77939c2e944"><title>Reikon Shoumetsu</title><status>Pseudo-Release</status></release></relation></relation-list><medium-list count="1"><medium><position>1</position><track-list count="2" offset="0"><track id="ac898be7-2965-4d17-9ac8-48d45852d73c"><position>1</position><number>1</number><title>霊魂消滅</title><recording id="fd6f4cd8-9cff-43da-8cd7-3351357b6f5a"><title>霊魂消滅</title></recording></track><track id="21648b0b-deaf-4b93-a257-5fc18363b25d"><position>2</position><number>2</number><title>蜃気楼</title><recording id="0eeb0621-8013-4c0e-8e49-ddfd78d56051"><title>蜃気楼</title></recording></track></track-list></medium></medium-list></release></metadata>"#;
+        let release = Release::from_xml_str(xml).unwrap();
+
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let relations: Vec<Relation> = reader.read_vec("//mb:relation-list/mb:relation").unwrap();
+        let versions = track_list_versions(&relations);
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].title, "Reikon Shoumetsu".to_string());
+        assert!(versions[0].is_pseudo_release());
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release id="d3d2a860-0093-461d-8d95-b77939c2e944"><title>Reikon Shoumetsu</title><status>Pseudo-Release</status><text-representation><language>jpn</language><script>Latn</script></text-representation><medium-list count="1"><medium><position>1</position><track-list count="1" offset="0"><track id="5e67884f-1c13-4aa5-bb1a-caa0dbeabe42"><position>1</position><number>1</number><title>Reikon Shoumetsu</title><recording id="fd6f4cd8-9cff-43da-8cd7-3351357b6f5a"><title>霊魂消滅</title></recording></track></track-list></medium></medium-list></release></metadata>"#;
+        let transliteration = Release::from_xml_str(xml).unwrap();
+        assert_eq!(transliteration.script, Some("Latn".to_string()));
+        assert_eq!(
+            release.track_titles_in(&transliteration),
+            vec!["Reikon Shoumetsu", "蜃気楼"]
+        );
+    }
+
+    #[cfg(feature = "client")]
     #[test]
     fn tracks_without_length()
     {
         let mbid = Mbid::from_str("02173013-59ed-4229-b0a5-e5aa486ed5d7").unwrap();
         let release: Release = ::util::test_utils::fetch_entity(&mbid).unwrap();
 
-        let ref medium = release.mediums[0];
-        assert_eq!(medium.tracks[0].length, None);
-        assert_eq!(medium.tracks[1].length, None);
-        assert_eq!(medium.tracks[2].length, None);
-        assert_eq!(medium.tracks[3].length, None);
+        let tracks = release.mediums.as_ref().unwrap()[0].tracks.as_ref().unwrap();
+        assert_eq!(tracks[0].length, None);
+        assert_eq!(tracks[1].length, None);
+        assert_eq!(tracks[2].length, None);
+        assert_eq!(tracks[3].length, None);
     }
 
+    #[cfg(feature = "client")]
     #[test]
     fn multi_cd()
     {
         let mbid = Mbid::from_str("ce22b20d-3a45-4e47-abaa-b7c8d10281fa").unwrap();
         let release: Release = ::util::test_utils::fetch_entity(&mbid).unwrap();
 
-        let mediums = release.mediums;
+        let artists = release.artists.clone().unwrap();
+        assert_eq!(artists[0].name, "Boris".to_string());
+        assert_eq!(
+            artists[0].disambiguation,
+            Some("Japanese experimental doom/sludge/drone/psych/metal band".to_string())
+        );
+
+        let mediums = release.mediums.as_ref().unwrap();
 
         assert_eq!(mediums.len(), 2);
 
         assert_eq!(mediums[0].position, 1);
-        assert_eq!(mediums[0].tracks.len(), 11);
-        assert_eq!(mediums[0].tracks[0].position, 1);
-        assert_eq!(mediums[0].tracks[0].number, "1".to_string());
-        assert_eq!(mediums[0].tracks[1].position, 2);
-        assert_eq!(mediums[0].tracks[1].number, "2".to_string());
+        let tracks = mediums[0].tracks.as_ref().unwrap();
+        assert_eq!(tracks.len(), 11);
+        assert_eq!(mediums[0].track_offset, 0);
+        assert_eq!(mediums[0].track_count, 11);
+        assert!(!mediums[0].is_partial());
+        assert_eq!(tracks[0].position, 1);
+        assert_eq!(tracks[0].number, "1".to_string());
+        assert_eq!(tracks[1].position, 2);
+        assert_eq!(tracks[1].number, "2".to_string());
 
         assert_eq!(mediums[1].position, 2);
-        assert_eq!(mediums[1].tracks.len(), 9);
-        assert_eq!(mediums[1].tracks[0].position, 1);
-        assert_eq!(mediums[1].tracks[0].number, "1".to_string());
-        assert_eq!(mediums[1].tracks[1].position, 2);
-        assert_eq!(mediums[1].tracks[1].number, "2".to_string());
+        let tracks = mediums[1].tracks.as_ref().unwrap();
+        assert_eq!(tracks.len(), 9);
+        assert_eq!(mediums[1].track_count, 9);
+        assert!(!mediums[1].is_partial());
+        assert_eq!(tracks[0].position, 1);
+        assert_eq!(tracks[0].number, "1".to_string());
+        assert_eq!(tracks[1].position, 2);
+        assert_eq!(tracks[1].number, "2".to_string());
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn track_accessors()
+    {
+        let mbid = Mbid::from_str("ce22b20d-3a45-4e47-abaa-b7c8d10281fa").unwrap();
+        let release: Release = ::util::test_utils::fetch_entity(&mbid).unwrap();
+
+        assert_eq!(release.track_count(), 20);
+
+        let tracks = release.all_tracks();
+        assert_eq!(tracks.len(), 20);
+        assert_eq!(tracks[0].0, 1);
+        assert_eq!(tracks[0].1.title, "Farewell".to_string());
+        assert_eq!(tracks[11].0, 2);
+        assert_eq!(tracks[11].1.position, 1);
+
+        let recording = Mbid::from_str("4940d931-771d-4f5c-92cc-759124510ef3").unwrap();
+        let track = release.find_track_by_recording(&recording).unwrap();
+        assert_eq!(track.title, "PINK".to_string());
+
+        let unknown = Mbid::from_str("a74b1b7f-71a5-4011-9441-d0b5e4122711").unwrap();
+        assert_eq!(release.find_track_by_recording(&unknown), None);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn continuous_track_numbers()
+    {
+        let mbid = Mbid::from_str("ce22b20d-3a45-4e47-abaa-b7c8d10281fa").unwrap();
+        let release: Release = ::util::test_utils::fetch_entity(&mbid).unwrap();
+
+        assert_eq!(release.continuous_track_number(1, 1), Some(1));
+        assert_eq!(release.continuous_track_number(1, 11), Some(11));
+        assert_eq!(release.continuous_track_number(2, 1), Some(12));
+        assert_eq!(release.continuous_track_number(2, 9), Some(20));
+        assert_eq!(release.continuous_track_number(2, 10), None);
+        assert_eq!(release.continuous_track_number(3, 1), None);
+
+        assert_eq!(release.track_for_continuous_number(1), Some((1, 1)));
+        assert_eq!(release.track_for_continuous_number(12), Some((2, 1)));
+        assert_eq!(release.track_for_continuous_number(20), Some((2, 9)));
+        assert_eq!(release.track_for_continuous_number(0), None);
+        assert_eq!(release.track_for_continuous_number(21), None);
+
+        // The numbers don't depend on the order of the mediums in the response.
+        let mut reversed = release.clone();
+        reversed.mediums.as_mut().unwrap().reverse();
+        for number in 1..21 {
+            let (medium, track) = reversed.track_for_continuous_number(number).unwrap();
+            assert_eq!(reversed.continuous_track_number(medium, track), Some(number));
+            assert_eq!(release.track_for_continuous_number(number), Some((medium, track)));
+        }
+        assert_eq!(reversed.continuous_track_number(2, 1), Some(12));
+    }
+
+    fn medium_with_lengths(lengths: Vec<Option<u64>>) -> ReleaseMedium
+    {
+        let mbid = Mbid::from_str("ac898be7-2965-4d17-9ac8-48d45852d73c").unwrap();
+        let track_count = lengths.len() as u32;
+        ReleaseMedium {
+            position: 1,
+            format: None,
+            track_offset: 0,
+            track_count: track_count,
+            tracks: Some(
+                lengths
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, length)| ReleaseTrack {
+                        mbid: mbid.clone(),
+                        position: i as u16 + 1,
+                        number: (i + 1).to_string(),
+                        title: "Track".to_string(),
+                        length: length.map(Duration::from_millis),
+                        recording: RecordingRef {
+                            mbid: mbid.clone(),
+                            title: "Track".to_string(),
+                            length: length.map(Duration::from_millis),
+                        },
+                        artists: Vec::new(),
+                        isrcs: Vec::new(),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn earliest_release_event_date()
+    {
+        let event = |date: Option<&str>| {
+            ReleaseEvent {
+                date: date.map(|d| PartialDate::from_str(d).unwrap()),
+                area: None,
+            }
+        };
+
+        let events = vec![
+            event(Some("2004-06-09")),
+            event(None),
+            event(Some("2003-11")),
+            event(Some("????-01-01")),
+            event(Some("2003-11-20")),
+        ];
+        assert_eq!(
+            earliest_date(&events),
+            Some(&PartialDate::from_str("2003-11").unwrap())
+        );
+        assert_eq!(earliest_date(&[event(None)]), None);
+    }
+
+    #[test]
+    fn partial_track_list()
+    {
+        use xpath_reader::XpathStrReader;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><medium><position>1</position><track-list count="30" offset="25"><track id="ac898be7-2965-4d17-9ac8-48d45852d73c"><position>26</position><number>26</number><title>Track</title><recording id="ac898be7-2965-4d17-9ac8-48d45852d73c"><title>Track</title></recording></track></track-list></medium></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let medium: ReleaseMedium = reader.read("//mb:medium").unwrap();
+
+        assert_eq!(medium.tracks.as_ref().unwrap().len(), 1);
+        assert_eq!(medium.track_offset, 25);
+        assert_eq!(medium.track_count, 30);
+        assert!(medium.is_partial());
+
+        assert!(!medium_with_lengths(vec![Some(1000), None]).is_partial());
+    }
+
+    #[test]
+    fn mediums_not_requested()
+    {
+        use entities::FromXmlStr;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release id="ed118c5f-d940-4b52-a37b-b1a205374abe"><title>Test</title></release></metadata>"#;
+        let mut release = Release::from_xml_str(xml).unwrap();
+        assert_eq!(release.mediums, None);
+        assert_eq!(release.track_count(), 0);
+        release.mark_requested(&[Include::Media]);
+        assert_eq!(release.mediums, Some(Vec::new()));
+
+        // With `Include::Media` only the number of tracks is returned.
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release id="ed118c5f-d940-4b52-a37b-b1a205374abe"><title>Test</title><medium-list count="2"><medium><position>1</position><track-list count="11"/></medium><medium><position>2</position><track-list count="0"/></medium></medium-list></release></metadata>"#;
+        let release = Release::from_xml_str(xml).unwrap();
+        let mediums = release.mediums.as_ref().unwrap();
+        assert_eq!(mediums[0].tracks, None);
+        assert_eq!(mediums[0].track_count, 11);
+        assert!(mediums[0].is_partial());
+        assert_eq!(mediums[1].tracks, Some(Vec::new()));
+        assert!(!mediums[1].is_partial());
+        assert_eq!(release.continuous_track_number(2, 1), None);
+        assert_eq!(release.continuous_track_number(1, 11), Some(11));
+        assert!(release.all_tracks().is_empty());
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn total_length()
+    {
+        let complete = medium_with_lengths(vec![Some(232000), Some(258000)]);
+        assert_eq!(
+            complete.total_length(),
+            TotalLength {
+                length: Duration::from_millis(490000),
+                complete: true,
+            }
+        );
+
+        let unknown = medium_with_lengths(vec![Some(100000), None, Some(20000)]);
+        assert_eq!(
+            unknown.total_length(),
+            TotalLength {
+                length: Duration::from_millis(120000),
+                complete: false,
+            }
+        );
+
+        let mut partial = medium_with_lengths(vec![Some(100000)]);
+        partial.track_count = 2;
+        assert!(!partial.total_length().complete);
+
+        let mbid = Mbid::from_str("ed118c5f-d940-4b52-a37b-b1a205374abe").unwrap();
+        let mut release: Release = ::util::test_utils::fetch_entity(&mbid).unwrap();
+        release.mediums = Some(vec![complete.clone(), complete]);
+        assert_eq!(
+            release.total_length(),
+            TotalLength {
+                length: Duration::from_millis(980000),
+                complete: true,
+            }
+        );
+        release.mediums.as_mut().unwrap().push(unknown);
+        assert_eq!(
+            release.total_length(),
+            TotalLength {
+                length: Duration::from_millis(1100000),
+                complete: false,
+            }
+        );
+    }
+
+    #[test]
+    fn match_track_lengths()
+    {
+        let medium = medium_with_lengths(vec![Some(232000), Some(258000), None, Some(228000)]);
+        let tolerance = Duration::from_secs(2);
+
+        let exact = vec![
+            Duration::from_millis(232000),
+            Duration::from_millis(258000),
+            Duration::from_millis(100000),
+            Duration::from_millis(228000),
+        ];
+        assert_eq!(medium.match_track_lengths(&exact, tolerance), 0.75);
+
+        let off = vec![
+            Duration::from_millis(233500),
+            Duration::from_millis(256500),
+            Duration::from_millis(100000),
+            Duration::from_millis(240000),
+        ];
+        assert_eq!(medium.match_track_lengths(&off, tolerance), 0.5);
+
+        assert_eq!(medium.match_track_lengths(&exact[..3], tolerance), 0.);
+        assert_eq!(medium_with_lengths(vec![]).match_track_lengths(&[], tolerance), 0.);
     }
 
     /// It's possible that a release has a catalog number but is not linked to
     /// any label in the database.
+    #[cfg(feature = "client")]
     #[test]
     fn catalog_number_but_no_label_ref()
     {
@@ -455,12 +1296,12 @@ mod tests {
 
         assert_eq!(
             release.labels,
-            vec![
+            Some(vec![
                 LabelInfo {
                     label: None,
                     catalog_number: Some("BIRD 4".to_string()),
                 },
-            ]
+            ])
         );
     }
 }
@@ -1,7 +1,13 @@
+use std::collections::{BTreeMap, BTreeSet};
 use xpath_reader::{FromXml, FromXmlError, XpathReader};
 use xpath_reader::reader::{FromXmlContained, FromXmlElement};
 
-use entities::{Mbid, Resource};
+#[cfg(feature = "client")]
+use client::{Client, Progress};
+use entities::{Include, Mbid, PartialDate, Rating, Release, Resource};
+#[cfg(feature = "client")]
+use errors::ClientError;
+use entities::helper::{mark_requested, read_attr, read_mb_list, PSEUDO_RELEASE};
 use entities::refs::{ArtistRef, ReleaseRef};
 
 enum_mb_xml! {
@@ -67,10 +73,22 @@ pub struct ReleaseGroup {
     pub title: String,
 
     /// The artists of a release group.
-    pub artists: Vec<ArtistRef>,
+    ///
+    /// `None` if the artists weren't requested (`Include::Artists` or
+    /// `Include::ArtistCredits`).
+    pub artists: Option<Vec<ArtistRef>>,
 
-    /// Releases of this releaes group.
-    pub releases: Vec<ReleaseRef>,
+    /// Releases of this release group, without the pseudo-releases.
+    ///
+    /// Use `all_releases` to include the pseudo-releases. `None` if the
+    /// releases weren't requested (`Include::Releases`).
+    pub releases: Option<Vec<ReleaseRef>>,
+
+    /// The pseudo-releases of this release group, i. e. translated or
+    /// transliterated track lists of its releases.
+    ///
+    /// `None` if the releases weren't requested (`Include::Releases`).
+    pub pseudo_releases: Option<Vec<ReleaseRef>>,
 
     /// The type of this release group.
     pub release_type: ReleaseGroupType,
@@ -83,6 +101,235 @@ pub struct ReleaseGroup {
     pub annotation: Option<String>,
 }
 
+impl ReleaseGroup {
+    /// Returns `true` if the release group is credited to *Various Artists*.
+    pub fn is_va(&self) -> bool
+    {
+        self.artists
+            .as_ref()
+            .map_or(false, |artists| artists.iter().any(|a| a.is_various_artists()))
+    }
+
+    /// Returns `true` if the release group is a compilation, i. e. it either
+    /// has the secondary type `Compilation` or is credited to *Various
+    /// Artists*.
+    pub fn is_compilation(&self) -> bool
+    {
+        self.is_va() ||
+            self.release_type.secondary.contains(
+                &ReleaseGroupSecondaryType::Compilation,
+            )
+    }
+
+    /// All releases of this release group, the pseudo-releases following the
+    /// actual releases.
+    ///
+    /// Empty if the releases weren't requested.
+    pub fn all_releases(&self) -> Vec<&ReleaseRef>
+    {
+        self.releases
+            .iter()
+            .chain(self.pseudo_releases.iter())
+            .flat_map(|releases| releases.iter())
+            .collect()
+    }
+
+    /// A summary of where, when and on which formats the releases of this
+    /// release group were released, skipping pseudo-releases.
+    ///
+    /// The formats are only known if the release group was fetched with the
+    /// media of its releases, e. g. by `fetch_with_media`.
+    pub fn availability(&self) -> Availability
+    {
+        let releases: &[ReleaseRef] = match self.releases {
+            Some(ref releases) => releases,
+            None => &[],
+        };
+
+        let mut countries = BTreeMap::new();
+        let mut formats = BTreeSet::new();
+        for release in releases {
+            if let Some(ref country) = release.country {
+                *countries.entry(country.clone()).or_insert(0) += 1;
+            }
+            formats.extend(release.formats.iter().cloned());
+        }
+
+        let dates: Vec<&PartialDate> = releases
+            .iter()
+            .filter_map(|release| release.date.as_ref())
+            .filter(|date| date.year().is_some())
+            .collect();
+        let key = |date: &&PartialDate| (date.year(), date.month(), date.day());
+
+        Availability {
+            release_count: releases.len(),
+            countries: countries,
+            formats: formats,
+            earliest: dates.iter().cloned().min_by_key(&key).cloned(),
+            latest: dates.iter().cloned().max_by_key(&key).cloned(),
+        }
+    }
+
+    /// Fetch the release group including the mediums of its releases, so
+    /// `availability` also lists the formats.
+    #[cfg(feature = "client")]
+    pub fn fetch_with_media(client: &mut Client, mbid: &Mbid) -> Result<ReleaseGroup, ClientError>
+    {
+        client.get_by_mbid_with_includes(
+            mbid,
+            &[Include::Annotation, Include::Artists, Include::Releases, Include::Media],
+        )
+    }
+
+    /// Fetch the community rating of this release group.
+    #[cfg(feature = "client")]
+    pub fn fetch_rating(&self, client: &mut Client) -> Result<Rating, ClientError>
+    {
+        client.get_rating::<ReleaseGroup>(&self.mbid)
+    }
+
+    /// Fetch the community ratings of multiple release groups, e. g. to sort
+    /// them by rating.
+    ///
+    /// The ratings are returned in the order of the MBIDs. Every release group
+    /// costs one request, so this takes about a second per release group.
+    #[cfg(feature = "client")]
+    pub fn fetch_ratings(client: &mut Client, mbids: &[Mbid]) -> Result<Vec<Rating>, ClientError>
+    {
+        ReleaseGroup::fetch_ratings_with_progress(client, mbids, |_| {})
+    }
+
+    /// Like `fetch_ratings`, but calls `on_progress` after every fetched
+    /// rating.
+    #[cfg(feature = "client")]
+    pub fn fetch_ratings_with_progress<F>(
+        client: &mut Client,
+        mbids: &[Mbid],
+        mut on_progress: F,
+    ) -> Result<Vec<Rating>, ClientError>
+    where
+        F: FnMut(Progress),
+    {
+        let mut ratings = Vec::with_capacity(mbids.len());
+        for (i, mbid) in mbids.iter().enumerate() {
+            ratings.push(client.get_rating::<ReleaseGroup>(mbid)?);
+            on_progress(Progress::new(i + 1, mbids.len(), mbid));
+        }
+        Ok(ratings)
+    }
+
+    /// Fetch the releases of this release group which aren't pseudo-releases
+    /// for tagging and pick the one preferred by `preference`.
+    ///
+    /// Every release costs one request, so this takes about a second per
+    /// release.
+    #[cfg(feature = "client")]
+    pub fn fetch_preferred_release(
+        &self,
+        client: &mut Client,
+        preference: &ReleasePreference,
+    ) -> Result<Option<Release>, ClientError>
+    {
+        let mut releases = Vec::new();
+        for release in self.releases.iter().flat_map(|releases| releases.iter()) {
+            releases.push(Release::fetch_for_tagging(client, &release.mbid)?);
+        }
+        Ok(preference.select(&releases).cloned())
+    }
+}
+
+/// The availability of the releases of a release group, as returned by
+/// `ReleaseGroup::availability`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Availability {
+    /// The number of releases.
+    pub release_count: usize,
+
+    /// The number of releases per country they were released in, releases
+    /// without a country aren't counted.
+    pub countries: BTreeMap<String, usize>,
+
+    /// The formats of the mediums of the releases, e. g. `"CD"`.
+    pub formats: BTreeSet<String>,
+
+    /// The date of the earliest release.
+    ///
+    /// Unknown months and days are considered earlier than known ones.
+    pub earliest: Option<PartialDate>,
+
+    /// The date of the latest release.
+    pub latest: Option<PartialDate>,
+}
+
+/// Preferences to pick the canonical release of a release group, e. g. to tag
+/// files against.
+///
+/// Releases with a complete track list always come first. Of these the ones
+/// with the most preferred medium format are picked, then the ones with cover
+/// art. Of equally good releases the first one is picked.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleasePreference {
+    /// The medium formats from most to least preferred, formats which aren't
+    /// listed come last.
+    ///
+    /// A format matches all formats ending with it, so e. g. `"Vinyl"` matches
+    /// `12" Vinyl` too.
+    pub formats: Vec<String>,
+
+    /// Whether releases with cover art are preferred.
+    pub cover_art: bool,
+}
+
+impl Default for ReleasePreference {
+    /// Prefers CDs over vinyl over digital releases, and releases with cover
+    /// art.
+    fn default() -> Self
+    {
+        ReleasePreference {
+            formats: vec!["CD".to_string(), "Vinyl".to_string(), "Digital Media".to_string()],
+            cover_art: true,
+        }
+    }
+}
+
+impl ReleasePreference {
+    /// Pick the most preferred of the releases, `None` if there are none.
+    pub fn select<'a>(&self, releases: &'a [Release]) -> Option<&'a Release>
+    {
+        // `min_by_key` returns the first minimum, keeping the order of the
+        // releases for equally good ones.
+        releases.iter().min_by_key(|release| {
+            (
+                !has_track_list(release),
+                self.format_rank(release),
+                self.cover_art && !release.cover_art,
+            )
+        })
+    }
+
+    /// The index of the most preferred format of the mediums of the release.
+    fn format_rank(&self, release: &Release) -> usize
+    {
+        release
+            .mediums
+            .iter()
+            .flat_map(|mediums| mediums.iter())
+            .filter_map(|medium| medium.format.as_ref())
+            .filter_map(|format| self.formats.iter().position(|f| format.ends_with(f.as_str())))
+            .min()
+            .unwrap_or(self.formats.len())
+    }
+}
+
+/// Returns `true` if the tracks of all mediums of the release are known.
+fn has_track_list(release: &Release) -> bool
+{
+    release.mediums.as_ref().map_or(false, |mediums| {
+        !mediums.is_empty() && mediums.iter().all(|medium| medium.track_count > 0)
+    })
+}
+
 impl Resource for ReleaseGroup {
     fn get_name() -> &'static str
     {
@@ -93,6 +340,17 @@ impl Resource for ReleaseGroup {
     {
         "annotation+artists+releases"
     }
+
+    fn mark_requested(&mut self, includes: &[Include])
+    {
+        mark_requested(
+            &mut self.artists,
+            includes,
+            &[Include::Artists, Include::ArtistCredits],
+        );
+        mark_requested(&mut self.releases, includes, &[Include::Releases]);
+        mark_requested(&mut self.pseudo_releases, includes, &[Include::Releases]);
+    }
 }
 
 impl FromXmlContained for ReleaseGroup {}
@@ -102,11 +360,22 @@ impl FromXml for ReleaseGroup {
         R: XpathReader<'d>,
     {
         Ok(ReleaseGroup {
-            mbid: reader.read(".//mb:release-group/@id")?,
+            mbid: read_attr(reader, ".//mb:release-group", "id")?,
             title: reader.read(".//mb:release-group/mb:title/text()")?,
-            releases: reader.read_vec(".//mb:release-group/mb:release-list/mb:release")?,
-            artists: reader.read_vec(
-                ".//mb:release-group/mb:artist-credit/mb:name-credit/mb:artist",
+            releases: read_mb_list(
+                reader,
+                ".//mb:release-group/mb:release-list",
+                &format!("/mb:release[not({})]", PSEUDO_RELEASE),
+            )?,
+            pseudo_releases: read_mb_list(
+                reader,
+                ".//mb:release-group/mb:release-list",
+                &format!("/mb:release[{}]", PSEUDO_RELEASE),
+            )?,
+            artists: read_mb_list(
+                reader,
+                ".//mb:release-group/mb:artist-credit",
+                "/mb:name-credit",
             )?,
             release_type: reader.read(".//mb:release-group")?,
             disambiguation: reader.read_option(".//mb:release-group/mb:disambiguation/text()")?,
@@ -121,6 +390,138 @@ mod tests {
     use std::str::FromStr;
     use entities::*;
 
+    #[test]
+    fn release_availability()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release-group id="76a4e2c2-bf7a-445e-8081-5a1e291f3b16" type="Album"><title>Mixtape</title><primary-type>Album</primary-type><release-list count="4"><release id="289bf4e7-0af5-433c-b5a2-493b863b4b47"><title>Mixtape</title><status>Official</status><date>2012-03</date><country>US</country><medium-list count="1"><medium><format>CD</format></medium></medium-list></release><release id="289bf4e7-0af5-433c-b5a2-493b863b4b48"><title>Mixtape</title><status>Official</status><date>2013</date><country>US</country><medium-list count="2"><medium><format>12" Vinyl</format></medium><medium><format>12" Vinyl</format></medium></medium-list></release><release id="289bf4e7-0af5-433c-b5a2-493b863b4b49"><title>Mixtape</title><status>Official</status><date>2012-03-20</date><country>JP</country><medium-list count="1"><medium><format>CD</format></medium></medium-list></release><release id="289bf4e7-0af5-433c-b5a2-493b863b4b50"><title>Mixtape</title><status>Pseudo-Release</status><date>2011</date><country>XW</country><medium-list count="1"><medium><format>Digital Media</format></medium></medium-list></release></release-list></release-group></metadata>"#;
+        let rg = ReleaseGroup::from_xml_str(xml).unwrap();
+        assert_eq!(
+            rg.releases.as_ref().unwrap()[1].formats,
+            vec!["12\" Vinyl".to_string(), "12\" Vinyl".to_string()]
+        );
+
+        let availability = rg.availability();
+        assert_eq!(availability.release_count, 3);
+        assert_eq!(
+            availability.countries.into_iter().collect::<Vec<_>>(),
+            vec![("JP".to_string(), 1), ("US".to_string(), 2)]
+        );
+        assert_eq!(
+            availability.formats.into_iter().collect::<Vec<_>>(),
+            vec!["12\" Vinyl".to_string(), "CD".to_string()]
+        );
+        assert_eq!(availability.earliest, Some(PartialDate::from_str("2012-03").unwrap()));
+        assert_eq!(availability.latest, Some(PartialDate::from_str("2013").unwrap()));
+    }
+
+    #[test]
+    fn skip_pseudo_releases()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release-group id="76a4e2c2-bf7a-445e-8081-5a1e291f3b16" type="Album"><title>Mixtape</title><primary-type>Album</primary-type><release-list count="3"><release id="289bf4e7-0af5-433c-b5a2-493b863b4b47"><title>Mixtape</title><status>Official</status></release><release id="289bf4e7-0af5-433c-b5a2-493b863b4b48"><title>Mixtape</title><status>Pseudo-Release</status></release><release id="289bf4e7-0af5-433c-b5a2-493b863b4b49"><title>Mixtape</title></release></release-list></release-group></metadata>"#;
+        let rg = ReleaseGroup::from_xml_str(xml).unwrap();
+        let mbids = |releases: &[&ReleaseRef]| -> Vec<String> {
+            releases.iter().map(|r| r.mbid.to_string()).collect()
+        };
+
+        let releases: Vec<&ReleaseRef> = rg.releases.as_ref().unwrap().iter().collect();
+        assert_eq!(
+            mbids(&releases),
+            vec![
+                "289bf4e7-0af5-433c-b5a2-493b863b4b47".to_string(),
+                "289bf4e7-0af5-433c-b5a2-493b863b4b49".to_string(),
+            ]
+        );
+        let pseudo_releases = rg.pseudo_releases.as_ref().unwrap();
+        assert_eq!(pseudo_releases.len(), 1);
+        assert!(pseudo_releases[0].is_pseudo_release());
+        assert_eq!(
+            mbids(&rg.all_releases()),
+            vec![
+                "289bf4e7-0af5-433c-b5a2-493b863b4b47".to_string(),
+                "289bf4e7-0af5-433c-b5a2-493b863b4b49".to_string(),
+                "289bf4e7-0af5-433c-b5a2-493b863b4b48".to_string(),
+            ]
+        );
+    }
+
+    fn release(mbid: &str, formats: &[&str], track_count: u32, cover_art: bool) -> Release
+    {
+        Release {
+            mbid: Mbid::from_str(mbid).unwrap(),
+            title: "Mixtape".to_string(),
+            artists: None,
+            date: None,
+            country: None,
+            release_events: Vec::new(),
+            labels: None,
+            barcode: None,
+            status: Some(ReleaseStatus::Official),
+            packaging: None,
+            language: None,
+            script: None,
+            disambiguation: None,
+            annotation: None,
+            mediums: Some(
+                formats
+                    .iter()
+                    .enumerate()
+                    .map(|(i, format)| {
+                        ReleaseMedium {
+                            position: i as u16 + 1,
+                            format: Some(format.to_string()),
+                            tracks: None,
+                            track_offset: 0,
+                            track_count: track_count,
+                        }
+                    })
+                    .collect(),
+            ),
+            cover_art: cover_art,
+        }
+    }
+
+    #[test]
+    fn select_preferred_release()
+    {
+        let releases = vec![
+            release("289bf4e7-0af5-433c-b5a2-493b863b4b41", &["Digital Media"], 12, true),
+            release("289bf4e7-0af5-433c-b5a2-493b863b4b42", &["CD"], 0, true),
+            release(
+                "289bf4e7-0af5-433c-b5a2-493b863b4b43",
+                &["12\" Vinyl", "12\" Vinyl"],
+                12,
+                false,
+            ),
+            release("289bf4e7-0af5-433c-b5a2-493b863b4b44", &["7\" Vinyl"], 12, true),
+            release("289bf4e7-0af5-433c-b5a2-493b863b4b45", &["Cassette"], 12, true),
+        ];
+        let selected = |preference: &ReleasePreference, releases: &[Release]| {
+            preference.select(releases).map(|r| r.mbid.to_string())
+        };
+
+        // The CD has no track list, so the vinyl with cover art wins.
+        let preference = ReleasePreference::default();
+        assert_eq!(
+            selected(&preference, &releases),
+            Some("289bf4e7-0af5-433c-b5a2-493b863b4b44".to_string())
+        );
+        assert_eq!(
+            selected(&preference, &releases[2..3]),
+            Some("289bf4e7-0af5-433c-b5a2-493b863b4b43".to_string())
+        );
+        assert_eq!(selected(&preference, &[]), None);
+
+        let preference = ReleasePreference {
+            formats: vec!["Digital Media".to_string()],
+            cover_art: false,
+        };
+        assert_eq!(
+            selected(&preference, &releases),
+            Some("289bf4e7-0af5-433c-b5a2-493b863b4b41".to_string())
+        );
+    }
+
+    #[cfg(feature = "client")]
     #[test]
     fn read_1()
     {
@@ -131,25 +532,31 @@ mod tests {
         assert_eq!(rg.title, "Mixtape".to_string());
         assert_eq!(
             rg.artists,
-            vec![
+            Some(vec![
                 ArtistRef {
                     mbid: Mbid::from_str("0e6b3a2c-6a42-4b43-a4f6-c6625c5855de").unwrap(),
                     name: "POP ETC".to_string(),
                     sort_name: "POP ETC".to_string(),
+                    artist_type: None,
+                    disambiguation: None,
+                    aliases: Vec::new(),
+                    credited_as: None,
+                    join_phrase: None,
                 },
-            ]
+            ])
         );
         assert_eq!(
             rg.releases,
-            vec![
+            Some(vec![
                 ReleaseRef {
                     mbid: Mbid::from_str("289bf4e7-0af5-433c-b5a2-493b863b4b47").unwrap(),
                     title: "Mixtape".to_string(),
                     date: Some(PartialDate::from_str("2012-03").unwrap()),
                     status: Some(ReleaseStatus::Official),
                     country: Some("US".to_string()),
+                    formats: Vec::new(),
                 },
-            ]
+            ])
         );
         assert_eq!(
             rg.release_type.primary,
@@ -161,5 +568,7 @@ mod tests {
         );
         assert_eq!(rg.disambiguation, None);
         assert_eq!(rg.annotation, None);
+        assert!(!rg.is_va());
+        assert!(!rg.is_compilation());
     }
 }
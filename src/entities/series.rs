@@ -1,5 +1,5 @@
-use entities::{Mbid, PartialDate, Resource};
-use entities::refs::AreaRef;
+use entities::{Include, Mbid, PartialDate, Resource};
+use entities::helper::{mark_requested, read_attr, read_mb_list};
 use xpath_reader::{FromXml, FromXmlError, XpathReader};
 use xpath_reader::reader::{FromXmlContained, FromXmlElement};
 
@@ -27,7 +27,10 @@ pub struct Series {
     /// Type of the series.
     pub series_type: SeriesType,
 
-    pub aliases: Vec<String>,
+    /// Alternative names of the series.
+    ///
+    /// `None` if the aliases weren't requested (`Include::Aliases`).
+    pub aliases: Option<Vec<String>>,
 
     pub disambiguation: Option<String>,
 
@@ -42,9 +45,9 @@ impl FromXml for Series {
         R: XpathReader<'d>,
     {
         Ok(Series {
-            mbid: reader.read(".//mb:series/@id")?,
-            series_type: reader.read(".//mb:series/@type")?,
-            aliases: reader.read_vec(".//mb:series/mb:alias-list/mb:alias/text()")?,
+            mbid: read_attr(reader, ".//mb:series", "id")?,
+            series_type: read_attr(reader, ".//mb:series", "type")?,
+            aliases: read_mb_list(reader, ".//mb:series/mb:alias-list", "/mb:alias/text()")?,
             disambiguation: reader.read_option(".//mb:series/mb:disambiguation/text()")?,
             annotation: reader.read_option(".//mb:series/mb:annotation/text()")?,
         })
@@ -61,6 +64,11 @@ impl Resource for Series {
     {
         "annotation+aliases"
     }
+
+    fn mark_requested(&mut self, includes: &[Include])
+    {
+        mark_requested(&mut self.aliases, includes, &[Include::Aliases]);
+    }
 }
 
 #[cfg(test)]
@@ -68,6 +76,7 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[cfg(feature = "client")]
     #[test]
     fn read_series_1()
     {
@@ -76,7 +85,7 @@ mod tests {
 
         assert_eq!(series.mbid, mbid);
         assert_eq!(series.series_type, SeriesType::Catalogue);
-        assert_eq!(series.aliases, vec!["BWV".to_string()]);
+        assert_eq!(series.aliases, Some(vec!["BWV".to_string()]));
         assert_eq!(series.disambiguation, None);
         assert_eq!(series.annotation, None);
     }
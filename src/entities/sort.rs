@@ -0,0 +1,121 @@
+//! Ordering of entities by their sort names.
+
+use std::cmp::Ordering;
+
+use entities::{Area, AreaRef, Artist, ArtistRef, Label, LabelRef};
+
+/// Leading articles which are ignored for sorting.
+const ARTICLES: &'static [&'static str] = &["the", "a", "an"];
+
+/// Entities which have a sort name.
+///
+/// The sort names provided by MusicBrainz are mostly consistent (e. g.
+/// `"Beatles, The"`), but especially for labels and areas this isn't always
+/// the case. `sort_key` normalizes them so entities can be ordered the way the
+/// website does it.
+///
+/// Since this is implemented for different types it can also be used to sort
+/// mixed lists using `cmp_sort_names`.
+pub trait SortName {
+    /// The sort name as provided by MusicBrainz.
+    fn sort_name(&self) -> &str;
+
+    /// The normalized sort name used for ordering: lowercased, with a leading
+    /// English article (`"The Beatles"`) or a trailing one (`"Beatles, The"`)
+    /// removed.
+    fn sort_key(&self) -> String
+    {
+        sort_key(self.sort_name())
+    }
+}
+
+/// Compares two entities by their sort keys, falling back to the sort names
+/// themselves if the keys are equal.
+///
+/// # Examples
+/// ```
+/// # use musicbrainz::entities::{cmp_sort_names, SortName};
+/// # fn sort(mut entities: Vec<&SortName>) {
+/// entities.sort_by(|a, b| cmp_sort_names(*a, *b));
+/// # }
+/// ```
+pub fn cmp_sort_names(a: &SortName, b: &SortName) -> Ordering
+{
+    a.sort_key().cmp(&b.sort_key()).then_with(|| a.sort_name().cmp(b.sort_name()))
+}
+
+fn sort_key(sort_name: &str) -> String
+{
+    let lowercase = sort_name.to_lowercase();
+    let mut words: Vec<&str> = lowercase.split_whitespace().collect();
+
+    if words.len() > 1 {
+        let last = words.len() - 1;
+        if ARTICLES.contains(&words[last]) && words[last - 1].ends_with(',') {
+            // "Beatles, The"
+            words.pop();
+            words[last - 1] = words[last - 1].trim_right_matches(',');
+        } else if ARTICLES.contains(&words[0]) {
+            // "The Beatles"
+            words.remove(0);
+        }
+    }
+
+    words.join(" ")
+}
+
+macro_rules! impl_sort_name {
+    ( $( $type:ty ),+ ) => {
+        $(
+            impl SortName for $type {
+                fn sort_name(&self) -> &str
+                {
+                    self.sort_name.as_str()
+                }
+            }
+        )+
+    }
+}
+
+impl_sort_name!(Area, AreaRef, Artist, ArtistRef, Label, LabelRef);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Named(&'static str);
+
+    impl SortName for Named {
+        fn sort_name(&self) -> &str
+        {
+            self.0
+        }
+    }
+
+    #[test]
+    fn normalize_articles()
+    {
+        assert_eq!(sort_key("Beatles, The"), "beatles".to_string());
+        assert_eq!(sort_key("The Beatles"), "beatles".to_string());
+        assert_eq!(sort_key("Perfect Circle, A"), "perfect circle".to_string());
+        assert_eq!(sort_key("  An   Example  "), "example".to_string());
+        assert_eq!(sort_key("The"), "the".to_string());
+        assert_eq!(sort_key("Who, The"), "who".to_string());
+        assert_eq!(sort_key("Theatre"), "theatre".to_string());
+    }
+
+    #[test]
+    fn sort_mixed()
+    {
+        let a = Named("The Cure");
+        let b = Named("Beatles, The");
+        let c = Named("ABBA");
+        let d = Named("beatles");
+
+        let mut entities: Vec<&SortName> = vec![&a, &b, &c, &d];
+        entities.sort_by(|a, b| cmp_sort_names(*a, *b));
+
+        let names: Vec<&str> = entities.iter().map(|e| e.sort_name()).collect();
+        assert_eq!(names, vec!["ABBA", "Beatles, The", "beatles", "The Cure"]);
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use entities::helper::read_attr_option;
+use xpath_reader::{FromXml, FromXmlError, XpathReader};
+use xpath_reader::reader::FromXmlElement;
+
+/// A folksonomy tag applied to an entity by MusicBrainz users, e. g. a genre
+/// like `"synthpop"`.
+///
+/// Additional information can be found in the [MusicBrainz
+/// docs](https://musicbrainz.org/doc/Folksonomy_Tagging).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Tag {
+    /// The tag itself, tags are always lower case.
+    pub name: String,
+
+    /// The number of users who applied the tag minus the ones who voted
+    /// against it, which is negative if more users voted against it.
+    pub count: i32,
+}
+
+impl FromXmlElement for Tag {}
+impl FromXml for Tag {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        Ok(Tag {
+            name: reader.read("./mb:name/text()")?,
+            count: read_attr_option(reader, ".", "count")?.unwrap_or(0),
+        })
+    }
+}
+
+/// Sums up the counts of the tags by their name, ignoring the case and tags
+/// with a count of zero or less.
+fn tag_counts(tags: &[Tag]) -> HashMap<String, u32>
+{
+    let mut counts = HashMap::new();
+    for tag in tags.iter().filter(|tag| tag.count > 0) {
+        *counts.entry(tag.name.trim().to_lowercase()).or_insert(0) += tag.count as u32;
+    }
+    counts
+}
+
+/// Computes how similar two entities are by the overlap of their tags, from
+/// `0.0` (no tag in common) to `1.0` (the same tags with the same counts).
+///
+/// This is the weighted Jaccard index: for every tag the smaller of the two
+/// counts is summed up and divided by the sum of the larger counts. So tags
+/// many users agree on weigh more than tags applied by a single user. Tags
+/// with a count of zero or less don't contribute at all.
+pub fn tag_similarity(a: &[Tag], b: &[Tag]) -> f32
+{
+    let a = tag_counts(a);
+    let b = tag_counts(b);
+
+    let mut shared = 0;
+    let mut total = 0;
+    for (name, &count_a) in &a {
+        let count_b = b.get(name).cloned().unwrap_or(0);
+        shared += count_a.min(count_b);
+        total += count_a.max(count_b);
+    }
+    for (name, &count_b) in &b {
+        if !a.contains_key(name) {
+            total += count_b;
+        }
+    }
+
+    if total == 0 {
+        0.
+    } else {
+        shared as f32 / total as f32
+    }
+}
+
+/// The `n` tags with the highest counts, ties are kept in the original order.
+///
+/// Tags with a count of zero or less are skipped.
+pub(crate) fn top_tags(tags: &[Tag], n: usize) -> Vec<&Tag>
+{
+    let mut sorted: Vec<&Tag> = tags.iter().filter(|tag| tag.count > 0).collect();
+    sorted.sort_by(|a, b| b.count.cmp(&a.count));
+    sorted.truncate(n);
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xpath_reader::XpathStrReader;
+    use entities::helper::read_mb_vec;
+
+    fn tag(name: &str, count: i32) -> Tag
+    {
+        Tag {
+            name: name.to_string(),
+            count: count,
+        }
+    }
+
+    #[test]
+    fn read_tags()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><tag-list><tag count="12"><name>synthpop</name></tag><tag><name>japanese</name></tag><tag count="-2"><name>pop</name></tag></tag-list></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let tags: Vec<Tag> = read_mb_vec(&reader, "//mb:tag-list/mb:tag").unwrap();
+
+        assert_eq!(tags, vec![tag("synthpop", 12), tag("japanese", 0), tag("pop", -2)]);
+    }
+
+    #[test]
+    fn similarity()
+    {
+        let a = vec![tag("rock", 10), tag("alternative rock", 4), tag("british", 2)];
+        let b = vec![tag("Rock", 5), tag("alternative rock", 4), tag("electronic", 3)];
+
+        assert_eq!(tag_similarity(&a, &a), 1.);
+        // (5 + 4) / (10 + 4 + 2 + 3)
+        assert_eq!(tag_similarity(&a, &b), 9. / 19.);
+        assert_eq!(tag_similarity(&a, &b), tag_similarity(&b, &a));
+        assert_eq!(tag_similarity(&a, &[tag("jazz", 1)]), 0.);
+        assert_eq!(tag_similarity(&[], &[]), 0.);
+
+        // Downvoted tags are ignored instead of reducing the counts.
+        let downvoted = vec![tag("rock", 10), tag("rock", -3), tag("pop", -5)];
+        assert_eq!(tag_similarity(&downvoted, &[tag("rock", 10)]), 1.);
+    }
+
+    #[test]
+    fn top()
+    {
+        let tags = vec![
+            tag("british", 2),
+            tag("rock", 10),
+            tag("uk", 2),
+            tag("seen live", 0),
+            tag("pop", -4),
+        ];
+        let names: Vec<&str> = top_tags(&tags, 3).iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["rock", "british", "uk"]);
+        assert_eq!(top_tags(&tags, 10).len(), 3);
+    }
+}
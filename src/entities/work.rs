@@ -0,0 +1,184 @@
+use xpath_reader::{FromXml, FromXmlError, XpathReader};
+use xpath_reader::reader::FromXmlContained;
+
+#[cfg(feature = "client")]
+use client::Client;
+use entities::{Alias, Include, Language, Mbid, Resource};
+use entities::helper::{mark_requested, read_attr, read_attr_option, read_lyrics_languages,
+                       read_mb_list};
+use entities::refs::ArtistRef;
+use entities::relationships::{filter_relations, Direction, RelType, Relation, RelationTarget};
+#[cfg(feature = "client")]
+use errors::ClientError;
+
+/// A distinct intellectual or artistic creation, e. g. a song or a symphony,
+/// which can be recorded many times.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Work {
+    /// MBID of the entity in the MusicBrainz database.
+    pub mbid: Mbid,
+
+    /// The title of the work.
+    pub title: String,
+
+    /// The type of the work, e. g. `"Song"` or `"Symphony"`.
+    pub work_type: Option<String>,
+
+    /// The languages of the lyrics, the main language first.
+    ///
+    /// Special codes like `"mul"` (multiple languages) aren't included.
+    pub languages: Vec<Language>,
+
+    /// Whether the work is marked as having no lyrics.
+    pub instrumental: bool,
+
+    /// The [ISWCs](https://musicbrainz.org/doc/ISWC) of the work, e. g.
+    /// `"T-101.690.320-9"`.
+    pub iswcs: Vec<String>,
+
+    /// Aliases of the title, e. g. translations.
+    ///
+    /// `None` if the aliases weren't requested (`Include::Aliases`).
+    pub aliases: Option<Vec<Alias>>,
+
+    /// A disambiguation comment if present, which allows to differentiate this
+    /// work from other works with the same title.
+    pub disambiguation: Option<String>,
+
+    /// Any additional free form annotation for this `Work`.
+    pub annotation: Option<String>,
+}
+
+impl FromXmlContained for Work {}
+impl FromXml for Work {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        let (languages, instrumental) = read_lyrics_languages(reader, ".//mb:work")?;
+
+        Ok(Work {
+            aliases: read_mb_list(reader, ".//mb:work/mb:alias-list", "/mb:alias")?,
+            annotation: reader.read_option(".//mb:work/mb:annotation/mb:text/text()")?,
+            disambiguation: reader.read_option(".//mb:work/mb:disambiguation/text()")?,
+            instrumental: instrumental,
+            iswcs: reader.read_vec(".//mb:work/mb:iswc-list/mb:iswc/text()")?,
+            languages: languages,
+            mbid: read_attr(reader, ".//mb:work", "id")?,
+            title: reader.read(".//mb:work/mb:title/text()")?,
+            work_type: read_attr_option(reader, ".//mb:work", "type")?,
+        })
+    }
+}
+
+/// The credit of a writer of a `Work`, as returned by `Work::fetch_writers`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WriterCredit {
+    /// The credited artist.
+    pub artist: ArtistRef,
+
+    /// The role of the artist, one of `RelType::Composer`, `RelType::Lyricist`,
+    /// `RelType::Librettist` and `RelType::Writer`.
+    pub role: RelType,
+
+    /// Further attributes of the credit, e. g. `"additional"`.
+    pub attributes: Vec<String>,
+}
+
+impl Work {
+    /// The main language of the lyrics, `None` for instrumental works and
+    /// works whose language isn't known.
+    pub fn lyrics_language(&self) -> Option<&Language>
+    {
+        self.languages.first()
+    }
+
+    /// Fetch the composers, lyricists, librettists and writers of this work in
+    /// the order provided by the server.
+    #[cfg(feature = "client")]
+    pub fn fetch_writers(&self, client: &mut Client) -> Result<Vec<WriterCredit>, ClientError>
+    {
+        let relations = client.get_relations::<Work>(&self.mbid)?;
+        Ok(writers(&relations))
+    }
+}
+
+/// The relations from an artist to a work are forward, so the writers of a
+/// work are found by the backward ones.
+fn writers(relations: &[Relation]) -> Vec<WriterCredit>
+{
+    let roles = [RelType::Composer, RelType::Lyricist, RelType::Librettist, RelType::Writer];
+
+    filter_relations(relations, &roles, Direction::Backward)
+        .into_iter()
+        .filter_map(|r| match r.target {
+            RelationTarget::Artist(ref artist) => {
+                roles.iter().find(|role| r.is(role)).map(|role| WriterCredit {
+                    artist: artist.clone(),
+                    role: role.clone(),
+                    attributes: r.attributes.iter().map(|a| a.name.clone()).collect(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+impl Resource for Work {
+    fn get_name() -> &'static str
+    {
+        "work"
+    }
+
+    fn get_incs() -> &'static str
+    {
+        "aliases+annotation"
+    }
+
+    fn mark_requested(&mut self, includes: &[Include])
+    {
+        mark_requested(&mut self.aliases, includes, &[Include::Aliases]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::FromXmlStr;
+    use xpath_reader::XpathStrReader;
+
+    #[test]
+    fn read_work()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><work type="Song" id="b1df2cf3-69a9-3bc0-be44-f71e79b27a22"><title>Frozen</title><language>eng</language><language-list><language>eng</language></language-list><iswc-list><iswc>T-010.451.268-0</iswc></iswc-list></work></metadata>"#;
+        let work = Work::from_xml_str(xml).unwrap();
+
+        assert_eq!(work.title, "Frozen".to_string());
+        assert_eq!(work.work_type, Some("Song".to_string()));
+        assert_eq!(work.lyrics_language().map(|l| l.to_639_3()), Some("eng"));
+        assert!(!work.instrumental);
+        assert_eq!(work.iswcs, vec!["T-010.451.268-0".to_string()]);
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><work id="b1df2cf3-69a9-3bc0-be44-f71e79b27a22"><title>Interlude</title><language>zxx</language></work></metadata>"#;
+        let work = Work::from_xml_str(xml).unwrap();
+        assert_eq!(work.lyrics_language(), None);
+        assert!(work.instrumental);
+    }
+
+    #[test]
+    fn writer_credits()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><work id="b1df2cf3-69a9-3bc0-be44-f71e79b27a22"><title>Frozen</title><relation-list target-type="artist"><relation type="composer"><target>79239441-bfd5-4981-a70c-55c3f15c1287</target><direction>backward</direction><artist id="79239441-bfd5-4981-a70c-55c3f15c1287"><name>Madonna</name><sort-name>Madonna</sort-name></artist></relation><relation type="lyricist"><target>79239441-bfd5-4981-a70c-55c3f15c1287</target><direction>backward</direction><attribute-list><attribute>additional</attribute></attribute-list><artist id="79239441-bfd5-4981-a70c-55c3f15c1287"><name>Madonna</name><sort-name>Madonna</sort-name></artist></relation><relation type="performance"><target>2ad5ebd6-5e1d-4b5a-b4d6-ea1e3d3a1d2e</target><direction>backward</direction><artist id="2ad5ebd6-5e1d-4b5a-b4d6-ea1e3d3a1d2e"><name>Somebody</name><sort-name>Somebody</sort-name></artist></relation></relation-list></work></metadata>"#;
+        let context = ::util::musicbrainz_context();
+        let reader = XpathStrReader::new(xml, &context).unwrap();
+        let relations: Vec<Relation> = reader.read_vec("//mb:relation-list/mb:relation").unwrap();
+
+        let credits = writers(&relations);
+        assert_eq!(credits.len(), 2);
+        assert_eq!(credits[0].artist.name, "Madonna".to_string());
+        assert_eq!(credits[0].role, RelType::Composer);
+        assert_eq!(credits[0].attributes, Vec::<String>::new());
+        assert_eq!(credits[1].role, RelType::Lyricist);
+        assert_eq!(credits[1].attributes, vec!["additional".to_string()]);
+    }
+}
@@ -1,65 +1,439 @@
-// TODO: Actually the docs generated by error chain are rather misleading since
-// they are exactly the same for both ClientError and ParseError.
-//
-// One work around would be to put each error chain in its own module and
-// document that module, then reexport the types.
+//! The error types of this crate.
+//!
+//! There are two error types: `ParseError` for failures to interpret data,
+//! and `ClientError` for everything which can go wrong while communicating
+//! with the MusicBrainz API. The latter is only available with the `client`
+//! feature.
 
-error_chain! {
-    types {
-        ParseError, ParseErrorKind, ChainParseErr;
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "client")]
+use std::io;
+use std::num::ParseIntError;
+
+use entities::ParseDateError;
+#[cfg(feature = "client")]
+use entities::{CollectionEntity, InvalidInclude};
+#[cfg(feature = "client")]
+use reqwest_mock::Error as HttpError;
+#[cfg(feature = "client")]
+use reqwest_mock::UrlError;
+use uuid;
+use xpath_reader::{FromXmlError, XpathError};
+
+/// An error which occurred while parsing data into the types of this crate.
+#[derive(Debug)]
+pub enum ParseError {
+    /// Evaluating an XPath expression on a document failed.
+    Xpath(XpathError),
+
+    /// Reading a value from an XML document failed.
+    FromXml(FromXmlError),
+
+    /// A MBID was malformed.
+    Uuid(uuid::ParseError),
+
+    /// An integer was malformed.
+    ParseInt(ParseIntError),
+
+    /// A date was malformed.
+    ParseDate(ParseDateError),
+
+    /// The data is invalid, the message describes what exactly is wrong.
+    InvalidData(String),
+
+    /// Somewhere in our code something went wrong, that really shouldn't have.
+    /// These are always considered a bug that should reported as an issue.
+    Internal(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        use self::ParseError::*;
+        match *self {
+            Xpath(ref e) => write!(f, "xpath error: {}", e),
+            FromXml(ref e) => write!(f, "failed reading xml: {}", e),
+            Uuid(ref e) => write!(f, "invalid mbid: {}", e),
+            ParseInt(ref e) => write!(f, "invalid integer: {}", e),
+            ParseDate(ref e) => write!(f, "invalid date: {}", e),
+            InvalidData(ref msg) => write!(f, "invalid data: {}", msg),
+            Internal(ref msg) => {
+                write!(f, "internal error: {}\nYou should probably report this bug.", msg)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn description(&self) -> &str
+    {
+        use self::ParseError::*;
+        match *self {
+            Xpath(_) => "xpath error",
+            FromXml(_) => "failed reading xml",
+            Uuid(_) => "invalid mbid",
+            ParseInt(_) => "invalid integer",
+            ParseDate(_) => "invalid date",
+            InvalidData(_) => "invalid data",
+            Internal(_) => "internal error",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error>
+    {
+        use self::ParseError::*;
+        match *self {
+            Xpath(ref e) => Some(e),
+            FromXml(ref e) => Some(e),
+            Uuid(ref e) => Some(e),
+            ParseInt(ref e) => Some(e),
+            ParseDate(ref e) => Some(e),
+            InvalidData(_) | Internal(_) => None,
+        }
+    }
+}
+
+impl From<XpathError> for ParseError {
+    fn from(e: XpathError) -> Self
+    {
+        ParseError::Xpath(e)
+    }
+}
+
+impl From<FromXmlError> for ParseError {
+    fn from(e: FromXmlError) -> Self
+    {
+        ParseError::FromXml(e)
+    }
+}
+
+impl From<uuid::ParseError> for ParseError {
+    fn from(e: uuid::ParseError) -> Self
+    {
+        ParseError::Uuid(e)
+    }
+}
+
+impl From<ParseIntError> for ParseError {
+    fn from(e: ParseIntError) -> Self
+    {
+        ParseError::ParseInt(e)
     }
+}
+
+impl From<ParseDateError> for ParseError {
+    fn from(e: ParseDateError) -> Self
+    {
+        ParseError::ParseDate(e)
+    }
+}
+
+/// An error which occurred while fetching an entity with a caller supplied
+/// `FetchBody`, see `fetch::fetch_entity`.
+#[derive(Debug)]
+pub enum FetchError<E> {
+    /// The `FetchBody` failed to get the response.
+    Fetch(E),
 
-    links {
-        XpathReadError(::xpath_reader::XpathError, ::xpath_reader::XpathErrorKind);
+    /// The response couldn't be parsed.
+    Parse(ParseError),
+}
+
+impl<E: fmt::Display> fmt::Display for FetchError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match *self {
+            FetchError::Fetch(ref e) => write!(f, "fetching failed: {}", e),
+            FetchError::Parse(ref e) => write!(f, "parsing the response failed: {}", e),
+        }
     }
+}
 
-    // Automatic conversions between this error chain and errors not defined using error
-    // chain.
-    foreign_links {
-        UuidParseError(::uuid::ParseError);
-        ParseIntError(::std::num::ParseIntError);
-        ParseDateError(super::entities::ParseDateError);
+impl<E: Error> Error for FetchError<E> {
+    fn description(&self) -> &str
+    {
+        match *self {
+            FetchError::Fetch(_) => "fetching failed",
+            FetchError::Parse(_) => "parsing the response failed",
+        }
     }
 
-    // Custom error kinds.
-    errors {
-        InvalidData(msg: String) {
-            description("invalid data")
-            display("invalid data: {}", msg)
+    fn cause(&self) -> Option<&Error>
+    {
+        match *self {
+            FetchError::Fetch(ref e) => Some(e),
+            FetchError::Parse(ref e) => Some(e),
         }
-        /// Somewhere in our code something went wrong, that really shouldn't have.
-        /// These are always considered a bug that should reported as an issue.
-        InternalError(msg: String) {
-            description("internal error")
-            display("internal error: {}\nYou should probably report this bug.", msg)
+    }
+}
+
+/// An error which occurred while communicating with the MusicBrainz API.
+///
+/// Besides the `kind` of the error, it provides the url of the request and
+/// the type of the requested entity if the error occurred while performing a
+/// request, so failures of bulk jobs can be logged in an actionable way.
+#[cfg(feature = "client")]
+#[derive(Debug)]
+pub struct ClientError {
+    kind: ClientErrorKind,
+    url: Option<String>,
+    entity: Option<&'static str>,
+}
+
+/// The different kinds of `ClientError`s.
+#[cfg(feature = "client")]
+#[derive(Debug)]
+pub enum ClientErrorKind {
+    /// Parsing the response failed.
+    Parse(ParseError),
+
+    /// Evaluating an XPath expression on the response failed.
+    Xpath(XpathError),
+
+    /// Reading a value from the response failed.
+    FromXml(FromXmlError),
+
+    /// Performing the HTTP request failed.
+    Http(HttpError),
+
+    /// An IO error occurred.
+    Io(io::Error),
+
+    /// An url was malformed.
+    Url(UrlError),
+
+    /// The server returned an error message.
+    ///
+    /// This is most likely a rate limit, but since the errors aren't coded and
+    /// there are also other issues like the server being busy we don't
+    /// distinguish it except for the provided message.
+    MusicbrainzServerError(String),
+
+    /// The server responded with 503 (ServiceUnavailable) to all attempts.
+    ServiceUnavailable {
+        /// The number of attempts made.
+        attempts: u8,
+    },
+
+    /// The requested includes can't be combined in a lookup of the entity, the
+    /// request wasn't made.
+    InvalidIncludes(InvalidInclude),
+
+    /// The server responded with something other than an XML document, e.g.
+    /// an HTML maintenance page of a proxy.
+    UnexpectedContent {
+        /// The content type of the response, if provided.
+        content_type: Option<String>,
+        /// The beginning of the response body.
+        snippet: String,
+    },
+
+    /// The server responded with 401 (Unauthorized), the request requires
+    /// authentication, e. g. because of an include like `Include::UserTags`.
+    Unauthorized,
+
+    /// The response body is larger than `ClientConfig::max_response_size`.
+    ResponseTooLarge {
+        /// The size of the body in bytes, as announced by the server or read.
+        size: usize,
+        /// The configured limit in bytes.
+        limit: usize,
+    },
+
+    /// A language code, e. g. of a Wikipedia, contains characters other than
+    /// ASCII letters and `-`, the request wasn't made.
+    InvalidLanguage(String),
+
+    /// The contents of a collection were requested as entities of another
+    /// type than the collection holds, the request wasn't made.
+    CollectionEntityMismatch {
+        /// The type of the entities in the collection.
+        collection: CollectionEntity,
+        /// The requested type.
+        requested: CollectionEntity,
+    },
+}
+
+#[cfg(feature = "client")]
+impl ClientError {
+    /// The kind of this error.
+    pub fn kind(&self) -> &ClientErrorKind
+    {
+        &self.kind
+    }
+
+    /// Consumes the error, returning its kind.
+    pub fn into_kind(self) -> ClientErrorKind
+    {
+        self.kind
+    }
+
+    /// The url of the request during which the error occurred, if known.
+    pub fn url(&self) -> Option<&str>
+    {
+        self.url.as_ref().map(|s| s.as_str())
+    }
+
+    /// The name of the requested entity type (e. g. `"artist"`), if known.
+    pub fn entity(&self) -> Option<&'static str>
+    {
+        self.entity
+    }
+
+    /// Attach the url and the requested entity type of the request during
+    /// which the error occurred.
+    pub(crate) fn with_request<S>(mut self, url: S, entity: Option<&'static str>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.url = Some(url.into());
+        self.entity = entity;
+        self
+    }
+}
+
+#[cfg(feature = "client")]
+impl fmt::Display for ClientErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        use self::ClientErrorKind::*;
+        match *self {
+            Parse(ref e) => write!(f, "parse error: {}", e),
+            Xpath(ref e) => write!(f, "xpath error: {}", e),
+            FromXml(ref e) => write!(f, "failed reading xml: {}", e),
+            Http(ref e) => write!(f, "http error: {}", e),
+            Io(ref e) => write!(f, "io error: {}", e),
+            Url(ref e) => write!(f, "invalid url: {}", e),
+            MusicbrainzServerError(ref msg) => write!(f, "MusicBrainz server error: {}", msg),
+            InvalidIncludes(ref e) => write!(f, "invalid includes: {}", e),
+            InvalidLanguage(ref code) => write!(f, "invalid language code: {:?}", code),
+            ServiceUnavailable { attempts } => {
+                write!(
+                    f,
+                    "MusicBrainz returned 503 (ServiceUnavailable) {} times",
+                    attempts
+                )
+            }
+            UnexpectedContent {
+                ref content_type,
+                ref snippet,
+            } => {
+                write!(
+                    f,
+                    "unexpected content (content type: {}): {}",
+                    content_type.as_ref().map(|s| s.as_str()).unwrap_or("unknown"),
+                    snippet
+                )
+            }
+            Unauthorized => write!(f, "MusicBrainz returned 401 (Unauthorized)"),
+            ResponseTooLarge { size, limit } => {
+                write!(f, "response of {} bytes exceeds the limit of {} bytes", size, limit)
+            }
+            CollectionEntityMismatch {
+                ref collection,
+                ref requested,
+            } => {
+                write!(f, "requested {} entities of a {} collection", requested, collection)
+            }
         }
     }
 }
 
-error_chain!{
-    types {
-        ClientError, ClientErrorKind, ChainClientErr;
+#[cfg(feature = "client")]
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.kind)?;
+        if let Some(ref url) = self.url {
+            write!(f, " (url: {}", url)?;
+            if let Some(entity) = self.entity {
+                write!(f, ", entity: {}", entity)?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
     }
+}
 
-    links {
-        ParseError(ParseError, ParseErrorKind);
-        XpathReadError(::xpath_reader::XpathError, ::xpath_reader::XpathErrorKind);
-        ReqwestMockError(::reqwest_mock::Error, ::reqwest_mock::error::ErrorKind);
+#[cfg(feature = "client")]
+impl Error for ClientError {
+    fn description(&self) -> &str
+    {
+        use self::ClientErrorKind::*;
+        match self.kind {
+            Parse(_) => "parse error",
+            Xpath(_) => "xpath error",
+            FromXml(_) => "failed reading xml",
+            Http(_) => "http error",
+            Io(_) => "io error",
+            Url(_) => "invalid url",
+            MusicbrainzServerError(_) => "MusicBrainz server error",
+            ServiceUnavailable { .. } => "MusicBrainz service unavailable",
+            InvalidIncludes(_) => "invalid includes",
+            InvalidLanguage(_) => "invalid language code",
+            UnexpectedContent { .. } => "unexpected content",
+            Unauthorized => "unauthorized",
+            ResponseTooLarge { .. } => "response too large",
+            CollectionEntityMismatch { .. } => "collection entity mismatch",
+        }
     }
 
-    foreign_links {
-        IoError(::std::io::Error);
-        Url(::reqwest_mock::UrlError);
-        FromXmlError(::xpath_reader::FromXmlError);
+    fn cause(&self) -> Option<&Error>
+    {
+        use self::ClientErrorKind::*;
+        match self.kind {
+            Parse(ref e) => Some(e),
+            Xpath(ref e) => Some(e),
+            FromXml(ref e) => Some(e),
+            Http(ref e) => Some(e),
+            Io(ref e) => Some(e),
+            Url(ref e) => Some(e),
+            InvalidIncludes(ref e) => Some(e),
+            MusicbrainzServerError(_) |
+            InvalidLanguage(_) |
+            ServiceUnavailable { .. } |
+            UnexpectedContent { .. } |
+            Unauthorized |
+            ResponseTooLarge { .. } |
+            CollectionEntityMismatch { .. } => None,
+        }
     }
+}
 
-    errors {
-        /// This is most likely a rate limit, but since the errors aren't coded and there are
-        /// also other issues like the server being busy we don't distinguish it except for the
-        /// provided message.
-        MusicbrainzServerError(msg: String) {
-            description("MusicBrainz server error")
-            display("MusicBrainz server error: {}", msg)
+#[cfg(feature = "client")]
+impl From<ClientErrorKind> for ClientError {
+    fn from(kind: ClientErrorKind) -> Self
+    {
+        ClientError {
+            kind: kind,
+            url: None,
+            entity: None,
         }
     }
 }
+
+#[cfg(feature = "client")]
+macro_rules! client_error_from {
+    ( $( $type:ty => $variant:ident ),+ ) => {
+        $(
+            impl From<$type> for ClientError {
+                fn from(e: $type) -> Self
+                {
+                    ClientErrorKind::$variant(e).into()
+                }
+            }
+        )+
+    }
+}
+
+#[cfg(feature = "client")]
+client_error_from!(
+    ParseError => Parse,
+    XpathError => Xpath,
+    FromXmlError => FromXml,
+    HttpError => Http,
+    io::Error => Io,
+    InvalidInclude => InvalidIncludes,
+    UrlError => Url
+);
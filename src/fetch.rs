@@ -0,0 +1,109 @@
+//! Fetching entities with a caller supplied HTTP stack.
+//!
+//! Without the `client` feature, e. g. on `wasm32-unknown-unknown`, the
+//! documents can be requested by any means (like the browser's `fetch`) by
+//! implementing `FetchBody`, the urls and the parsing are the same as for the
+//! `Client`.
+
+use entities::{FromXmlStr, Mbid, Resource};
+use errors::FetchError;
+use xpath_reader::reader::FromXmlContained;
+
+/// Performs the GET requests to the web service for `fetch_entity`.
+///
+/// It is implemented for closures taking the url and returning the body of
+/// the response.
+pub trait FetchBody {
+    /// The error returned if a request fails.
+    type Error;
+
+    /// Requests `url` and returns the body of the response.
+    fn fetch_body(&mut self, url: &str) -> Result<String, Self::Error>;
+}
+
+impl<F, E> FetchBody for F
+where
+    F: FnMut(&str) -> Result<String, E>,
+{
+    type Error = E;
+
+    fn fetch_body(&mut self, url: &str) -> Result<String, E>
+    {
+        self(url)
+    }
+}
+
+/// Fetches the entity with the MBID from the main MusicBrainz server, with
+/// the same includes `Client::get_by_mbid` requests.
+pub fn fetch_entity<Res, F>(fetcher: &mut F, mbid: &Mbid) -> Result<Res, FetchError<F::Error>>
+where
+    Res: Resource + FromXmlContained,
+    F: FetchBody,
+{
+    fetch_entity_at(fetcher, ::util::DEFAULT_BASE_URL, mbid)
+}
+
+/// Like `fetch_entity` but from the web service at `base`, e. g.
+/// `"https://test.musicbrainz.org/ws/2"`.
+pub fn fetch_entity_at<Res, F>(
+    fetcher: &mut F,
+    base: &str,
+    mbid: &Mbid,
+) -> Result<Res, FetchError<F::Error>>
+where
+    Res: Resource + FromXmlContained,
+    F: FetchBody,
+{
+    let body = fetcher.fetch_body(&Res::get_url_at(base, mbid)).map_err(FetchError::Fetch)?;
+    Res::from_xml_str(&body).map_err(FetchError::Parse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::Collection;
+    use std::str::FromStr;
+
+    const XML: &'static str = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><collection type="Artist" entity-type="artist" type-id="0f2f7d6d-2b7b-3b0c-a4a8-0b0a3c9f6bd0" id="9b2b4c88-8d27-4c5c-9f5a-1c0e0c8d0f35"><name>Favourite artists</name><editor>example</editor><artist-list count="42"/></collection></metadata>"#;
+
+    #[test]
+    fn fetch_with_closure()
+    {
+        let mbid = Mbid::from_str("9b2b4c88-8d27-4c5c-9f5a-1c0e0c8d0f35").unwrap();
+        let mut urls = Vec::new();
+        let collection: Collection = fetch_entity(
+            &mut |url: &str| -> Result<String, ()> {
+                urls.push(url.to_string());
+                Ok(XML.to_string())
+            },
+            &mbid,
+        ).unwrap();
+
+        assert_eq!(collection.name, "Favourite artists".to_string());
+        assert_eq!(urls, vec![Collection::get_url(&mbid)]);
+    }
+
+    #[test]
+    fn fetch_errors()
+    {
+        let mbid = Mbid::from_str("9b2b4c88-8d27-4c5c-9f5a-1c0e0c8d0f35").unwrap();
+
+        let offline = fetch_entity::<Collection, _>(
+            &mut |_: &str| -> Result<String, &'static str> { Err("offline") },
+            &mbid,
+        );
+        match offline {
+            Err(FetchError::Fetch(e)) => assert_eq!(e, "offline"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let garbage = fetch_entity::<Collection, _>(
+            &mut |_: &str| -> Result<String, ()> { Ok("<html></html>".to_string()) },
+            &mbid,
+        );
+        match garbage {
+            Err(FetchError::Parse(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
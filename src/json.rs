@@ -0,0 +1,568 @@
+//! Export of entities as JSON in the layout of the MusicBrainz JSON web
+//! service.
+//!
+//! This allows feeding consumers of the JSON API (ws/2 with `fmt=json`) with
+//! data fetched by this crate. Keys are kebab-case, lengths are in
+//! milliseconds and missing values are `null` just like in the responses of
+//! the JSON API, data which isn't read by this crate or which wasn't requested
+//! (e. g. the aliases of an artist fetched without `Include::Aliases`) is
+//! omitted.
+//!
+//! Only available with the `serde_json` feature.
+
+use std::time::Duration;
+
+use serde_json::Value;
+
+use entities::{Alias, Area, AreaRef, Artist, ArtistRef, Event, Label, LabelInfo, LabelRef, Place,
+               PartialDate, Recording, RecordingRef, Release, ReleaseEvent, ReleaseGroup,
+               ReleaseMedium, ReleaseRef, ReleaseTrack, Series, Tag};
+
+/// Conversion into the JSON representation of the MusicBrainz JSON API.
+pub trait ToMbJson {
+    /// Returns the JSON value as it would be returned by the JSON API.
+    fn to_mb_json(&self) -> Value;
+
+    /// Returns the serialized JSON document.
+    fn to_mb_json_string(&self) -> String
+    {
+        self.to_mb_json().to_string()
+    }
+}
+
+impl<T: ToMbJson> ToMbJson for Option<T> {
+    fn to_mb_json(&self) -> Value
+    {
+        match *self {
+            Some(ref value) => value.to_mb_json(),
+            None => Value::Null,
+        }
+    }
+}
+
+impl<T: ToMbJson> ToMbJson for Vec<T> {
+    fn to_mb_json(&self) -> Value
+    {
+        Value::Array(self.iter().map(|v| v.to_mb_json()).collect())
+    }
+}
+
+/// A string value or `null`.
+fn string<T: ToString>(value: &Option<T>) -> Value
+{
+    match *value {
+        Some(ref v) => Value::String(v.to_string()),
+        None => Value::Null,
+    }
+}
+
+/// A length in milliseconds or `null`.
+fn length(value: &Option<Duration>) -> Value
+{
+    match *value {
+        Some(d) => json!(d.as_secs() * 1000 + (d.subsec_nanos() / 1_000_000) as u64),
+        None => Value::Null,
+    }
+}
+
+/// A date in the format of the JSON API or `null`.
+///
+/// Unknown trailing parts are left out instead of written as `??`, e. g.
+/// `"2012-03"` instead of `"2012-03-??"`.
+fn date(value: &Option<PartialDate>) -> Value
+{
+    match *value {
+        Some(ref d) => {
+            let full = d.to_string();
+            match full.trim_right_matches(|c: char| c == '?' || c == '-') {
+                "" => Value::Null,
+                known => Value::String(known.to_string()),
+            }
+        }
+        None => Value::Null,
+    }
+}
+
+fn life_span(begin: &Option<PartialDate>, end: &Option<PartialDate>, ended: bool) -> Value
+{
+    json!({
+        "begin": date(begin),
+        "end": date(end),
+        "ended": ended
+    })
+}
+
+/// The JSON API represents label codes as numbers.
+fn label_code(value: &Option<String>) -> Value
+{
+    match *value {
+        Some(ref code) => match code.parse::<u32>() {
+            Ok(n) => json!(n),
+            Err(_) => Value::String(code.clone()),
+        },
+        None => Value::Null,
+    }
+}
+
+fn artist_credit(artists: &[ArtistRef]) -> Value
+{
+    Value::Array(
+        artists
+            .iter()
+            .map(|artist| {
+                json!({
+                    "name": artist.credited_as.as_ref().unwrap_or(&artist.name),
+                    "joinphrase": artist.join_phrase.clone().unwrap_or_default(),
+                    "artist": artist.to_mb_json()
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Removes the keys which are `null`, for lists which weren't requested and
+/// which the JSON API omits as well.
+fn omit_missing(mut value: Value, keys: &[&str]) -> Value
+{
+    if let Some(object) = value.as_object_mut() {
+        for key in keys {
+            if object.get(*key) == Some(&Value::Null) {
+                object.remove(*key);
+            }
+        }
+    }
+    value
+}
+
+/// Aliases which were only read as plain names.
+fn name_aliases(aliases: &[String]) -> Value
+{
+    Value::Array(
+        aliases
+            .iter()
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "sort-name": null,
+                    "locale": null,
+                    "primary": null,
+                    "type": null
+                })
+            })
+            .collect(),
+    )
+}
+
+impl ToMbJson for Alias {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "name": self.name,
+            "sort-name": string(&self.sort_name),
+            "locale": string(&self.locale),
+            "primary": if self.primary { json!(true) } else { Value::Null },
+            "type": string(&self.alias_type)
+        })
+    }
+}
+
+impl ToMbJson for Tag {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "name": self.name,
+            "count": self.count
+        })
+    }
+}
+
+impl ToMbJson for AreaRef {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "id": self.mbid.to_string(),
+            "name": self.name,
+            "sort-name": self.sort_name,
+            "type": string(&self.area_type),
+            "iso-3166-1-codes": self.iso_3166.iter().collect::<Vec<_>>(),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default()
+        })
+    }
+}
+
+impl ToMbJson for ArtistRef {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "id": self.mbid.to_string(),
+            "name": self.name,
+            "sort-name": self.sort_name,
+            "type": string(&self.artist_type),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default(),
+            "aliases": self.aliases.to_mb_json()
+        })
+    }
+}
+
+impl ToMbJson for LabelRef {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "id": self.mbid.to_string(),
+            "name": self.name,
+            "sort-name": self.sort_name,
+            "label-code": label_code(&self.label_code),
+            "aliases": self.aliases.to_mb_json()
+        })
+    }
+}
+
+impl ToMbJson for RecordingRef {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "id": self.mbid.to_string(),
+            "title": self.title,
+            "length": length(&self.length)
+        })
+    }
+}
+
+impl ToMbJson for ReleaseRef {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "id": self.mbid.to_string(),
+            "title": self.title,
+            "date": date(&self.date),
+            "status": string(&self.status),
+            "country": string(&self.country)
+        })
+    }
+}
+
+impl ToMbJson for Area {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "id": self.mbid.to_string(),
+            "name": self.name,
+            "sort-name": self.sort_name,
+            "type": self.area_type.to_string(),
+            "iso-3166-1-codes": self.iso_3166.iter().collect::<Vec<_>>(),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default()
+        })
+    }
+}
+
+impl ToMbJson for Artist {
+    fn to_mb_json(&self) -> Value
+    {
+        let json = json!({
+            "id": self.mbid.to_string(),
+            "name": self.name,
+            "sort-name": self.sort_name,
+            "type": string(&self.artist_type),
+            "gender": string(&self.gender),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default(),
+            "annotation": string(&self.annotation),
+            "area": self.area.to_mb_json(),
+            "life-span": life_span(&self.begin_date, &self.end_date, self.ended),
+            "ipis": self.ipi_code.iter().collect::<Vec<_>>(),
+            "isnis": self.isni_code.iter().collect::<Vec<_>>(),
+            "aliases": self.aliases.to_mb_json(),
+            "tags": self.tags.to_mb_json()
+        });
+        omit_missing(json, &["aliases", "tags"])
+    }
+}
+
+impl ToMbJson for Event {
+    fn to_mb_json(&self) -> Value
+    {
+        let json = json!({
+            "id": self.mbid.to_string(),
+            "name": self.name,
+            "type": string(&self.event_type),
+            "setlist": self.setlist.clone().unwrap_or_default(),
+            "time": self.time.as_ref().map(|t| t.to_string()).unwrap_or_default(),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default(),
+            "annotation": string(&self.annotation),
+            "life-span": life_span(&self.begin_date, &self.end_date, self.ended),
+            "aliases": self.aliases.as_ref().map(|a| name_aliases(a))
+        });
+        omit_missing(json, &["aliases"])
+    }
+}
+
+impl ToMbJson for Label {
+    fn to_mb_json(&self) -> Value
+    {
+        let json = json!({
+            "id": self.mbid.to_string(),
+            "name": self.name,
+            "sort-name": self.sort_name,
+            "type": string(&self.label_type),
+            "label-code": label_code(&self.label_code),
+            "country": string(&self.country),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default(),
+            "life-span": life_span(&self.begin_date, &self.end_date, self.ended),
+            "ipis": self.ipi_code.iter().collect::<Vec<_>>(),
+            "isnis": self.isni_code.iter().collect::<Vec<_>>(),
+            "aliases": self.aliases.as_ref().map(|a| name_aliases(a))
+        });
+        omit_missing(json, &["aliases"])
+    }
+}
+
+impl ToMbJson for Place {
+    fn to_mb_json(&self) -> Value
+    {
+        let coordinates = match self.coordinates {
+            Some(ref c) => json!({
+                "latitude": c.latitude.parse::<f64>().ok(),
+                "longitude": c.longitude.parse::<f64>().ok()
+            }),
+            None => Value::Null,
+        };
+
+        let json = json!({
+            "id": self.mbid.to_string(),
+            "name": self.name,
+            "type": string(&self.place_type),
+            "address": self.address.clone().unwrap_or_default(),
+            "coordinates": coordinates,
+            "area": self.area.to_mb_json(),
+            "life-span": life_span(&self.begin, &self.end, self.ended),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default(),
+            "annotation": string(&self.annotation),
+            "aliases": self.aliases.as_ref().map(|a| name_aliases(a))
+        });
+        omit_missing(json, &["aliases"])
+    }
+}
+
+impl ToMbJson for Recording {
+    fn to_mb_json(&self) -> Value
+    {
+        let json = json!({
+            "id": self.mbid.to_string(),
+            "title": self.title,
+            "length": length(&self.duration),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default(),
+            "annotation": string(&self.annotation),
+            "isrcs": self.isrc_code.iter().map(|i| i.to_string()).collect::<Vec<_>>(),
+            "artist-credit": self.artists.as_ref().map(|a| artist_credit(a))
+        });
+        omit_missing(json, &["artist-credit"])
+    }
+}
+
+impl ToMbJson for ReleaseTrack {
+    fn to_mb_json(&self) -> Value
+    {
+        let mut recording = self.recording.to_mb_json();
+        if !self.isrcs.is_empty() {
+            let isrcs: Vec<&str> = self.isrcs.iter().map(|isrc| isrc.as_str()).collect();
+            recording["isrcs"] = json!(isrcs);
+        }
+
+        json!({
+            "id": self.mbid.to_string(),
+            "position": self.position,
+            "number": self.number,
+            "title": self.title,
+            "length": length(&self.length),
+            "artist-credit": artist_credit(&self.artists),
+            "recording": recording
+        })
+    }
+}
+
+impl ToMbJson for ReleaseMedium {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "position": self.position,
+            "format": string(&self.format),
+            "track-count": self.track_count,
+            "track-offset": self.track_offset,
+            "tracks": self.tracks.to_mb_json()
+        })
+    }
+}
+
+impl ToMbJson for LabelInfo {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "catalog-number": string(&self.catalog_number),
+            "label": self.label.to_mb_json()
+        })
+    }
+}
+
+impl ToMbJson for ReleaseEvent {
+    fn to_mb_json(&self) -> Value
+    {
+        json!({
+            "date": date(&self.date),
+            "area": self.area.to_mb_json()
+        })
+    }
+}
+
+impl ToMbJson for Release {
+    fn to_mb_json(&self) -> Value
+    {
+        let json = json!({
+            "id": self.mbid.to_string(),
+            "title": self.title,
+            "status": string(&self.status),
+            "packaging": string(&self.packaging),
+            "date": date(&self.date),
+            "country": string(&self.country),
+            "barcode": string(&self.barcode),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default(),
+            "annotation": string(&self.annotation),
+            "text-representation": {
+                "language": self.language.as_ref().map(|l| l.to_639_3()),
+                "script": string(&self.script)
+            },
+            "release-events": self.release_events.to_mb_json(),
+            "label-info": self.labels.to_mb_json(),
+            "media": self.mediums.to_mb_json(),
+            "artist-credit": self.artists.as_ref().map(|a| artist_credit(a)),
+            "cover-art-archive": {
+                "artwork": self.cover_art
+            }
+        });
+        omit_missing(json, &["label-info", "artist-credit"])
+    }
+}
+
+impl ToMbJson for ReleaseGroup {
+    fn to_mb_json(&self) -> Value
+    {
+        let json = json!({
+            "id": self.mbid.to_string(),
+            "title": self.title,
+            "primary-type": string(&self.release_type.primary),
+            "secondary-types": self.release_type
+                .secondary
+                .iter()
+                .map(|t| t.to_string())
+                .collect::<Vec<_>>(),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default(),
+            "annotation": string(&self.annotation),
+            "artist-credit": self.artists.as_ref().map(|a| artist_credit(a)),
+            "releases": self.releases.as_ref().map(|_| {
+                self.all_releases().iter().map(|r| r.to_mb_json()).collect::<Vec<_>>()
+            })
+        });
+        omit_missing(json, &["artist-credit", "releases"])
+    }
+}
+
+impl ToMbJson for Series {
+    fn to_mb_json(&self) -> Value
+    {
+        let json = json!({
+            "id": self.mbid.to_string(),
+            "type": self.series_type.to_string(),
+            "disambiguation": self.disambiguation.clone().unwrap_or_default(),
+            "annotation": string(&self.annotation),
+            "aliases": self.aliases.as_ref().map(|a| name_aliases(a))
+        });
+        omit_missing(json, &["aliases"])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::Mbid;
+    use std::str::FromStr;
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn release_group_json()
+    {
+        let mbid = Mbid::from_str("76a4e2c2-bf7a-445e-8081-5a1e291f3b16").unwrap();
+        let rg: ReleaseGroup = ::util::test_utils::fetch_entity(&mbid).unwrap();
+
+        assert_eq!(
+            rg.to_mb_json(),
+            json!({
+                "id": "76a4e2c2-bf7a-445e-8081-5a1e291f3b16",
+                "title": "Mixtape",
+                "primary-type": "Album",
+                "secondary-types": ["Mixtape/Street"],
+                "disambiguation": "",
+                "annotation": null,
+                "artist-credit": [{
+                    "name": "POP ETC",
+                    "joinphrase": "",
+                    "artist": {
+                        "id": "0e6b3a2c-6a42-4b43-a4f6-c6625c5855de",
+                        "name": "POP ETC",
+                        "sort-name": "POP ETC",
+                        "type": null,
+                        "disambiguation": "",
+                        "aliases": []
+                    }
+                }],
+                "releases": [{
+                    "id": "289bf4e7-0af5-433c-b5a2-493b863b4b47",
+                    "title": "Mixtape",
+                    "date": "2012-03",
+                    "status": "Official",
+                    "country": "US"
+                }]
+            })
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn omit_lists_not_requested()
+    {
+        let mbid = Mbid::from_str("76a4e2c2-bf7a-445e-8081-5a1e291f3b16").unwrap();
+        let mut rg: ReleaseGroup = ::util::test_utils::fetch_entity(&mbid).unwrap();
+        rg.releases = None;
+
+        let json = rg.to_mb_json();
+        assert!(json.get("releases").is_none());
+        assert!(json.get("artist-credit").is_some());
+    }
+
+    #[test]
+    fn lengths_and_label_codes()
+    {
+        assert_eq!(length(&Some(Duration::from_millis(232560))), json!(232560));
+        assert_eq!(length(&None), Value::Null);
+        assert_eq!(label_code(&Some("7649".to_string())), json!(7649));
+        assert_eq!(label_code(&None), Value::Null);
+    }
+
+    #[test]
+    fn dates_and_life_spans()
+    {
+        let date_of = |s: &str| Some(PartialDate::from_str(s).unwrap());
+        assert_eq!(date(&date_of("1992-09-21")), json!("1992-09-21"));
+        assert_eq!(date(&date_of("2012-03")), json!("2012-03"));
+        assert_eq!(date(&date_of("1991")), json!("1991"));
+        assert_eq!(date(&Some(PartialDate::new(None, None, None))), Value::Null);
+        assert_eq!(date(&None), Value::Null);
+
+        // The end date alone doesn't mean the life span has ended.
+        assert_eq!(
+            life_span(&date_of("2014-03-28"), &date_of("2014-03-28"), false),
+            json!({"begin": "2014-03-28", "end": "2014-03-28", "ended": false})
+        );
+        assert_eq!(
+            life_span(&date_of("1985"), &None, true),
+            json!({"begin": "1985", "end": null, "ended": true})
+        );
+    }
+}
@@ -1,27 +1,40 @@
-// Apparently this is recommended when using error-chain.
-#![recursion_limit = "1024"]
-
 // TODO: Remove before stable release.
 #![allow(dead_code)]
 
-#[macro_use]
-extern crate error_chain;
 extern crate isolang;
+#[cfg(feature = "regex")]
 extern crate regex;
+#[cfg(feature = "client")]
 extern crate reqwest_mock;
 extern crate uuid;
+#[cfg(feature = "client")]
 extern crate url;
 extern crate xpath_reader;
 
 pub mod errors;
 pub use self::errors::*;
 
+#[cfg(feature = "client")]
 pub mod client;
+pub mod endpoint;
 pub mod entities;
+pub mod fetch;
+#[cfg(feature = "client")]
 pub mod search;
+pub mod tagging;
+#[cfg(feature = "text")]
+pub mod text;
 
 mod util;
 
+#[cfg(feature = "serde_json")]
+#[macro_use]
+extern crate serde_json;
+#[cfg(feature = "serde_json")]
+pub mod json;
+#[cfg(feature = "wikipedia")]
+pub mod wikipedia;
+
 #[cfg(feature = "rusqlite")]
 extern crate rusqlite;
 #[cfg(feature = "rusqlite")]
@@ -1,8 +1,8 @@
-use super::entities::{Date, Mbid};
+use super::entities::{Mbid, PartialDate};
 use rusqlite::Error as RusqliteError;
 use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, Value, ValueRef};
 
-impl FromSql for Date {
+impl FromSql for PartialDate {
     fn column_result(value: ValueRef) -> Result<Self, FromSqlError>
     {
         match value {
@@ -12,7 +12,7 @@ impl FromSql for Date {
     }
 }
 
-impl ToSql for Date {
+impl ToSql for PartialDate {
     fn to_sql(&self) -> Result<ToSqlOutput, RusqliteError>
     {
         let s = self.to_string();
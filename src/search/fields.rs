@@ -11,6 +11,9 @@ use super::full_entities;
 // use super::query::QueryExpression;
 use super::full_entities::{Mbid, PartialDate};
 // use super::entities;
+use super::query::{escape_lucene, escape_lucene_keep_wildcards};
+use std::cmp;
+use std::time::Duration;
 
 pub trait SearchField {
     type Value;
@@ -35,6 +38,166 @@ macro_rules! define_fields {
     }
 }
 
+/// Formats a date the way it is stored in the search index, leaving out the
+/// unknown components.
+///
+/// A date without a year is used as an open bound (`*`) of a range.
+fn lucene_date(date: &PartialDate) -> String
+{
+    match (date.year(), date.month(), date.day()) {
+        (Some(y), Some(m), Some(d)) => format!("{:04}-{:02}-{:02}", y, m, d),
+        (Some(y), Some(m), None) => format!("{:04}-{:02}", y, m),
+        (Some(y), _, _) => format!("{:04}", y),
+        (None, _, _) => "*".to_string(),
+    }
+}
+
+/// Formats an inclusive Lucene range query value.
+fn lucene_range<T: ToString>(from: T, to: T) -> String
+{
+    format!("[{} TO {}]", from.to_string(), to.to_string())
+}
+
+/// Formats a date as a value matching all dates within it, e. g. all dates in
+/// 2014 for `2014`, which is `[2014 TO 2014-12-31]`.
+///
+/// The index stores dates as strings with only the known components, so a
+/// partial date like `2014` wouldn't match `2014-03-28` as a plain term.
+fn lucene_date_within(date: &PartialDate) -> String
+{
+    match (date.year(), date.month(), date.day()) {
+        (Some(_), Some(_), Some(_)) => lucene_date(date),
+        (Some(y), Some(m), None) => {
+            lucene_range(lucene_date(date), format!("{:04}-{:02}-31", y, m))
+        }
+        (Some(y), None, _) => lucene_range(lucene_date(date), format!("{:04}-12-31", y)),
+        (None, _, _) => "*".to_string(),
+    }
+}
+
+macro_rules! define_date_fields {
+    ( $( $(#[$attr:meta])* - $type:ident, $range_type:ident; )* ) => {
+        $(
+            $(#[$attr])*
+            ///
+            /// A partial date matches all dates within it, e. g. `2014` matches
+            /// every date in 2014.
+            pub struct $type(pub PartialDate);
+
+            impl SearchField for $type {
+                type Value = PartialDate;
+
+                fn to_string(&self) -> String
+                {
+                    lucene_date_within(&self.0)
+                }
+            }
+
+            $(#[$attr])*
+            ///
+            /// This is an inclusive range, use a `PartialDate` without a year
+            /// for an open bound.
+            pub struct $range_type(pub PartialDate, pub PartialDate);
+
+            impl SearchField for $range_type {
+                type Value = (PartialDate, PartialDate);
+
+                fn to_string(&self) -> String
+                {
+                    lucene_range(lucene_date(&self.0), lucene_date(&self.1))
+                }
+            }
+        )*
+    }
+}
+
+define_date_fields!(
+    /// Begin date of the searched entity, e. g. the formation date of a group.
+    ///
+    /// Check the searched entity's documentation for more information what
+    /// this means concretely.
+    - BeginDate, BeginDateRange;
+    /// End date of the searched entity, e. g. the dissolution date of a group.
+    ///
+    /// Check the searched entity's documentation for more information what
+    /// this means concretely.
+    - EndDate, EndDateRange;
+    /// Release date of a `Release`, e. g. `date:[2000 TO 2010]`.
+    - ReleaseDate, DateRange;
+);
+
+/// An inclusive range of durations, e. g. `dur:[200000 TO 300000]`.
+///
+/// The index stores durations in milliseconds.
+pub struct DurationRange(pub Duration, pub Duration);
+
+impl SearchField for DurationRange {
+    type Value = (Duration, Duration);
+
+    fn to_string(&self) -> String
+    {
+        let millis = |d: &Duration| d.as_secs() * 1000 + (d.subsec_nanos() / 1_000_000) as u64;
+        lucene_range(millis(&self.0), millis(&self.1))
+    }
+}
+
+/// An inclusive range of the total number of tracks, e. g. `tracks:[10 TO 12]`.
+pub struct TrackCountRange(pub u32, pub u32);
+
+impl SearchField for TrackCountRange {
+    type Value = (u32, u32);
+
+    fn to_string(&self) -> String
+    {
+        lucene_range(self.0, self.1)
+    }
+}
+
+/// Matches terms similar to the value of the wrapped field, e. g.
+/// `artist:radiohed~`.
+///
+/// The second value is the maximal edit distance between a term of the value
+/// and a matched term, Lucene supports distances from 0 to 2 so larger
+/// distances are reduced to 2. Special characters in the value are escaped, so
+/// the value is used literally. A value without any terms results in an empty
+/// string, which the search builders skip.
+pub struct Fuzzy<F>(pub F, pub u8);
+
+impl<F: SearchField> SearchField for Fuzzy<F> {
+    type Value = F::Value;
+
+    fn to_string(&self) -> String
+    {
+        let distance = cmp::min(self.1, 2);
+        let terms: Vec<String> = self.0
+            .to_string()
+            .split_whitespace()
+            .map(|term| format!("{}~{}", escape_lucene(term), distance))
+            .collect();
+        if terms.is_empty() {
+            String::new()
+        } else {
+            format!("({})", terms.join(" "))
+        }
+    }
+}
+
+/// Uses the value of the wrapped field as a wildcard pattern, e. g.
+/// `release:ok comp*`.
+///
+/// In the pattern `*` matches any number of characters and `?` matches a
+/// single character. All other special characters are escaped.
+pub struct Wildcard<F>(pub F);
+
+impl<F: SearchField> SearchField for Wildcard<F> {
+    type Value = F::Value;
+
+    fn to_string(&self) -> String
+    {
+        format!("({})", escape_lucene_keep_wildcards(self.0.to_string().trim()))
+    }
+}
+
 // TODO consider whether we should rename `Comment` to `Disambiguation` or
 // something like that to
 // be more consistent with the rest of the crate.
@@ -43,6 +206,8 @@ macro_rules! define_fields {
 // TODO it's a bit ugly we have `-` at the beginning of every line but its a
 // workaround around the parsing ambiguity we'd have if we didn't.
 define_fields!(
+    /// The address of a `Place`.
+    - Address, String;
     /// Alias of the searched entity's name.
     - Alias, String;
     /// The MBID of the `Area`.
@@ -72,10 +237,6 @@ define_fields!(
     /// The barcode of a `Release`.
     - Barcode, String;
     - BeginArea, String;
-    /// Begin date of the searched entity.
-    ///
-    /// Check the searched entity's documentation for more information what this means concretely.
-    - BeginDate, PartialDate;
     - CatalogNumber, String;
     /// Disambiguation comment of the searched entity.
     - Comment, String;
@@ -83,10 +244,14 @@ define_fields!(
     - CreditName, String;
     - DataQuality, String;
     - EndArea, String;
-    /// End date of the searched entity.
-    ///
-    /// Check the searched entity's documentation for more information what this means concretely.
-    - EndDate, PartialDate;
+    /// The MBID of the `Event`.
+    - EventMbid, Mbid;
+    /// The name of the `Event` without accented characters.
+    - EventName, String;
+    /// The name of the `Event` with accented characters.
+    - EventNameAccent, String;
+    /// The type of the `Event`.
+    - EventType, full_entities::EventType;
     /// Whether the searched entity has already ended.
     ///
     /// Check the searched entity's documentation for more information what this means concretely.
@@ -94,8 +259,24 @@ define_fields!(
     /// The gender of an `Artist`.
     - Gender, String;
     - IpiCode, String;
+    /// The ISRC of a `Recording`.
+    - Isrc, String;
+    /// The ISWC of a `Work`.
+    - Iswc, String;
+    /// The label code of a `Label`, without the `LC-` prefix.
+    - LabelCode, u32;
     - LabelId, String;
+    /// The name of the `Label` without accented characters.
+    - LabelName, String;
+    /// The name of the `Label` with accented characters.
+    - LabelNameAccent, String;
+    /// The type of the `Label`.
+    - LabelType, full_entities::LabelType;
     - Language, full_entities::Language;
+    /// The latitude of a `Place`.
+    - Latitude, f64;
+    /// The longitude of a `Place`.
+    - Longitude, f64;
     - MediumCount, u32;
     - MediumFormat, String;
     /// The searched entity's name. (TODO implement for all relevant searches)
@@ -104,8 +285,23 @@ define_fields!(
     - NumDiscIdsMedium, u32;
     - NumTracks, u32;
     - NumTracksMedium, u32;
+    /// The position of the medium in the `Release`.
+    - MediumPosition, u32;
+    /// The MBID of the `Place`.
+    - PlaceMbid, Mbid;
+    /// The name of the `Place` without accented characters.
+    - PlaceName, String;
+    /// The name of the `Place` with accented characters.
+    - PlaceNameAccent, String;
+    /// The type of the `Place`.
+    - PlaceType, full_entities::PlaceType;
     - PrimaryType, full_entities::ReleaseGroupPrimaryType;
-    - ReleaseDate, full_entities::PartialDate;
+    /// The MBID of the `Recording`.
+    - RecordingMbid, Mbid;
+    /// The name of the `Recording` without accented characters.
+    - RecordingName, String;
+    /// The name of the `Recording` with accented characters.
+    - RecordingNameAccent, String;
     - ReleaseGroupId, Mbid;
     - ReleaseGroupName, String;
     - ReleaseGroupNameAccent, String;
@@ -118,131 +314,398 @@ define_fields!(
     - ReleaseStatus, full_entities::ReleaseStatus;
     - Script, String;
     - SecondaryType, String;
+    /// The MBID of the `Series`.
+    - SeriesMbid, Mbid;
+    /// The name of the `Series` without accented characters.
+    - SeriesName, String;
+    /// The name of the `Series` with accented characters.
+    - SeriesNameAccent, String;
+    /// The type of the `Series`.
+    - SeriesType, full_entities::SeriesType;
     /// The sort name of the searched entity.
     - SortName, String;
-    - Tag, String
+    - Tag, String;
+    /// The MBID of a track.
+    - TrackMbid, Mbid;
+    /// The number of a track as printed on the release, e. g. `A1`.
+    - TrackNumber, String;
+    /// The position of a track on its medium.
+    - TrackPosition, u32;
+    /// The number of tracks of the `Release` the searched entity appears on.
+    - TracksRelease, u32;
+    /// Whether a `Recording` is a video.
+    - Video, bool;
+    /// The MBID of the `Work`.
+    - WorkMbid, Mbid;
+    /// The name of the `Work` without accented characters.
+    - WorkName, String;
+    /// The name of the `Work` with accented characters.
+    - WorkNameAccent, String;
+    /// The type of the `Work`.
+    - WorkType, String
 );
 
+/// Defines the traits of the fields which can be used to search the entities
+/// and modules reexporting these fields, from a table with one block per
+/// entity.
+///
+/// Every line of a block maps the name of an index field to the field type, a
+/// field type can be listed for multiple index fields and an index field can
+/// take multiple field types (e. g. a single value and a range). The
+/// documentation given before the trait name is used for the module.
 macro_rules! define_entity_fields {
     (
-        $field_trait:ident, $modname:ident;
         $(
-             $strname:expr, $field_type:ident
-        );*
-        ;
+            $(#[$attr:meta])*
+            $field_trait:ident, $modname:ident {
+                $(
+                    $strname:expr, $field_type:ident;
+                )*
+            }
+        )*
     )
         =>
     {
-        /// Acceptable fields searching for instances of the entity.
-        pub trait $field_trait : SearchField {
-            fn name() -> &'static str;
-        }
+        $(
+            /// Acceptable fields searching for instances of the entity.
+            pub trait $field_trait : SearchField {
+                fn name() -> &'static str;
+            }
 
-        pub mod $modname {
-            pub use super::$field_trait;
+            impl<F: $field_trait> $field_trait for Fuzzy<F> {
+                fn name() -> &'static str { F::name() }
+            }
 
-            $(
-                pub use super::$field_type;
+            impl<F: $field_trait> $field_trait for Wildcard<F> {
+                fn name() -> &'static str { F::name() }
+            }
 
-                impl $field_trait for $field_type {
-                    fn name() -> &'static str { $strname }
-                }
-            )*
-        }
+            $(#[$attr])*
+            pub mod $modname {
+                pub use super::$field_trait;
+                pub use super::{Fuzzy, Wildcard};
 
+                $(
+                    pub use super::$field_type;
+
+                    impl $field_trait for $field_type {
+                        fn name() -> &'static str { $strname }
+                    }
+                )*
+            }
+        )*
     }
 }
 
-define_entity_fields!(
-    AreaSearchField, area;
-
-    "aid", AreaMbid;
-    "alias", Alias;
-    "area", AreaName;
-    "area", Name;
-    "begin", BeginDate;
-    "comment", Comment;
-    "end", EndDate;
-    "ended", Ended;
-    "iso", AreaIso;
-    "iso1", AreaIso1;
-    "iso2", AreaIso2;
-    "iso3", AreaIso3;
-    "sortname", SortName;
-    "type", AreaType;
-);
+define_entity_fields! {
+    /// Fields to search for `Area`s.
+    AreaSearchField, area {
+        "aid", AreaMbid;
+        "alias", Alias;
+        "area", AreaName;
+        "area", Name;
+        "begin", BeginDate;
+        "begin", BeginDateRange;
+        "comment", Comment;
+        "end", EndDate;
+        "end", EndDateRange;
+        "ended", Ended;
+        "iso", AreaIso;
+        "iso1", AreaIso1;
+        "iso2", AreaIso2;
+        "iso3", AreaIso3;
+        "sortname", SortName;
+        "type", AreaType;
+    }
 
-define_entity_fields!(
-    ArtistSearchField, artist;
-
-    "alias", Alias;
-    "area", AreaName;
-    "arid", ArtistMbid;
-    "artist", ArtistName;
-    "artistaccent", ArtistNameAccent;
-    "begin", BeginDate;
-    "beginarea", BeginArea;
-    "comment", Comment;
-    "country", Country;
-    "end", EndDate;
-    "endarea", EndArea;
-    "ended", Ended;
-    "gender", Gender;
-    "ipi", IpiCode;
-    "sortname", SortName;
-    "tag", Tag;
-    "type", ArtistType;
-);
+    /// Fields to search for `Artist`s.
+    ArtistSearchField, artist {
+        "alias", Alias;
+        "area", AreaName;
+        "arid", ArtistMbid;
+        "artist", ArtistName;
+        "artist", Name;
+        "artistaccent", ArtistNameAccent;
+        "begin", BeginDate;
+        "begin", BeginDateRange;
+        "beginarea", BeginArea;
+        "comment", Comment;
+        "country", Country;
+        "end", EndDate;
+        "end", EndDateRange;
+        "endarea", EndArea;
+        "ended", Ended;
+        "gender", Gender;
+        "ipi", IpiCode;
+        "sortname", SortName;
+        "tag", Tag;
+        "type", ArtistType;
+    }
 
-// TODO what are puids?
-define_entity_fields!(
-    ReleaseSearchField, release;
-
-    "arid", ArtistMbid;
-    "artist", ArtistName;
-    "asin", Asin;
-    "barcode", Barcode;
-    "catno", CatalogNumber;
-    "comment", Comment;
-    "country", Country;
-    "creditname", CreditName;
-    "date", ReleaseDate;
-    "discids", NumDiscIds;
-    "discidsmedium", NumDiscIdsMedium;
-    "format", MediumFormat;
-    "laid", LabelId;
-    "lang", Language;
-    "mediums", MediumCount;
-    "primarytype", PrimaryType;
-    "quality", DataQuality;
-    "reid", ReleaseId;
-    "release", ReleaseName;
-    "releaseaccent", ReleaseNameAccent;
-    "rgid", ReleaseGroupId;
-    "script", Script;
-    "secondarytype", SecondaryType;
-    "status", ReleaseStatus;
-    "tag", Tag;
-    "tracks", NumTracks;
-    "tracksmedium", NumTracksMedium;
-);
+    // TODO what are puids?
+    /// Fields to search for `Release`s.
+    ReleaseSearchField, release {
+        "arid", ArtistMbid;
+        "artist", ArtistName;
+        "asin", Asin;
+        "barcode", Barcode;
+        "catno", CatalogNumber;
+        "comment", Comment;
+        "country", Country;
+        "creditname", CreditName;
+        "date", ReleaseDate;
+        "date", DateRange;
+        "discids", NumDiscIds;
+        "discidsmedium", NumDiscIdsMedium;
+        "format", MediumFormat;
+        "label", LabelName;
+        "laid", LabelId;
+        "lang", Language;
+        "mediums", MediumCount;
+        "primarytype", PrimaryType;
+        "quality", DataQuality;
+        "reid", ReleaseId;
+        "release", ReleaseName;
+        "releaseaccent", ReleaseNameAccent;
+        "rgid", ReleaseGroupId;
+        "script", Script;
+        "secondarytype", SecondaryType;
+        "status", ReleaseStatus;
+        "tag", Tag;
+        "tracks", NumTracks;
+        "tracks", TrackCountRange;
+        "tracksmedium", NumTracksMedium;
+    }
 
-define_entity_fields!(
-    ReleaseGroupSearchField, release_group;
-
-    "arid", ArtistMbid;
-    "artist", ArtistCredit;
-    "artistname", ArtistName;
-    "comment", Comment;
-    "creditname", CreditName;
-    "primarytype", PrimaryType;
-    "reid", ReleaseId;
-    "release", ReleaseName;
-    "releasegroup", ReleaseGroupName;
-    "releasegroupaccent", ReleaseGroupNameAccent;
-    "releases", ReleaseNumber;
-    "rgid", ReleaseGroupId;
-    "secondarytype", SecondaryType;
-    "status", ReleaseStatus;
-    "tag", Tag;
-);
+    /// Fields to search for `ReleaseGroup`s.
+    ReleaseGroupSearchField, release_group {
+        "arid", ArtistMbid;
+        "artist", ArtistCredit;
+        "artistname", ArtistName;
+        "comment", Comment;
+        "creditname", CreditName;
+        "primarytype", PrimaryType;
+        "reid", ReleaseId;
+        "release", ReleaseName;
+        "releasegroup", ReleaseGroupName;
+        "releasegroupaccent", ReleaseGroupNameAccent;
+        "releases", ReleaseNumber;
+        "rgid", ReleaseGroupId;
+        "secondarytype", SecondaryType;
+        "status", ReleaseStatus;
+        "tag", Tag;
+    }
+
+    /// Fields to search for `Event`s.
+    EventSearchField, event {
+        "aid", AreaMbid;
+        "alias", Alias;
+        "area", AreaName;
+        "arid", ArtistMbid;
+        "artist", ArtistName;
+        "begin", BeginDate;
+        "begin", BeginDateRange;
+        "comment", Comment;
+        "eid", EventMbid;
+        "end", EndDate;
+        "end", EndDateRange;
+        "ended", Ended;
+        "event", EventName;
+        "eventaccent", EventNameAccent;
+        "pid", PlaceMbid;
+        "place", PlaceName;
+        "tag", Tag;
+        "type", EventType;
+    }
+
+    /// Fields to search for `Label`s.
+    LabelSearchField, label {
+        "alias", Alias;
+        "area", AreaName;
+        "begin", BeginDate;
+        "begin", BeginDateRange;
+        "code", LabelCode;
+        "comment", Comment;
+        "country", Country;
+        "end", EndDate;
+        "end", EndDateRange;
+        "ended", Ended;
+        "ipi", IpiCode;
+        "label", LabelName;
+        "labelaccent", LabelNameAccent;
+        "laid", LabelId;
+        "sortname", SortName;
+        "tag", Tag;
+        "type", LabelType;
+    }
+
+    /// Fields to search for `Place`s.
+    PlaceSearchField, place {
+        "address", Address;
+        "alias", Alias;
+        "area", AreaName;
+        "begin", BeginDate;
+        "begin", BeginDateRange;
+        "comment", Comment;
+        "end", EndDate;
+        "end", EndDateRange;
+        "ended", Ended;
+        "lat", Latitude;
+        "long", Longitude;
+        "pid", PlaceMbid;
+        "place", PlaceName;
+        "placeaccent", PlaceNameAccent;
+        "type", PlaceType;
+    }
+
+    /// Fields to search for `Recording`s.
+    RecordingSearchField, recording {
+        "arid", ArtistMbid;
+        "artist", ArtistCredit;
+        "artistname", ArtistName;
+        "comment", Comment;
+        "country", Country;
+        "creditname", CreditName;
+        "date", ReleaseDate;
+        "date", DateRange;
+        "dur", DurationRange;
+        "format", MediumFormat;
+        "isrc", Isrc;
+        "number", TrackNumber;
+        "position", MediumPosition;
+        "primarytype", PrimaryType;
+        "recording", RecordingName;
+        "recordingaccent", RecordingNameAccent;
+        "reid", ReleaseId;
+        "release", ReleaseName;
+        "rgid", ReleaseGroupId;
+        "rid", RecordingMbid;
+        "secondarytype", SecondaryType;
+        "status", ReleaseStatus;
+        "tag", Tag;
+        "tid", TrackMbid;
+        "tnum", TrackPosition;
+        "tracks", NumTracksMedium;
+        "tracksrelease", TracksRelease;
+        "video", Video;
+    }
+
+    /// Fields to search for `Series`.
+    SeriesSearchField, series {
+        "alias", Alias;
+        "comment", Comment;
+        "series", SeriesName;
+        "seriesaccent", SeriesNameAccent;
+        "sid", SeriesMbid;
+        "tag", Tag;
+        "type", SeriesType;
+    }
+
+    /// Fields to search for `Work`s.
+    WorkSearchField, work {
+        "alias", Alias;
+        "arid", ArtistMbid;
+        "artist", ArtistName;
+        "comment", Comment;
+        "iswc", Iswc;
+        "lang", Language;
+        "tag", Tag;
+        "type", WorkType;
+        "wid", WorkMbid;
+        "work", WorkName;
+        "workaccent", WorkNameAccent;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_range()
+    {
+        let range = DateRange(
+            PartialDate::new(Some(2000), None, None),
+            PartialDate::new(Some(2010), Some(4), Some(1)),
+        );
+        assert_eq!(range.to_string(), "[2000 TO 2010-04-01]".to_string());
+
+        let open = DateRange(
+            PartialDate::new(Some(2000), Some(5), None),
+            PartialDate::new(None, None, None),
+        );
+        assert_eq!(open.to_string(), "[2000-05 TO *]".to_string());
+    }
+
+    #[test]
+    fn partial_dates()
+    {
+        let date = |y, m, d| BeginDate(PartialDate::new(y, m, d)).to_string();
+        assert_eq!(date(Some(2014), None, None), "[2014 TO 2014-12-31]".to_string());
+        assert_eq!(date(Some(2014), Some(3), None), "[2014-03 TO 2014-03-31]".to_string());
+        assert_eq!(date(Some(2014), Some(3), Some(28)), "2014-03-28".to_string());
+        assert_eq!(date(None, None, None), "*".to_string());
+
+        let formed = BeginDateRange(
+            PartialDate::new(Some(2010), None, None),
+            PartialDate::new(None, None, None),
+        );
+        assert_eq!(formed.to_string(), "[2010 TO *]".to_string());
+        assert_eq!(<artist::EndDateRange as ArtistSearchField>::name(), "end");
+    }
+
+    #[test]
+    fn duration_range()
+    {
+        let range = DurationRange(Duration::from_secs(200), Duration::from_millis(300500));
+        assert_eq!(range.to_string(), "[200000 TO 300500]".to_string());
+    }
+
+    #[test]
+    fn fuzzy()
+    {
+        assert_eq!(
+            Fuzzy(ArtistName("radiohed".to_string()), 1).to_string(),
+            "(radiohed~1)".to_string()
+        );
+        assert_eq!(
+            Fuzzy(ReleaseName("ok computr!".to_string()), 2).to_string(),
+            "(ok~2 computr\\!~2)".to_string()
+        );
+        assert_eq!(
+            Fuzzy(ArtistName("radiohed".to_string()), 5).to_string(),
+            "(radiohed~2)".to_string()
+        );
+        assert_eq!(Fuzzy(ArtistName(" ".to_string()), 1).to_string(), "".to_string());
+    }
+
+    #[test]
+    fn wildcard()
+    {
+        assert_eq!(
+            Wildcard(ReleaseName("ok comp*".to_string())).to_string(),
+            "(ok comp*)".to_string()
+        );
+        assert_eq!(
+            Wildcard(ReleaseName("AC/DC: b?ck".to_string())).to_string(),
+            "(AC/DC\\: b?ck)".to_string()
+        );
+    }
+
+    #[test]
+    fn entity_field_names()
+    {
+        assert_eq!(<recording::RecordingName as RecordingSearchField>::name(), "recording");
+        assert_eq!(<recording::DurationRange as RecordingSearchField>::name(), "dur");
+        assert_eq!(<label::LabelCode as LabelSearchField>::name(), "code");
+        assert_eq!(<work::Iswc as WorkSearchField>::name(), "iswc");
+        assert_eq!(<artist::Name as ArtistSearchField>::name(), "artist");
+        assert_eq!(<event::EventType as EventSearchField>::name(), "type");
+    }
+
+    #[test]
+    fn track_count_range()
+    {
+        assert_eq!(TrackCountRange(10, 12).to_string(), "[10 TO 12]".to_string());
+    }
+}
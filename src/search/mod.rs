@@ -15,15 +15,18 @@
 
 use entities as full_entities;
 use entities::Resource;
+use entities::helper::{read_attr, read_attr_option, read_mb_vec};
 use errors::ClientError;
 use client::Client;
 
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use reqwest_mock::Url;
-use url::percent_encoding::{DEFAULT_ENCODE_SET, utf8_percent_encode};
 use xpath_reader::{FromXml, FromXmlError, XpathReader, XpathStrReader};
 
 pub mod fields;
-use self::fields::{AreaSearchField, ArtistSearchField, ReleaseGroupSearchField, ReleaseSearchField};
+use self::fields::{AreaSearchField, ArtistSearchField, LabelSearchField, RecordingSearchField,
+                   ReleaseGroupSearchField, ReleaseSearchField, WorkSearchField};
 
 pub mod search_entities;
 use self::search_entities::SearchEntity;
@@ -40,11 +43,112 @@ pub trait SearchBuilder {
     /// The full entity a search entity can be expanded into.
     type FullEntity: Resource + FromXml;
 
+    /// Perform the search, returning the page of results together with its
+    /// metadata.
+    fn search_page(self) -> Result<SearchPage<Self::Entity>, ClientError>;
+
     /// Perform the search.
-    fn search(self) -> SearchResult<Self::Entity>;
+    fn search(self) -> SearchResult<Self::Entity>
+    where
+        Self: Sized,
+    {
+        self.search_page().map(|page| page.entries)
+    }
+}
+
+/// A page of search results.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchPage<E>
+where
+    E: SearchEntity,
+{
+    /// The entries of this page.
+    pub entries: Vec<SearchEntry<E>>,
+
+    /// The total number of results of the search, not only of this page.
+    pub count: u32,
+
+    /// The offset of the first entry of this page in all results.
+    pub offset: u32,
+
+    /// The time the search server created the response.
+    ///
+    /// The search index is updated separately from the database, so this can
+    /// be used to tell how fresh the index is, see `index_age`.
+    pub created: Option<SystemTime>,
+}
+
+impl<E> SearchPage<E>
+where
+    E: SearchEntity,
+{
+    /// How long ago the search server created the response.
+    ///
+    /// If this is older than a recent edit, the edit is probably not yet
+    /// reflected in the results. Returns `None` if the server didn't provide
+    /// the time or the clocks disagree.
+    pub fn index_age(&self) -> Option<Duration>
+    {
+        self.created.and_then(|created| SystemTime::now().duration_since(created).ok())
+    }
+}
+
+/// Parses a UTC timestamp like `"2017-05-06T09:45:01.432Z"` as provided in the
+/// `created` attribute of search responses.
+fn parse_timestamp(s: &str) -> Option<SystemTime>
+{
+    let s = s.trim();
+    let s = if s.ends_with('Z') {
+        &s[..s.len() - 1]
+    } else if s.ends_with("+00:00") {
+        &s[..s.len() - 6]
+    } else {
+        return None;
+    };
+
+    let mut date_time = s.splitn(2, 'T');
+    let date: Vec<u64> = date_time
+        .next()?
+        .split('-')
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+    let time = date_time.next()?;
+    let (time, millis) = match time.find('.') {
+        Some(i) => {
+            let fraction = &time[i + 1..];
+            let millis: u64 = format!("{:0<3}", fraction).get(..3)?.parse().ok()?;
+            (&time[..i], millis)
+        }
+        None => (time, 0),
+    };
+    let time: Vec<u64> = time.split(':').map(|n| n.parse().ok()).collect::<Option<_>>()?;
+
+    if date.len() != 3 || time.len() != 3 {
+        return None;
+    }
+    let (year, month, day) = (date[0], date[1], date[2]);
+    if year < 1970 || month < 1 || month > 12 || day < 1 || day > 31 || time[0] > 23 ||
+        time[1] > 59 || time[2] > 60
+    {
+        return None;
+    }
+
+    // Days since the epoch of the civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    let secs = days * 86400 + time[0] * 3600 + time[1] * 60 + time[2];
+    Some(UNIX_EPOCH + Duration::from_secs(secs) + Duration::from_millis(millis))
 }
 
 /// One entry of the search results.
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SearchEntry<E>
 where
     E: SearchEntity,
@@ -65,7 +169,11 @@ macro_rules! define_search_builder {
       $full_entity:ty,
       $list_tag:expr ) => {
         pub struct $builder<'cl> {
-            params: Vec<(&'static str, String)>,
+            /// Groups of parameters, the parameters of a group are combined
+            /// using `OR` and the groups are combined using `AND`.
+            params: Vec<Vec<(&'static str, String)>>,
+            /// If set, a dismax query with this text is performed instead.
+            dismax: Option<String>,
             client: &'cl mut Client,
         }
 
@@ -73,43 +181,104 @@ macro_rules! define_search_builder {
             pub fn new(client: &'cl mut Client) -> Self {
                 Self {
                     params: Vec::new(),
+                    dismax: None,
                     client: client,
                 }
             }
 
+            /// Perform a simple text query using the dismax query parser of the
+            /// search server, i. e. just search for entities matching the text.
+            ///
+            /// This bypasses the fields entirely, any parameters added using `add`
+            /// or `add_any` will be ignored. The text doesn't have to be escaped.
+            pub fn dismax<S>(mut self, query_text: S) -> Self
+                where S: Into<String>
+            {
+                self.dismax = Some(query_text.into());
+                self
+            }
+
             /// Specify an additional parameter for the query.
             ///
-            /// Currently all parameters will be combined using `AND`.
+            /// All parameters added will be combined using `AND`. Fields without a
+            /// value, e. g. a `Fuzzy` field without any terms, are skipped.
             pub fn add<F>(mut self, field: F) -> Self
                 where F: $fields
             {
-                self.params.push((F::name(), field.to_string()));
+                let value = field.to_string();
+                if !value.is_empty() {
+                    self.params.push(vec![(F::name(), value)]);
+                }
+                self
+            }
+
+            /// Specify a group of parameters of which at least one has to match.
+            ///
+            /// The fields of the group are combined using `OR`, the group itself is
+            /// combined with the other parameters using `AND`. This allows searching
+            /// for entities with any of multiple values of the same field, e. g. any
+            /// of multiple tags.
+            pub fn add_any<I, F>(mut self, fields: I) -> Self
+                where I: IntoIterator<Item = F>,
+                      F: $fields
+            {
+                let group: Vec<_> = fields.into_iter()
+                    .map(|field| (F::name(), field.to_string()))
+                    .filter(|&(_, ref value)| !value.is_empty())
+                    .collect();
+                if !group.is_empty() {
+                    self.params.push(group);
+                }
                 self
             }
 
             /// Builds the full url to be used to perform the search request.
             fn build_url(&self) -> Result<Url, ClientError> {
-                let mut query_parts: Vec<String> = Vec::new();
-                for &(p_name, ref p_value) in self.params.iter() {
-                    // TODO (FIXME): Does this also encode ":" ? 
-                    let value  = utf8_percent_encode(p_value.as_ref(), DEFAULT_ENCODE_SET);
-                    query_parts.push(format!("{}:{}", p_name, value));
+                type FE = $full_entity;
+                let endpoint = self.client.endpoint();
+
+                if let Some(ref text) = self.dismax {
+                    let params = [("dismax", "true")];
+                    return query::search_url(&endpoint, FE::get_name(), text, &params);
                 }
 
-                // TODO: In the future support OR queries too.
-                let query = query_parts.join("%20AND%20");
-                type FE = $full_entity;
-                Ok(Url::parse(format!("{}?query={}", FE::base_url(), query).as_ref())?)
+                let query_parts: Vec<String> = self.params.iter().map(|group| {
+                    let group_parts: Vec<String> = group.iter()
+                        .map(|&(p_name, ref p_value)| format!("{}:{}", p_name, p_value))
+                        .collect();
+
+                    if group_parts.len() == 1 {
+                        group_parts.into_iter().next().unwrap()
+                    } else {
+                        format!("({})", group_parts.join(" OR "))
+                    }
+                }).collect();
+
+                query::search_url(&endpoint, FE::get_name(), &query_parts.join(" AND "), &[])
             }
 
             /// Parse the search result.
-            fn parse_xml(xml: &str) -> SearchResult<$entity> {
+            fn parse_xml(xml: &str) -> Result<SearchPage<$entity>, ClientError> {
                 let mut context = ::util::musicbrainz_context();
                 context.set_namespace("ext", "http://musicbrainz.org/ns/ext#-2.0");
 
                 let reader = XpathStrReader::new(xml, &context)?;
                 ::client::check_response_error(&reader)?;
-                Ok(reader.read_vec("//mb:metadata")?)
+
+                let list = format!("//mb:metadata/mb:{}", $list_tag);
+                let entries: Vec<SearchEntry<$entity>> =
+                    read_mb_vec(&reader, format!("{}/*", list).as_str())?;
+                let count = read_attr_option(&reader, &list, "count")?;
+                let offset = read_attr_option(&reader, &list, "offset")?;
+                let created: Option<String> =
+                    read_attr_option(&reader, "//mb:metadata", "created")?;
+
+                Ok(SearchPage {
+                    count: count.unwrap_or(entries.len() as u32),
+                    offset: offset.unwrap_or(0),
+                    created: created.and_then(|c| parse_timestamp(&c)),
+                    entries: entries,
+                })
             }
         }
 
@@ -117,12 +286,16 @@ macro_rules! define_search_builder {
             type Entity = $entity;
             type FullEntity = $full_entity;
 
-            fn search(self) -> SearchResult<Self::Entity> {
+            fn search_page(self) -> Result<SearchPage<Self::Entity>, ClientError> {
+                type FE = $full_entity;
                 let url = self.build_url()?;
 
                 // Perform the request.
-                let response_body = self.client.get_body(url)?;
-                Self::parse_xml(response_body.as_str())
+                let url_str = url.as_str().to_string();
+                let result = self.client.get_body(url).and_then(|response_body| {
+                    Self::parse_xml(response_body.as_str())
+                });
+                result.map_err(|e| e.with_request(url_str, Some(FE::get_name())))
             }
         }
 
@@ -130,9 +303,10 @@ macro_rules! define_search_builder {
             fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
                 where R: XpathReader<'d>
             {
+                // The reader points to the element of the entry in the list.
                 Ok(Self {
-                    entity: reader.read(format!(".//mb:{}", $list_tag).as_str())?,
-                    score: reader.read(format!(".//mb:{}/*/@ext:score", $list_tag).as_str())?,
+                    entity: reader.read(".")?,
+                    score: read_attr(reader, ".", "ext:score")?,
                 })
             }
         }
@@ -155,7 +329,22 @@ define_search_builder!(
     "artist-list"
 );
 
-/* TODO
+define_search_builder!(
+    LabelSearchBuilder,
+    LabelSearchField,
+    search_entities::Label,
+    full_entities::Label,
+    "label-list"
+);
+
+define_search_builder!(
+    RecordingSearchBuilder,
+    RecordingSearchField,
+    search_entities::Recording,
+    full_entities::Recording,
+    "recording-list"
+);
+
 define_search_builder!(
     ReleaseSearchBuilder,
     ReleaseSearchField,
@@ -163,7 +352,6 @@ define_search_builder!(
     full_entities::Release,
     "release-list"
 );
-*/
 
 define_search_builder!(
     ReleaseGroupSearchBuilder,
@@ -173,9 +361,280 @@ define_search_builder!(
     "release-group-list"
 );
 
+define_search_builder!(
+    WorkSearchBuilder,
+    WorkSearchField,
+    search_entities::Work,
+    full_entities::Work,
+    "work-list"
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use client::{ClientConfig, ClientWaits};
+
+    fn test_client() -> Client
+    {
+        Client::new(ClientConfig {
+            user_agent: "MusicBrainz-Rust/Testing".to_string(),
+            max_retries: 5,
+            waits: ClientWaits::default(),
+            base_url: None,
+            accept_language: None,
+            max_response_size: None,
+        })
+    }
+
+    #[test]
+    fn build_url_or_group()
+    {
+        use self::fields::release_group::{ReleaseGroupName, Tag};
+
+        let mut client = test_client();
+        let url = ReleaseGroupSearchBuilder::new(&mut client)
+            .add_any(vec![Tag("rock".to_string()), Tag("indie".to_string())])
+            .add(ReleaseGroupName("Mixtape".to_string()))
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://musicbrainz.org/ws/2/release-group/?query=\
+             %28tag%3Arock+OR+tag%3Aindie%29+AND+releasegroup%3AMixtape"
+        );
+    }
+
+    #[test]
+    fn build_url_skip_empty_fields()
+    {
+        use self::fields::artist::{ArtistName, Fuzzy};
+
+        let mut client = test_client();
+        let url = ArtistSearchBuilder::new(&mut client)
+            .add(Fuzzy(ArtistName("".to_string()), 1))
+            .add_any(vec![Fuzzy(ArtistName(" ".to_string()), 1)])
+            .add(ArtistName("Boris".to_string()))
+            .build_url()
+            .unwrap();
+
+        assert_eq!(url.as_str(), "https://musicbrainz.org/ws/2/artist/?query=artist%3ABoris");
+    }
+
+    #[test]
+    fn build_url_dismax()
+    {
+        use self::fields::artist::ArtistName;
+
+        let mut client = test_client();
+        let url = ArtistSearchBuilder::new(&mut client)
+            .add(ArtistName("ignored".to_string()))
+            .dismax("Simon & Garfunkel")
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://musicbrainz.org/ws/2/artist/?query=Simon+%26+Garfunkel&dismax=true"
+        );
+    }
+
+    #[test]
+    fn build_url_custom_base_url()
+    {
+        use self::fields::artist::ArtistName;
+
+        let mut client = Client::new(ClientConfig {
+            user_agent: "MusicBrainz-Rust/Testing".to_string(),
+            max_retries: 5,
+            waits: ClientWaits::default(),
+            base_url: Some("http://localhost:5000/ws/2/".to_string()),
+            accept_language: None,
+            max_response_size: None,
+        });
+        let url = ArtistSearchBuilder::new(&mut client)
+            .add(ArtistName("Boris".to_string()))
+            .build_url()
+            .unwrap();
+
+        assert_eq!(url.as_str(), "http://localhost:5000/ws/2/artist/?query=artist%3ABoris");
+    }
+
+    #[test]
+    fn build_url_single_value_group()
+    {
+        use self::fields::release_group::Tag;
+
+        let mut client = test_client();
+        let url = ReleaseGroupSearchBuilder::new(&mut client)
+            .add_any(vec![Tag("rock".to_string())])
+            .add_any(Vec::<Tag>::new())
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://musicbrainz.org/ws/2/release-group/?query=tag%3Arock"
+        );
+    }
+
+    #[test]
+    fn build_url_non_ascii()
+    {
+        use self::fields::artist::ArtistName;
+
+        let mut client = test_client();
+        let url = ArtistSearchBuilder::new(&mut client)
+            .add(ArtistName("少女時代".to_string()))
+            .build_url()
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://musicbrainz.org/ws/2/artist/?query=\
+             artist%3A%E5%B0%91%E5%A5%B3%E6%99%82%E4%BB%A3"
+        );
+
+        let url = ArtistSearchBuilder::new(&mut client)
+            .add(ArtistName("Björk".to_string()))
+            .build_url()
+            .unwrap();
+        assert_eq!(url.as_str(), "https://musicbrainz.org/ws/2/artist/?query=artist%3ABj%C3%B6rk");
+    }
+
+    #[test]
+    fn build_url_reserved_characters()
+    {
+        use self::fields::artist::ArtistName;
+
+        let mut client = test_client();
+        let url = ArtistSearchBuilder::new(&mut client)
+            .add(ArtistName("AC/DC & M+M=#1?".to_string()))
+            .build_url()
+            .unwrap();
+
+        // Nothing is encoded twice and the query isn't cut off at `&` or `#`.
+        let pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        assert_eq!(
+            pairs,
+            vec![("query".to_string(), "artist:AC/DC & M+M=#1?".to_string())]
+        );
+        assert_eq!(url.fragment(), None);
+    }
+
+    #[test]
+    fn build_url_release()
+    {
+        use self::fields::release::{Barcode, CatalogNumber, LabelName};
+
+        let mut client = test_client();
+        let url = ReleaseSearchBuilder::new(&mut client)
+            .add(Barcode("4988006751583".to_string()))
+            .add(CatalogNumber("\"TOCT-24051\"".to_string()))
+            .add(LabelName("EMI".to_string()))
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://musicbrainz.org/ws/2/release/?query=\
+             barcode%3A4988006751583+AND+catno%3A%22TOCT-24051%22+AND+label%3AEMI"
+        );
+    }
+
+    #[test]
+    fn build_url_recording()
+    {
+        use self::fields::recording::{ArtistName, Isrc, RecordingName};
+
+        let mut client = test_client();
+        let url = RecordingSearchBuilder::new(&mut client)
+            .add_any(vec![Isrc("GBAYE9700117".to_string())])
+            .add(RecordingName("Paranoid Android".to_string()))
+            .add(ArtistName("Radiohead".to_string()))
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://musicbrainz.org/ws/2/recording/?query=\
+             isrc%3AGBAYE9700117+AND+recording%3AParanoid+Android+AND+artistname%3ARadiohead"
+        );
+    }
+
+    #[test]
+    fn build_url_label()
+    {
+        use self::fields::label::{LabelCode, LabelName};
+
+        let mut client = test_client();
+        let url = LabelSearchBuilder::new(&mut client)
+            .add(LabelCode(542))
+            .add(LabelName("EMI".to_string()))
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://musicbrainz.org/ws/2/label/?query=code%3A542+AND+label%3AEMI"
+        );
+    }
+
+    #[test]
+    fn build_url_work()
+    {
+        use self::fields::work::{ArtistName, WorkName};
+
+        let mut client = test_client();
+        let url = WorkSearchBuilder::new(&mut client)
+            .add(WorkName("Paranoid Android".to_string()))
+            .add(ArtistName("Radiohead".to_string()))
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://musicbrainz.org/ws/2/work/?query=\
+             work%3AParanoid+Android+AND+artist%3ARadiohead"
+        );
+    }
+
+    #[test]
+    fn parse_created_timestamp()
+    {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(UNIX_EPOCH));
+        assert_eq!(
+            parse_timestamp("2017-05-06T09:45:01.432Z"),
+            Some(UNIX_EPOCH + Duration::from_millis(1494063901432))
+        );
+        assert_eq!(
+            parse_timestamp("2000-02-29T12:00:00.5+00:00"),
+            Some(UNIX_EPOCH + Duration::from_millis(951825600500))
+        );
+        assert_eq!(parse_timestamp("2017-05-06T09:45:01"), None);
+        assert_eq!(parse_timestamp("2017-13-06T09:45:01Z"), None);
+        assert_eq!(parse_timestamp(""), None);
+    }
+
+    #[test]
+    fn deserialize_multiple_entries()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><metadata created="2017-05-06T09:45:01.432Z" xmlns="http://musicbrainz.org/ns/mmd-2.0#" xmlns:ext="http://musicbrainz.org/ns/ext#-2.0"><area-list count="12" offset="0"><area id="8dc97297-ac95-4d33-82bc-e07fab26fce1" type="City" ext:score="100"><name>Tokyo</name><sort-name>Tokyo</sort-name></area><area id="1b0bd2a3-3b3c-43ad-94c8-2df4b1f0ba7f" type="Subdivision" ext:score="92"><name>Tokyo</name><sort-name>Tokyo</sort-name><disambiguation>prefecture</disambiguation></area></area-list></metadata>"#;
+        let page: SearchPage<search_entities::Area> = AreaSearchBuilder::parse_xml(xml).unwrap();
+        assert_eq!(page.count, 12);
+        assert_eq!(page.entries.len(), 2);
+
+        assert_eq!(page.entries[0].score, 100);
+        assert_eq!(page.entries[0].entity.area_type, full_entities::AreaType::City);
+        assert_eq!(page.entries[0].entity.disambiguation, None);
+
+        assert_eq!(page.entries[1].score, 92);
+        assert_eq!(
+            page.entries[1].entity.mbid,
+            "1b0bd2a3-3b3c-43ad-94c8-2df4b1f0ba7f".parse().unwrap()
+        );
+        assert_eq!(page.entries[1].entity.area_type, full_entities::AreaType::Subdivision);
+        assert_eq!(page.entries[1].entity.disambiguation, Some("prefecture".to_string()));
+    }
 
     #[test]
     fn deserialize_releasegroup()
@@ -183,9 +642,14 @@ mod tests {
         // url: https://musicbrainz.org/ws/2/release-group/?query=releasegroup:
         // %E9%9C%8A%E9%AD%82%E6%B6%88%E6%BB%85
         let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><metadata created="2017-05-06T09:45:01.432Z" xmlns="http://musicbrainz.org/ns/mmd-2.0#" xmlns:ext="http://musicbrainz.org/ns/ext#-2.0"><release-group-list count="1" offset="0"><release-group id="739de9cd-7e81-4bb0-9fdb-0feb7ea709c7" type="Single" ext:score="100"><title>霊魂消滅</title><primary-type>Single</primary-type><artist-credit><name-credit><artist id="90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e"><name>NECRONOMIDOL</name><sort-name>NECRONOMIDOL</sort-name></artist></name-credit></artist-credit><release-list count="1"><release id="d3d2a860-0093-461d-8d95-b77939c2e944"><title>霊魂消滅</title><status>Official</status></release></release-list></release-group></release-group-list></metadata>"#;
-        let res: Vec<SearchEntry<search_entities::ReleaseGroup>> =
+        let page: SearchPage<search_entities::ReleaseGroup> =
             ReleaseGroupSearchBuilder::parse_xml(xml).unwrap();
+        assert_eq!(page.count, 1);
+        assert_eq!(page.offset, 0);
+        assert_eq!(page.created, parse_timestamp("2017-05-06T09:45:01.432Z"));
+        assert!(page.index_age().unwrap() > Duration::from_secs(86400));
 
+        let res = page.entries;
         assert_eq!(res.len(), 1);
         let ref rg = res[0];
 
@@ -196,4 +660,84 @@ mod tests {
         );
         assert_eq!(rg.entity.title, "霊魂消滅".to_string());
     }
+
+    #[test]
+    fn deserialize_release()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><metadata created="2017-05-06T09:45:01.432Z" xmlns="http://musicbrainz.org/ns/mmd-2.0#" xmlns:ext="http://musicbrainz.org/ns/ext#-2.0"><release-list count="1" offset="0"><release id="0b6b4ba0-d36f-47bd-b4ea-6a5b91842d29" ext:score="100"><title>OK Computer</title><status>Official</status><text-representation><language>eng</language><script>Latn</script></text-representation><artist-credit><name-credit><artist id="a74b1b7f-71a5-4011-9441-d0b5e4122711"><name>Radiohead</name><sort-name>Radiohead</sort-name></artist></name-credit></artist-credit><release-group id="b1392450-e666-3926-a536-22c65f834433" type="Album"><title>OK Computer</title></release-group><date>1997-05-21</date><country>JP</country><barcode>4988006751583</barcode><label-info-list><label-info><catalog-number>TOCP-50201</catalog-number><label id="c029628b-6633-439e-bcee-ed02e8a338f7"><name>EMI</name></label></label-info></label-info-list></release></release-list></metadata>"#;
+        let page: SearchPage<search_entities::Release> =
+            ReleaseSearchBuilder::parse_xml(xml).unwrap();
+        assert_eq!(page.count, 1);
+
+        let ref release = page.entries[0].entity;
+        assert_eq!(release.title, "OK Computer".to_string());
+        assert_eq!(release.status, Some(full_entities::ReleaseStatus::Official));
+        assert_eq!(release.language, Some("eng".to_string()));
+        assert_eq!(release.artists[0].name, "Radiohead".to_string());
+        assert_eq!(
+            release.date,
+            Some(full_entities::PartialDate::new(Some(1997), Some(5), Some(21)))
+        );
+        assert_eq!(release.country, Some("JP".to_string()));
+        assert_eq!(release.barcode, Some("4988006751583".to_string()));
+        assert_eq!(release.catalog_numbers, vec!["TOCP-50201".to_string()]);
+    }
+
+    #[test]
+    fn deserialize_recording()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><metadata created="2017-05-06T09:45:01.432Z" xmlns="http://musicbrainz.org/ns/mmd-2.0#" xmlns:ext="http://musicbrainz.org/ns/ext#-2.0"><recording-list count="1" offset="0"><recording id="8cc0e1fe-ac75-4cf1-9da9-9ad1b4b9dd21" ext:score="100"><title>Paranoid Android</title><length>386800</length><artist-credit><name-credit><artist id="a74b1b7f-71a5-4011-9441-d0b5e4122711"><name>Radiohead</name><sort-name>Radiohead</sort-name></artist></name-credit></artist-credit><release-list><release id="0b6b4ba0-d36f-47bd-b4ea-6a5b91842d29"><title>OK Computer</title><status>Official</status></release></release-list><isrc-list><isrc id="GBAYE9700117"/></isrc-list></recording></recording-list></metadata>"#;
+        let page: SearchPage<search_entities::Recording> =
+            RecordingSearchBuilder::parse_xml(xml).unwrap();
+        assert_eq!(page.count, 1);
+
+        let ref recording = page.entries[0].entity;
+        assert_eq!(recording.title, "Paranoid Android".to_string());
+        assert_eq!(recording.length, Some(Duration::from_millis(386800)));
+        assert_eq!(recording.artists[0].name, "Radiohead".to_string());
+        assert_eq!(recording.isrcs[0].as_str(), "GBAYE9700117");
+        assert_eq!(recording.releases[0].title, "OK Computer".to_string());
+    }
+
+    #[test]
+    fn deserialize_label()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><metadata created="2017-05-06T09:45:01.432Z" xmlns="http://musicbrainz.org/ns/mmd-2.0#" xmlns:ext="http://musicbrainz.org/ns/ext#-2.0"><label-list count="1" offset="0"><label id="c029628b-6633-439e-bcee-ed02e8a338f7" type="Original Production" ext:score="100"><name>EMI</name><sort-name>EMI</sort-name><label-code>542</label-code><disambiguation>EMI Records, since 1972</disambiguation><country>GB</country><area id="8a754a16-0027-3a29-b6d7-2b40ea0481ed"><name>United Kingdom</name><sort-name>United Kingdom</sort-name></area><alias-list><alias sort-name="EMI Records">EMI Records</alias></alias-list></label></label-list></metadata>"#;
+        let page: SearchPage<search_entities::Label> =
+            LabelSearchBuilder::parse_xml(xml).unwrap();
+        assert_eq!(page.count, 1);
+        assert_eq!(page.entries[0].score, 100);
+
+        let ref label = page.entries[0].entity;
+        assert_eq!(label.name, "EMI".to_string());
+        assert_eq!(label.sort_name, "EMI".to_string());
+        assert_eq!(label.label_type, Some(full_entities::LabelType::ProductionOriginal));
+        assert_eq!(label.label_code, Some("542".to_string()));
+        assert_eq!(label.country, Some("GB".to_string()));
+        assert_eq!(label.disambiguation, Some("EMI Records, since 1972".to_string()));
+    }
+
+    #[test]
+    fn deserialize_work()
+    {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><metadata created="2017-05-06T09:45:01.432Z" xmlns="http://musicbrainz.org/ns/mmd-2.0#" xmlns:ext="http://musicbrainz.org/ns/ext#-2.0"><work-list count="1" offset="0"><work id="a4f0b5fb-8e05-3b4c-8a5d-a8ba1f8e3ba4" type="Song" ext:score="100"><title>Paranoid Android</title><language>eng</language><iswc-list><iswc>T-010.168.394-4</iswc></iswc-list><relation-list target-type="artist"><relation type="composer"><artist id="a74b1b7f-71a5-4011-9441-d0b5e4122711"><name>Radiohead</name><sort-name>Radiohead</sort-name></artist></relation></relation-list></work></work-list></metadata>"#;
+        let page: SearchPage<search_entities::Work> =
+            WorkSearchBuilder::parse_xml(xml).unwrap();
+        assert_eq!(page.count, 1);
+
+        let ref work = page.entries[0].entity;
+        assert_eq!(work.title, "Paranoid Android".to_string());
+        assert_eq!(work.work_type, Some("Song".to_string()));
+        assert_eq!(work.languages, vec![full_entities::Language::from_639_3("eng").unwrap()]);
+        assert!(!work.instrumental);
+        assert_eq!(work.iswcs, vec!["T-010.168.394-4".to_string()]);
+        assert_eq!(work.disambiguation, None);
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><metadata created="2017-05-06T09:45:01.432Z" xmlns="http://musicbrainz.org/ns/mmd-2.0#" xmlns:ext="http://musicbrainz.org/ns/ext#-2.0"><work-list count="1" offset="0"><work id="b1df2cf3-69a9-3bc0-be44-f71e79b27a22" ext:score="100"><title>Interlude</title><language>zxx</language><language-list><language>zxx</language></language-list></work></work-list></metadata>"#;
+        let page: SearchPage<search_entities::Work> =
+            WorkSearchBuilder::parse_xml(xml).unwrap();
+        let ref work = page.entries[0].entity;
+        assert!(work.languages.is_empty());
+        assert!(work.instrumental);
+    }
 }
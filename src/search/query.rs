@@ -4,32 +4,46 @@
 //! through the facilities provided by `Client`.
 
 use super::*;
+use endpoint::Endpoint;
 use regex::Regex;
-use url::percent_encoding::{DEFAULT_ENCODE_SET, utf8_percent_encode};
 
-/// Escape all lucene special characters and then escape it so it can be used
-/// for a url string.
+/// Builds the url of a search for the Lucene query at the url of the entity
+/// type, with further parameters like `("dismax", "true")`.
 ///
-/// This is to be used for attribute values, like for example a release name.
-pub(crate) fn escape_full(text: &str) -> String
+/// The parameters are encoded using the url crate, so the query is passed
+/// as is: non-ASCII characters are encoded as UTF-8 and characters with a
+/// meaning in urls, like `&`, `=`, `+` and `#`, are percent encoded.
+pub(crate) fn search_url(
+    endpoint: &Endpoint,
+    entity: &str,
+    query: &str,
+    params: &[(&str, &str)],
+) -> Result<Url, ClientError>
 {
-    // Replace all special lucene syntax elements.
-    let re = Regex::new(r#"([+\-!\(\)\{\}\[\]\^"~\*\?:\\]|[&\|]{2})"#).unwrap();
-    let sanitized = re.replace_all(text, "\\$0");
-
-    // Now escape the result so it can be used in the query.
-    let s = escape_query(&*sanitized);
+    let mut url = Url::parse(&endpoint.entity_url(entity))?;
+    {
+        let mut pairs = url.query_pairs_mut();
+        pairs.append_pair("query", query);
+        for &(name, value) in params {
+            pairs.append_pair(name, value);
+        }
+    }
+    Ok(url)
+}
 
-    // Percent encode = and & which haven't been touched by escape_query.
-    let s = s.replace("&", "%26");
-    let s = s.replace("=", "%3D");
-    s
+/// Escape all lucene special characters, so the text will be matched
+/// literally.
+pub(crate) fn escape_lucene(text: &str) -> String
+{
+    let re = Regex::new(r#"([+\-!\(\)\{\}\[\]\^"~\*\?:\\]|[&\|]{2})"#).unwrap();
+    re.replace_all(text, "\\$0").into_owned()
 }
 
-/// actually it might be a good idea to not use this anywhere (TODO)
-fn escape_query(text: &str) -> String
+/// Escape all lucene special characters except for the wildcards `*` and `?`.
+pub(crate) fn escape_lucene_keep_wildcards(text: &str) -> String
 {
-    utf8_percent_encode(text, DEFAULT_ENCODE_SET).to_string()
+    let re = Regex::new(r#"([+\-!\(\)\{\}\[\]\^"~:\\]|[&\|]{2})"#).unwrap();
+    re.replace_all(text, "\\$0").into_owned()
 }
 
 pub trait QueryExpression: Sized {
@@ -104,44 +118,54 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_escape_full()
+    fn test_escape_lucene()
     {
         // lucene syntax elements
-        assert_eq!(escape_full("+"), escape_query(r"\+"));
-        assert_eq!(escape_full("-"), escape_query(r"\-"));
-        assert_eq!(escape_full("&&"), "\\%26%26".to_string());
-        assert_eq!(escape_full("||"), escape_query(r"\||"));
-        assert_eq!(escape_full("!"), escape_query(r"\!"));
-        assert_eq!(escape_full("("), escape_query(r"\("));
-        assert_eq!(escape_full(")"), escape_query(r"\)"));
-        assert_eq!(escape_full("{"), escape_query(r"\{"));
-        assert_eq!(escape_full("}"), escape_query(r"\}"));
-        assert_eq!(escape_full("["), escape_query(r"\["));
-        assert_eq!(escape_full("]"), escape_query(r"\]"));
-        assert_eq!(escape_full("^"), escape_query(r"\^"));
-        assert_eq!(escape_full("\""), escape_query("\\\""));
-        assert_eq!(escape_full("~"), escape_query(r"\~"));
-        assert_eq!(escape_full("*"), escape_query(r"\*"));
-        assert_eq!(escape_full("?"), escape_query(r"\?"));
-        assert_eq!(escape_full(":"), escape_query(r"\:"));
-        assert_eq!(escape_full(r"\"), escape_query(r"\\"));
-
-        // & and = are not to be touched by escape query but we have to escape them at
-        // this point
-        // too because it would mess up the query component.
-        assert_eq!(escape_full("&"), "%26".to_string());
-        assert_eq!(escape_full("="), "%3D".to_string());
-
-        // sanity check that the whitespace in the regex is actually ignored
-        assert_eq!(escape_full(" "), escape_query(" "));
-        assert_eq!(escape_full("  "), escape_query("  "));
+        assert_eq!(escape_lucene("+"), r"\+");
+        assert_eq!(escape_lucene("-"), r"\-");
+        assert_eq!(escape_lucene("&&"), r"\&&");
+        assert_eq!(escape_lucene("||"), r"\||");
+        assert_eq!(escape_lucene("!"), r"\!");
+        assert_eq!(escape_lucene("("), r"\(");
+        assert_eq!(escape_lucene(")"), r"\)");
+        assert_eq!(escape_lucene("{"), r"\{");
+        assert_eq!(escape_lucene("}"), r"\}");
+        assert_eq!(escape_lucene("["), r"\[");
+        assert_eq!(escape_lucene("]"), r"\]");
+        assert_eq!(escape_lucene("^"), r"\^");
+        assert_eq!(escape_lucene("\""), "\\\"");
+        assert_eq!(escape_lucene("~"), r"\~");
+        assert_eq!(escape_lucene("*"), r"\*");
+        assert_eq!(escape_lucene("?"), r"\?");
+        assert_eq!(escape_lucene(":"), r"\:");
+        assert_eq!(escape_lucene(r"\"), r"\\");
+
+        // single & and | as well as whitespace aren't lucene syntax
+        assert_eq!(escape_lucene("&"), "&");
+        assert_eq!(escape_lucene("|"), "|");
+        assert_eq!(escape_lucene("  "), "  ");
     }
 
     #[test]
-    fn test_escape_query()
+    fn test_search_url()
     {
-        // these are all legal in query component
-        let legal = r#"/:@-._~!$&'()*+,;="#;
-        assert_eq!(escape_query(legal), legal.to_string());
+        let endpoint = Endpoint::default();
+        let url = search_url(&endpoint, "artist", "artist:Simon & Garfunkel", &[]).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://musicbrainz.org/ws/2/artist/?query=artist%3ASimon+%26+Garfunkel"
+        );
+
+        // The decoded parameters are exactly the passed ones.
+        let query = r#"release:"1+1=2" AND tag:#1 AND comment:a/b?c"#;
+        let url = search_url(&endpoint, "release", query, &[("dismax", "true")]).unwrap();
+        let pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("query".to_string(), query.to_string()),
+                ("dismax".to_string(), "true".to_string()),
+            ]
+        );
     }
 }
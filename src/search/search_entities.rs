@@ -6,7 +6,10 @@
 
 use super::{Client, ClientError, full_entities};
 use self::full_entities::refs::*;
-use self::full_entities::{Mbid, Resource};
+use self::full_entities::{Isrc, Language, Mbid, PartialDate, Resource};
+use self::full_entities::helper::{read_attr, read_attr_option, read_lyrics_languages,
+                                  read_mb_duration, read_mb_vec, PSEUDO_RELEASE};
+use std::time::Duration;
 use xpath_reader::FromXmlError;
 use xpath_reader::reader::{FromXml, FromXmlElement, XpathReader};
 
@@ -41,14 +44,70 @@ impl SearchEntity for Artist {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Label {
+    pub mbid: Mbid,
+    pub name: String,
+    pub sort_name: String,
+    pub label_type: Option<full_entities::LabelType>,
+
+    /// The label code without the "LC" prefix, e. g. `"542"`.
+    pub label_code: Option<String>,
+
+    pub country: Option<String>,
+    pub disambiguation: Option<String>,
+}
+
+impl SearchEntity for Label {
+    type FullEntity = full_entities::Label;
+
+    fn fetch_full(&self, client: &mut Client) -> Result<Self::FullEntity, ClientError>
+    {
+        client.get_by_mbid(&self.mbid)
+    }
+}
+
+impl FromXmlElement for Label {}
+impl FromXml for Label {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        // The area and aliases of the label have names as well, so only the
+        // direct children are read.
+        Ok(Label {
+            mbid: read_attr(reader, ".", "id")?,
+            name: reader.read("./mb:name/text()")?,
+            sort_name: reader.read("./mb:sort-name/text()")?,
+            label_type: read_attr_option(reader, ".", "type")?,
+            label_code: reader.read_option("./mb:label-code/text()")?,
+            country: reader.read_option("./mb:country/text()")?,
+            disambiguation: reader.read_option("./mb:disambiguation/text()")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Release {
     pub mbid: Mbid,
     pub title: String,
-    pub status: full_entities::ReleaseStatus,
+    pub status: Option<full_entities::ReleaseStatus>,
     pub language: Option<String>,
     pub script: Option<String>,
     pub artists: Vec<ArtistRef>,
     // release group refs (TODO)
+
+    /// The date of the earliest release event.
+    pub date: Option<PartialDate>,
+
+    /// The country of the earliest release event.
+    pub country: Option<String>,
+
+    /// The barcode of the release, empty if it has none.
+    pub barcode: Option<String>,
+
+    /// The catalog numbers of the release, in the order of its labels.
+    pub catalog_numbers: Vec<String>,
 }
 
 impl SearchEntity for Release {
@@ -60,11 +119,51 @@ impl SearchEntity for Release {
     }
 }
 
+impl FromXmlElement for Release {}
+impl FromXml for Release {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        // The release group of the release is contained as well, so only the
+        // direct children are read.
+        Ok(Release {
+            mbid: read_attr(reader, ".", "id")?,
+            title: reader.read("./mb:title/text()")?,
+            status: reader.read_option("./mb:status/text()")?,
+            language: reader.read_option("./mb:text-representation/mb:language/text()")?,
+            script: reader.read_option("./mb:text-representation/mb:script/text()")?,
+            artists: read_mb_vec(reader, "./mb:artist-credit/mb:name-credit")?,
+            date: reader.read_option("./mb:date/text()")?,
+            country: reader.read_option("./mb:country/text()")?,
+            barcode: reader.read_option("./mb:barcode/text()")?,
+            catalog_numbers: reader.read_vec(
+                "./mb:label-info-list/mb:label-info/mb:catalog-number/text()",
+            )?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ReleaseGroup {
     pub mbid: Mbid,
     pub title: String,
     pub artists: Vec<ArtistRef>,
+
+    /// The releases of this release group, without the pseudo-releases.
     pub releases: Vec<ReleaseRef>,
+
+    /// The pseudo-releases of this release group.
+    pub pseudo_releases: Vec<ReleaseRef>,
+}
+
+impl ReleaseGroup {
+    /// All releases of this release group, the pseudo-releases following the
+    /// actual releases.
+    pub fn all_releases(&self) -> Vec<&ReleaseRef>
+    {
+        self.releases.iter().chain(self.pseudo_releases.iter()).collect()
+    }
 }
 
 impl SearchEntity for ReleaseGroup {
@@ -83,10 +182,109 @@ impl FromXml for ReleaseGroup {
         R: XpathReader<'d>,
     {
         Ok(ReleaseGroup {
-            mbid: reader.read(".//@id")?,
+            mbid: read_attr(reader, ".", "id")?,
             title: reader.read(".//mb:title")?,
-            artists: reader.read_vec(".//mb:artist-credit/mb:name-credit/mb:artist")?,
-            releases: reader.read_vec(".//mb:release-list/mb:release")?,
+            artists: read_mb_vec(reader, ".//mb:artist-credit/mb:name-credit")?,
+            releases: read_mb_vec(
+                reader,
+                &format!(".//mb:release-list/mb:release[not({})]", PSEUDO_RELEASE),
+            )?,
+            pseudo_releases: read_mb_vec(
+                reader,
+                &format!(".//mb:release-list/mb:release[{}]", PSEUDO_RELEASE),
+            )?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Recording {
+    pub mbid: Mbid,
+    pub title: String,
+
+    /// The length of the recording, if known.
+    pub length: Option<Duration>,
+
+    pub disambiguation: Option<String>,
+    pub artists: Vec<ArtistRef>,
+
+    /// The ISRCs assigned to the recording.
+    pub isrcs: Vec<Isrc>,
+
+    /// The releases the recording appears on.
+    pub releases: Vec<ReleaseRef>,
+}
+
+impl SearchEntity for Recording {
+    type FullEntity = full_entities::Recording;
+
+    fn fetch_full(&self, client: &mut Client) -> Result<Self::FullEntity, ClientError>
+    {
+        client.get_by_mbid(&self.mbid)
+    }
+}
+
+impl FromXmlElement for Recording {}
+impl FromXml for Recording {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        // The releases contain titles and artist credits as well, so only the
+        // direct children are read.
+        Ok(Recording {
+            mbid: read_attr(reader, ".", "id")?,
+            title: reader.read("./mb:title/text()")?,
+            length: read_mb_duration(reader, "./mb:length/text()")?,
+            disambiguation: reader.read_option("./mb:disambiguation/text()")?,
+            artists: read_mb_vec(reader, "./mb:artist-credit/mb:name-credit")?,
+            isrcs: read_mb_vec(reader, "./mb:isrc-list/mb:isrc/@id")?,
+            releases: read_mb_vec(reader, "./mb:release-list/mb:release")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Work {
+    pub mbid: Mbid,
+    pub title: String,
+    pub work_type: Option<String>,
+
+    /// The languages of the lyrics, the main language first.
+    pub languages: Vec<Language>,
+
+    /// Whether the work is marked as having no lyrics.
+    pub instrumental: bool,
+
+    pub iswcs: Vec<String>,
+    pub disambiguation: Option<String>,
+}
+
+impl SearchEntity for Work {
+    type FullEntity = full_entities::Work;
+
+    fn fetch_full(&self, client: &mut Client) -> Result<Self::FullEntity, ClientError>
+    {
+        client.get_by_mbid(&self.mbid)
+    }
+}
+
+impl FromXmlElement for Work {}
+impl FromXml for Work {
+    fn from_xml<'d, R>(reader: &'d R) -> Result<Self, FromXmlError>
+    where
+        R: XpathReader<'d>,
+    {
+        let (languages, instrumental) = read_lyrics_languages(reader, ".")?;
+
+        Ok(Work {
+            mbid: read_attr(reader, ".", "id")?,
+            title: reader.read("./mb:title/text()")?,
+            work_type: read_attr_option(reader, ".", "type")?,
+            languages: languages,
+            instrumental: instrumental,
+            iswcs: reader.read_vec("./mb:iswc-list/mb:iswc/text()")?,
+            disambiguation: reader.read_option("./mb:disambiguation/text()")?,
         })
     }
 }
@@ -0,0 +1,377 @@
+//! Helpers for tagging local files with the data of a release, e. g. for an
+//! auto-tagging flow which looks up a release and then assigns the files of
+//! an album directory to its tracks.
+
+use std::time::Duration;
+
+use entities::Release;
+
+/// A local audio file, e. g. one of the files in an album directory.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LocalFile {
+    /// The file name, optionally with the directories containing it.
+    pub name: String,
+
+    /// The length of the audio, if it is known.
+    pub length: Option<Duration>,
+}
+
+/// A track number found in the name of a local file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TrackNumber {
+    /// The number of the disc, if it was found.
+    pub disc: Option<u16>,
+
+    /// The number of the track on the disc. Without a disc this is either the
+    /// number on its medium or a continuous number over all mediums.
+    pub track: u32,
+}
+
+impl TrackNumber {
+    /// Parse the track number from the name of a file, optionally with the
+    /// directories containing it.
+    ///
+    /// Besides a plain number at the beginning of the file name (`"05 -
+    /// Title.flac"`) this recognizes numbers prefixed with the disc (`"2-05
+    /// Title.flac"`, `"2.05 Title.flac"`) and discs stored in separate
+    /// directories (`"CD2/05 - Title.flac"`, `"Disc 2/05 - Title.flac"`).
+    pub fn parse(name: &str) -> Option<TrackNumber>
+    {
+        let mut components = name.rsplit(|c: char| c == '/' || c == '\\');
+        let stem = file_stem(components.next().unwrap_or(name)).trim();
+        let (first, rest) = match split_number(stem) {
+            Some(split) => split,
+            None => return None,
+        };
+
+        if first <= 99 && (rest.starts_with('-') || rest.starts_with('.')) {
+            if let Some((second, _)) = split_number(&rest[1..]) {
+                return Some(TrackNumber {
+                    disc: Some(first as u16),
+                    track: second,
+                });
+            }
+        }
+
+        Some(TrackNumber {
+            disc: components.next().and_then(disc_directory),
+            track: first,
+        })
+    }
+
+    /// The positions of the medium and the track on it of the track on
+    /// `release` with this number.
+    ///
+    /// Numbers without a disc are considered to be continuous, which for
+    /// releases with a single medium is the same as the number on the medium.
+    pub fn resolve(&self, release: &Release) -> Option<(u16, u16)>
+    {
+        match self.disc {
+            Some(disc) if self.track <= u16::max_value() as u32 => release
+                .continuous_track_number(disc, self.track as u16)
+                .map(|_| (disc, self.track as u16)),
+            Some(_) => None,
+            None => release.track_for_continuous_number(self.track),
+        }
+    }
+
+    /// Returns `true` if this number refers to the track at the specified
+    /// position on the specified medium of `release`.
+    ///
+    /// Numbers without a disc match both the number on the medium and the
+    /// continuous number, since files of releases with multiple mediums are
+    /// often numbered per medium without any indication of it.
+    pub fn refers_to(&self, release: &Release, medium: u16, track: u16) -> bool
+    {
+        match self.disc {
+            Some(disc) => disc == medium && self.track == track as u32,
+            None => {
+                self.track == track as u32 ||
+                    release.continuous_track_number(medium, track) == Some(self.track)
+            }
+        }
+    }
+}
+
+/// A file assigned to a track of a release by `match_files`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileMatch {
+    /// The index of the file in the list passed to `match_files`.
+    pub file: usize,
+
+    /// The position of the medium containing the track.
+    pub medium: u16,
+
+    /// The position of the track on its medium.
+    pub track: u16,
+
+    /// How well the file matches the track, between `0` and `1`.
+    pub confidence: f32,
+}
+
+/// The assignment of local files to the tracks of a release.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assignment {
+    /// The matched files, in the order of the tracks of the release.
+    pub matches: Vec<FileMatch>,
+
+    /// The indices of the files which couldn't be matched to any track.
+    pub unmatched_files: Vec<usize>,
+
+    /// The positions of the medium and the track of the tracks which no file
+    /// was matched to.
+    pub unmatched_tracks: Vec<(u16, u16)>,
+
+    /// How well the files match the release as a whole, between `0` and `1`.
+    ///
+    /// This is the mean confidence over all tracks of the release, with tracks
+    /// missing a file counting as `0`.
+    pub confidence: f32,
+}
+
+/// The lengths of a file and a track differing by at most this many seconds
+/// are considered equal.
+const LENGTH_TOLERANCE: u64 = 3;
+
+/// Files whose length differs by more than this many seconds from the one of
+/// a track are considered to be a different track.
+const LENGTH_MAX_DIFFERENCE: u64 = 15;
+
+/// Match local files to the tracks of a release using the track numbers and
+/// titles found in the file names, and the lengths of the files.
+///
+/// Each file is assigned to at most one track and each track gets at most one
+/// file. The best matching pairs are assigned first, files which match no
+/// track at all are left unmatched.
+pub fn match_files(files: &[LocalFile], release: &Release) -> Assignment
+{
+    let tracks = release.all_tracks();
+
+    let mut candidates = Vec::new();
+    for (f, file) in files.iter().enumerate() {
+        let stem = file_stem(&file.name);
+        let number = TrackNumber::parse(&file.name);
+        let name = normalize(stem);
+
+        for (t, &(medium, track)) in tracks.iter().enumerate() {
+            let mut score = 0f32;
+            let mut total = 0f32;
+
+            if let Some(number) = number {
+                total += 0.3;
+                if number.refers_to(release, medium, track.position) {
+                    score += 0.3;
+                }
+            }
+
+            let title = normalize(&track.title);
+            if !title.is_empty() {
+                total += 0.4;
+                if name.contains(&title) {
+                    score += 0.4;
+                }
+            }
+
+            if let (Some(file_length), Some(track_length)) = (file.length, track.length) {
+                let difference = if file_length > track_length {
+                    file_length - track_length
+                } else {
+                    track_length - file_length
+                };
+                if difference.as_secs() > LENGTH_MAX_DIFFERENCE {
+                    continue;
+                }
+                total += 0.3;
+                if difference.as_secs() <= LENGTH_TOLERANCE {
+                    score += 0.3;
+                } else {
+                    score += 0.15;
+                }
+            }
+
+            if score > 0. {
+                candidates.push((score / total, f, t));
+            }
+        }
+    }
+
+    // Stable sort, so equally good candidates keep the order of the files.
+    candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+
+    let mut file_done = vec![false; files.len()];
+    let mut track_file: Vec<Option<(usize, f32)>> = vec![None; tracks.len()];
+    for (confidence, f, t) in candidates {
+        if !file_done[f] && track_file[t].is_none() {
+            file_done[f] = true;
+            track_file[t] = Some((f, confidence));
+        }
+    }
+
+    let mut matches = Vec::new();
+    let mut unmatched_tracks = Vec::new();
+    for (t, &(medium, track)) in tracks.iter().enumerate() {
+        match track_file[t] {
+            Some((f, confidence)) => matches.push(FileMatch {
+                file: f,
+                medium: medium,
+                track: track.position,
+                confidence: confidence,
+            }),
+            None => unmatched_tracks.push((medium, track.position)),
+        }
+    }
+
+    let confidence = if tracks.is_empty() {
+        0.
+    } else {
+        matches.iter().map(|m| m.confidence).sum::<f32>() / tracks.len() as f32
+    };
+
+    Assignment {
+        matches: matches,
+        unmatched_files: (0..files.len()).filter(|&f| !file_done[f]).collect(),
+        unmatched_tracks: unmatched_tracks,
+        confidence: confidence,
+    }
+}
+
+/// The file name without the directories and the extension.
+fn file_stem(name: &str) -> &str
+{
+    let name = name.rsplit(|c: char| c == '/' || c == '\\').next().unwrap_or(name);
+    match name.rfind('.') {
+        Some(i) if i > 0 => &name[..i],
+        _ => name,
+    }
+}
+
+/// Splits the number at the beginning of the text from the rest, e. g. `5`
+/// and `" - Creep"` of `"05 - Creep"`.
+fn split_number(text: &str) -> Option<(u32, &str)>
+{
+    let end = text.find(|c: char| !c.is_digit(10)).unwrap_or(text.len());
+    text[..end].parse().ok().map(|number| (number, &text[end..]))
+}
+
+/// The number of the disc stored in a directory like `"CD2"` or `"Disc 2"`.
+fn disc_directory(name: &str) -> Option<u16>
+{
+    let name = name.trim().to_lowercase();
+    for prefix in &["cd", "disc", "disk"] {
+        if name.starts_with(prefix) {
+            let number = name[prefix.len()..].trim_left_matches(|c: char| c == ' ' || c == '_');
+            return number.parse().ok();
+        }
+    }
+    None
+}
+
+/// Lowercases the text and removes everything but letters and digits, so
+/// titles can be found in file names regardless of punctuation.
+fn normalize(text: &str) -> String
+{
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::FromXmlStr;
+
+    const XML: &'static str = r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#"><release id="ed118c5f-d940-4b52-a37b-b1a205374abe"><title>Creep</title><medium-list count="1"><medium><position>1</position><track-list count="3" offset="0"><track id="ac898be7-2965-4d17-9ac8-48d45852d73c"><position>1</position><number>1</number><title>Creep</title><length>238000</length><recording id="fd6f4cd8-9cff-43da-8cd7-3351357b6f5a"><title>Creep</title></recording></track><track id="21648b0b-deaf-4b93-a257-5fc18363b25d"><position>2</position><number>2</number><title>Lurgee</title><length>187000</length><recording id="0eeb0621-8013-4c0e-8e49-ddfd78d56051"><title>Lurgee</title></recording></track><track id="5e67884f-1c13-4aa5-bb1a-caa0dbeabe42"><position>3</position><number>3</number><title>Inside My Head</title><length>173000</length><recording id="9b5a3bcd-0b38-4c35-bd82-2ab4ee0b1b13"><title>Inside My Head</title></recording></track></track-list></medium></medium-list></release></metadata>"#;
+
+    fn file(name: &str, secs: u64) -> LocalFile
+    {
+        LocalFile {
+            name: name.to_string(),
+            length: Some(Duration::from_secs(secs)),
+        }
+    }
+
+    #[test]
+    fn match_album_directory()
+    {
+        let release = Release::from_xml_str(XML).unwrap();
+        let files = vec![
+            file("Radiohead/Creep/03 - Inside My Head.flac", 173),
+            file("Radiohead/Creep/01 - Creep.flac", 239),
+            file("Radiohead/Creep/02 - Lurgee.flac", 187),
+            file("Radiohead/Creep/cover.flac", 1200),
+        ];
+
+        let assignment = match_files(&files, &release);
+        assert_eq!(
+            assignment.matches.iter().map(|m| (m.file, m.track)).collect::<Vec<_>>(),
+            vec![(1, 1), (2, 2), (0, 3)]
+        );
+        assert!(assignment.matches.iter().all(|m| m.confidence == 1.));
+        assert_eq!(assignment.unmatched_files, vec![3]);
+        assert_eq!(assignment.unmatched_tracks, Vec::new());
+        assert_eq!(assignment.confidence, 1.);
+    }
+
+    #[test]
+    fn match_without_numbers()
+    {
+        let release = Release::from_xml_str(XML).unwrap();
+        let files = vec![file("lurgee.mp3", 195), file("creep.mp3", 238)];
+
+        let assignment = match_files(&files, &release);
+        assert_eq!(
+            assignment.matches.iter().map(|m| (m.file, m.track)).collect::<Vec<_>>(),
+            vec![(1, 1), (0, 2)]
+        );
+        assert!(assignment.matches[1].confidence < 1.);
+        assert_eq!(assignment.unmatched_tracks, vec![(1, 3)]);
+        assert!(assignment.confidence < 2. / 3.);
+    }
+
+    #[test]
+    fn file_names()
+    {
+        assert_eq!(file_stem("a/b/05 - Creep.flac"), "05 - Creep");
+        assert_eq!(file_stem("C:\\Music\\.hidden"), ".hidden");
+        assert_eq!(split_number("05 - Creep"), Some((5, " - Creep")));
+        assert_eq!(split_number("Creep"), None);
+        assert_eq!(normalize("Creep (Acoustic)"), "creepacoustic".to_string());
+    }
+
+    fn number(disc: Option<u16>, track: u32) -> Option<TrackNumber>
+    {
+        Some(TrackNumber {
+            disc: disc,
+            track: track,
+        })
+    }
+
+    #[test]
+    fn parse_track_numbers()
+    {
+        assert_eq!(TrackNumber::parse("05 - Creep.flac"), number(None, 5));
+        assert_eq!(TrackNumber::parse("Album/05. Creep.flac"), number(None, 5));
+        assert_eq!(TrackNumber::parse("2-05 Creep.flac"), number(Some(2), 5));
+        assert_eq!(TrackNumber::parse("2.05 - Creep.flac"), number(Some(2), 5));
+        assert_eq!(TrackNumber::parse("Album/CD2/05 - Creep.flac"), number(Some(2), 5));
+        assert_eq!(TrackNumber::parse("Album\\Disc 2\\05 - Creep.flac"), number(Some(2), 5));
+        assert_eq!(TrackNumber::parse("Album/Bonus/05 - Creep.flac"), number(None, 5));
+        assert_eq!(TrackNumber::parse("1985-Creep.flac"), number(None, 1985));
+        assert_eq!(TrackNumber::parse("Creep.flac"), None);
+    }
+
+    #[test]
+    fn resolve_track_numbers()
+    {
+        let release = Release::from_xml_str(XML).unwrap();
+
+        assert_eq!(number(None, 2).unwrap().resolve(&release), Some((1, 2)));
+        assert_eq!(number(Some(1), 3).unwrap().resolve(&release), Some((1, 3)));
+        assert_eq!(number(Some(2), 1).unwrap().resolve(&release), None);
+        assert_eq!(number(None, 4).unwrap().resolve(&release), None);
+
+        assert!(number(None, 2).unwrap().refers_to(&release, 1, 2));
+        assert!(!number(Some(2), 2).unwrap().refers_to(&release, 1, 2));
+    }
+}
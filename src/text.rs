@@ -0,0 +1,233 @@
+//! Helpers for working with free form text as it is found in tags of local
+//! files or entered by users, and for preparing it for use with the
+//! MusicBrainz database.
+
+use regex::Regex;
+
+use entities::ArtistRef;
+
+/// Split an artist credit string as it is commonly found in local files,
+/// e.g. `"A feat. B & C"`, into the names of the individual artists.
+///
+/// This is the inverse of rendering an artist credit: the common join phrases
+/// (`feat.`, `ft.`, `featuring`, `vs.`, `with`, `x`, `&`, `and` and `,`) are
+/// used to split the string. Since these phrases can also be part of an
+/// artist's name (think of "Simon & Garfunkel") the returned segments are only
+/// candidates, which are intended to seed multi-artist searches.
+///
+/// # Examples
+/// ```
+/// use musicbrainz::text::split_artist_credit;
+///
+/// assert_eq!(
+///     split_artist_credit("A feat. B & C"),
+///     vec!["A".to_string(), "B".to_string(), "C".to_string()]
+/// );
+/// ```
+pub fn split_artist_credit(credit: &str) -> Vec<String>
+{
+    let re = Regex::new(
+        r"(?i)\s+(?:feat\.?|ft\.?|featuring|vs\.?|versus|with|x|and)\s+|\s*(?:&|,)\s*",
+    ).unwrap();
+
+    re.split(credit)
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Configures how `render_artist_credit` renders an artist credit, e. g. to
+/// follow the conventions of a locale.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditStyle {
+    /// Whether artists are rendered with the name they are credited as, as
+    /// printed on the release, instead of their current name.
+    pub credited_names: bool,
+
+    /// Replaces the featuring join phrases (`feat.`, `ft.` and `featuring`),
+    /// e. g. with `"ft."` or `"con"`.
+    pub featuring: Option<String>,
+
+    /// Replaces the join phrases `&` and `and`, e. g. with `"und"` or `"et"`.
+    pub and: Option<String>,
+}
+
+impl Default for CreditStyle {
+    /// Renders the credit exactly as it is printed on the release.
+    fn default() -> Self
+    {
+        CreditStyle {
+            credited_names: true,
+            featuring: None,
+            and: None,
+        }
+    }
+}
+
+/// Render the artist credit of a release, release group, recording or track
+/// as a single string, e. g. `"A feat. B & C"`.
+///
+/// The join phrases are only replaced if they consist of nothing but one of
+/// the replaced phrases and whitespace, which is kept.
+///
+/// # Examples
+/// ```
+/// use musicbrainz::entities::Release;
+/// use musicbrainz::text::{render_artist_credit, CreditStyle};
+///
+/// fn album_artist(release: &Release) -> String
+/// {
+///     let style = CreditStyle {
+///         featuring: Some("ft.".to_string()),
+///         ..CreditStyle::default()
+///     };
+///     match release.artists {
+///         Some(ref artists) => render_artist_credit(artists, &style),
+///         None => String::new(),
+///     }
+/// }
+/// ```
+pub fn render_artist_credit(artists: &[ArtistRef], style: &CreditStyle) -> String
+{
+    let mut credit = String::new();
+    for artist in artists {
+        match artist.credited_as {
+            Some(ref credited) if style.credited_names => credit.push_str(credited),
+            _ => credit.push_str(&artist.name),
+        }
+        if let Some(ref phrase) = artist.join_phrase {
+            credit.push_str(&render_join_phrase(phrase, style));
+        }
+    }
+    credit
+}
+
+fn render_join_phrase(phrase: &str, style: &CreditStyle) -> String
+{
+    let token = phrase.trim();
+    let replacement = match token.to_lowercase().as_str() {
+        "feat." | "feat" | "ft." | "ft" | "featuring" => style.featuring.as_ref(),
+        "&" | "and" => style.and.as_ref(),
+        _ => None,
+    };
+
+    match replacement {
+        Some(replacement) => {
+            let start = phrase.len() - phrase.trim_left().len();
+            let end = start + token.len();
+            format!("{}{}{}", &phrase[..start], replacement, &phrase[end..])
+        }
+        None => phrase.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::Mbid;
+    use std::str::FromStr;
+
+    fn credited(name: &str, credited_as: Option<&str>, join_phrase: Option<&str>) -> ArtistRef
+    {
+        ArtistRef {
+            mbid: Mbid::from_str("650e7db6-b795-4eb5-a702-5ea2fc46c848").unwrap(),
+            name: name.to_string(),
+            sort_name: name.to_string(),
+            artist_type: None,
+            disambiguation: None,
+            aliases: Vec::new(),
+            credited_as: credited_as.map(|s| s.to_string()),
+            join_phrase: join_phrase.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn split_credit_join_phrases()
+    {
+        let expected = vec!["A".to_string(), "B".to_string()];
+        assert_eq!(split_artist_credit("A feat. B"), expected);
+        assert_eq!(split_artist_credit("A Feat B"), expected);
+        assert_eq!(split_artist_credit("A ft. B"), expected);
+        assert_eq!(split_artist_credit("A featuring B"), expected);
+        assert_eq!(split_artist_credit("A vs. B"), expected);
+        assert_eq!(split_artist_credit("A with B"), expected);
+        assert_eq!(split_artist_credit("A x B"), expected);
+        assert_eq!(split_artist_credit("A and B"), expected);
+        assert_eq!(split_artist_credit("A & B"), expected);
+        assert_eq!(split_artist_credit("A, B"), expected);
+    }
+
+    #[test]
+    fn split_credit_multiple()
+    {
+        assert_eq!(
+            split_artist_credit("Lady Gaga feat. Colby O'Donis, Akon & RedOne"),
+            vec![
+                "Lady Gaga".to_string(),
+                "Colby O'Donis".to_string(),
+                "Akon".to_string(),
+                "RedOne".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_credit_keeps_words_containing_phrases()
+    {
+        // Join phrases are only recognized as separate words.
+        assert_eq!(
+            split_artist_credit("Xandria"),
+            vec!["Xandria".to_string()]
+        );
+        assert_eq!(
+            split_artist_credit("Andrew Bird"),
+            vec!["Andrew Bird".to_string()]
+        );
+        assert_eq!(split_artist_credit("  "), Vec::<String>::new());
+    }
+
+    #[test]
+    fn render_credit()
+    {
+        let artists = vec![
+            credited("Lady Gaga", None, Some(" feat. ")),
+            credited("Colby O'Donis", Some("Colby O’Donis"), Some(" & ")),
+            credited("Akon", None, None),
+        ];
+
+        assert_eq!(
+            render_artist_credit(&artists, &CreditStyle::default()),
+            "Lady Gaga feat. Colby O’Donis & Akon".to_string()
+        );
+
+        let style = CreditStyle {
+            credited_names: false,
+            featuring: Some("mit".to_string()),
+            and: Some("und".to_string()),
+        };
+        assert_eq!(
+            render_artist_credit(&artists, &style),
+            "Lady Gaga mit Colby O'Donis und Akon".to_string()
+        );
+    }
+
+    #[test]
+    fn render_credit_keeps_other_join_phrases()
+    {
+        let artists = vec![
+            credited("Simon", None, Some(", ")),
+            credited("Garfunkel", None, Some(" vs. ")),
+            credited("Simon & Garfunkel", None, None),
+        ];
+        let style = CreditStyle {
+            credited_names: true,
+            featuring: Some("ft.".to_string()),
+            and: Some("et".to_string()),
+        };
+        assert_eq!(
+            render_artist_credit(&artists, &style),
+            "Simon, Garfunkel vs. Simon & Garfunkel".to_string()
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use xpath_reader::Context;
 
+/// The url of the web service of the main MusicBrainz server.
+pub const DEFAULT_BASE_URL: &'static str = "https://musicbrainz.org/ws/2";
+
 pub fn musicbrainz_context<'d>() -> Context<'d>
 {
     let mut context = Context::default();
@@ -7,7 +10,7 @@ pub fn musicbrainz_context<'d>() -> Context<'d>
     context
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "client"))]
 pub mod test_utils {
     use client::{Client, ClientConfig, ClientWaits};
     use entities::{Mbid, Resource};
@@ -22,6 +25,9 @@ pub mod test_utils {
                 user_agent: "MusicBrainz-Rust/Testing".to_string(),
                 max_retries: 5,
                 waits: ClientWaits::default(),
+                base_url: None,
+                accept_language: None,
+                max_response_size: None,
             },
             HttpClient::replay_file(format!(
                 "replay/test_entities/{}/{}.json",
@@ -33,5 +39,5 @@ pub mod test_utils {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "client"))]
 pub use self::test_utils::*;
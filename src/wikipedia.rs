@@ -0,0 +1,209 @@
+//! Short biographies of entities from Wikipedia, found by the Wikidata item
+//! an entity is linked to.
+//!
+//! This requires two additional requests to the Wikidata and the Wikipedia
+//! APIs, which are performed through the `Client` and thus also wait between
+//! requests.
+
+use reqwest_mock::Url;
+use serde_json::{self, Value};
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+
+use client::Client;
+use entities::{LinkService, Relation, RelationTarget};
+use errors::{ClientError, ClientErrorKind};
+
+/// The introduction of the Wikipedia article about an entity.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bio {
+    /// The title of the article.
+    pub title: String,
+
+    /// The language code of the Wikipedia the article is from, e. g. `"en"`.
+    pub language: String,
+
+    /// The first paragraph of the article as plain text.
+    pub extract: String,
+
+    /// The url of the article.
+    pub url: String,
+}
+
+/// The ID of the Wikidata item (e. g. `"Q44190"`) a url points to.
+pub fn wikidata_item(url: &str) -> Option<String>
+{
+    if LinkService::classify(url) != LinkService::Wikidata {
+        return None;
+    }
+    let id = url.trim_right_matches('/').rsplit('/').next().unwrap_or("");
+    if id.starts_with('Q') && id.len() > 1 && id[1..].chars().all(|c| c.is_digit(10)) {
+        Some(id.to_string())
+    } else {
+        None
+    }
+}
+
+/// Fetch the biography in the specified language (e. g. `"en"`) of the entity
+/// with the specified relationships.
+///
+/// Returns `None` if the entity isn't linked to a Wikidata item or if there is
+/// no article about the item in the language. Since the language is part of
+/// the host name of the Wikipedia, an error is returned if it contains other
+/// characters than ASCII letters and `-`.
+pub fn fetch_bio(
+    client: &mut Client,
+    relations: &[Relation],
+    language: &str,
+) -> Result<Option<Bio>, ClientError>
+{
+    if !is_language_code(language) {
+        return Err(ClientErrorKind::InvalidLanguage(language.to_string()).into());
+    }
+
+    let item = relations
+        .iter()
+        .filter_map(|r| match r.target {
+            RelationTarget::Url(ref url) => wikidata_item(url),
+            _ => None,
+        })
+        .next();
+    let item = match item {
+        Some(item) => item,
+        None => return Ok(None),
+    };
+
+    let url = format!("https://www.wikidata.org/wiki/Special:EntityData/{}.json", item);
+    let title = match sitelink_title(&get_json(client, url.parse()?)?, language) {
+        Some(title) => title,
+        None => return Ok(None),
+    };
+
+    let url = format!(
+        "https://{}.wikipedia.org/api/rest_v1/page/summary/{}",
+        language,
+        utf8_percent_encode(&title.replace(' ', "_"), PATH_SEGMENT_ENCODE_SET)
+    );
+    Ok(read_summary(&get_json(client, url.parse()?)?, language))
+}
+
+/// Whether `language` can be the language code of a Wikipedia, e. g. `"en"` or
+/// `"zh-yue"`.
+fn is_language_code(language: &str) -> bool
+{
+    !language.is_empty() && language.chars().all(|c| c.is_ascii_alphabetic() || c == '-')
+}
+
+/// Performs a request and parses the response as JSON.
+fn get_json(client: &mut Client, url: Url) -> Result<Value, ClientError>
+{
+    let url_string = url.to_string();
+    let body = client.get_body_checked(url, |_, _| Ok(()))?;
+    serde_json::from_str(&body).map_err(|_| {
+        ClientError::from(ClientErrorKind::UnexpectedContent {
+            content_type: None,
+            snippet: body.chars().take(200).collect(),
+        }).with_request(url_string, None)
+    })
+}
+
+/// The title of the article in the language from the response of the Wikidata
+/// entity data API.
+fn sitelink_title(entity_data: &Value, language: &str) -> Option<String>
+{
+    entity_data["entities"]
+        .as_object()
+        .and_then(|entities| entities.values().next())
+        .and_then(|entity| entity["sitelinks"][format!("{}wiki", language)]["title"].as_str())
+        .map(|title| title.to_string())
+}
+
+/// Reads the response of the Wikipedia page summary API.
+fn read_summary(summary: &Value, language: &str) -> Option<Bio>
+{
+    // Disambiguation pages don't describe the entity.
+    if summary["type"].as_str() == Some("disambiguation") {
+        return None;
+    }
+
+    match (
+        summary["title"].as_str(),
+        summary["extract"].as_str(),
+        summary["content_urls"]["desktop"]["page"].as_str(),
+    ) {
+        (Some(title), Some(extract), Some(url)) => Some(Bio {
+            title: title.to_string(),
+            language: summary["lang"].as_str().unwrap_or(language).to_string(),
+            extract: extract.to_string(),
+            url: url.to_string(),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use client::ClientConfig;
+
+    #[test]
+    fn wikidata_items()
+    {
+        assert_eq!(
+            wikidata_item("https://www.wikidata.org/wiki/Q44190"),
+            Some("Q44190".to_string())
+        );
+        assert_eq!(wikidata_item("https://www.wikidata.org/wiki/Special:Random"), None);
+        assert_eq!(wikidata_item("https://example.com/wiki/Q44190"), None);
+    }
+
+    #[test]
+    fn language_codes()
+    {
+        assert!(is_language_code("en"));
+        assert!(is_language_code("zh-yue"));
+        assert!(!is_language_code(""));
+        assert!(!is_language_code("en.example.com/"));
+        assert!(!is_language_code("de?"));
+
+        // The language is checked before anything else is done.
+        let mut client = Client::new(ClientConfig::public("MusicBrainz-Rust/Testing"));
+        match fetch_bio(&mut client, &[], "en.example.com/").unwrap_err().kind() {
+            &ClientErrorKind::InvalidLanguage(ref code) => assert_eq!(code, "en.example.com/"),
+            kind => panic!("unexpected error: {}", kind),
+        }
+    }
+
+    #[test]
+    fn read_sitelink_title()
+    {
+        let data = serde_json::from_str(
+            r#"{"entities":{"Q44190":{"id":"Q44190","sitelinks":{"enwiki":{"site":"enwiki","title":"Radiohead"},"jawiki":{"site":"jawiki","title":"レディオヘッド"}}}}}"#,
+        ).unwrap();
+        assert_eq!(sitelink_title(&data, "ja"), Some("レディオヘッド".to_string()));
+        assert_eq!(sitelink_title(&data, "de"), None);
+    }
+
+    #[test]
+    fn read_page_summary()
+    {
+        let summary = serde_json::from_str(
+            r#"{"type":"standard","title":"Radiohead","lang":"en","extract":"Radiohead are an English rock band formed in Abingdon, Oxfordshire, in 1985.","content_urls":{"desktop":{"page":"https://en.wikipedia.org/wiki/Radiohead"}}}"#,
+        ).unwrap();
+        assert_eq!(
+            read_summary(&summary, "en"),
+            Some(Bio {
+                title: "Radiohead".to_string(),
+                language: "en".to_string(),
+                extract: "Radiohead are an English rock band formed in Abingdon, Oxfordshire, \
+                          in 1985."
+                    .to_string(),
+                url: "https://en.wikipedia.org/wiki/Radiohead".to_string(),
+            })
+        );
+
+        let disambiguation = serde_json::from_str(
+            r#"{"type":"disambiguation","title":"Creep","extract":"Creep may refer to:","content_urls":{"desktop":{"page":"https://en.wikipedia.org/wiki/Creep"}}}"#,
+        ).unwrap();
+        assert_eq!(read_summary(&disambiguation, "en"), None);
+    }
+}
@@ -0,0 +1,662 @@
+//! Golden tests parsing the real responses in `tests/fixtures` and asserting
+//! the complete parsed structures.
+//!
+//! The fixtures are stored as `tests/fixtures/<entity>/<mbid>.xml`. When adding
+//! a new fixture also add it to `parse_all_fixtures`, so at least parsing it is
+//! checked even if there is no golden test for it yet.
+
+extern crate musicbrainz;
+extern crate xpath_reader;
+
+use musicbrainz::entities::*;
+use std::fs::File;
+use std::io::Read;
+use std::str::FromStr;
+use xpath_reader::{Context, XpathReader, XpathStrReader};
+use xpath_reader::reader::FromXmlContained;
+
+fn context<'d>() -> Context<'d>
+{
+    let mut context = Context::default();
+    context.set_namespace("mb", "http://musicbrainz.org/ns/mmd-2.0#");
+    context
+}
+
+fn read_fixture(entity: &str, mbid: &str) -> String
+{
+    let path = format!("tests/fixtures/{}/{}.xml", entity, mbid);
+    let mut xml = String::new();
+    File::open(&path)
+        .and_then(|mut f| f.read_to_string(&mut xml))
+        .expect(path.as_str());
+    xml
+}
+
+fn fixture<E: Resource + FromXmlContained>(mbid: &str) -> E
+{
+    let xml = read_fixture(E::get_name(), mbid);
+    let context = context();
+    let reader = XpathStrReader::new(xml.as_str(), &context).unwrap();
+    match E::from_xml(&reader) {
+        Ok(entity) => entity,
+        Err(e) => panic!("failed parsing {}/{}: {}", E::get_name(), mbid, e),
+    }
+}
+
+/// The relationships of the entity in the fixture, in document order.
+fn fixture_relations<E: Resource>(mbid: &str) -> Vec<Relation>
+{
+    let xml = read_fixture(E::get_name(), mbid);
+    let context = context();
+    let reader = XpathStrReader::new(xml.as_str(), &context).unwrap();
+    reader.read_vec("//mb:relation-list/mb:relation").unwrap()
+}
+
+fn mbid(s: &str) -> Mbid
+{
+    Mbid::from_str(s).unwrap()
+}
+
+#[test]
+fn parse_all_fixtures()
+{
+    fixture::<Area>("2db42837-c832-3c27-b4a3-08198f75693c");
+    fixture::<Area>("a1411661-be21-4290-8dc1-50f3d8e3ea67");
+    fixture::<Artist>("650e7db6-b795-4eb5-a702-5ea2fc46c848");
+    fixture::<Artist>("90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e");
+    fixture::<Artist>("a74b1b7f-71a5-4011-9441-d0b5e4122711");
+    fixture::<Event>("6e2ab7d5-f340-4c41-99a3-c901733402b4");
+    fixture::<Event>("9754f4dd-6fad-49b7-8f30-940c9af6b776");
+    fixture::<Label>("168f48c8-057e-4974-9600-aa9956d21e1a");
+    fixture::<Label>("c029628b-6633-439e-bcee-ed02e8a338f7");
+    fixture::<Place>("d1ab65f8-d082-492a-bd70-ce375548dabf");
+    fixture::<Recording>("fbe3d0b9-3990-4a76-bddb-12f4a0447a2c");
+    fixture::<ReleaseGroup>("76a4e2c2-bf7a-445e-8081-5a1e291f3b16");
+    fixture::<Release>("02173013-59ed-4229-b0a5-e5aa486ed5d7");
+    fixture::<Release>("61f8b05f-a3b5-49f4-a3a6-8f0d564c1664");
+    fixture::<Release>("785d7c67-a920-4cee-a871-8cd9896eb8aa");
+    fixture::<Release>("ce22b20d-3a45-4e47-abaa-b7c8d10281fa");
+    fixture::<Release>("d1881a4c-0188-4f0f-a2e7-4e7849aec109");
+    fixture::<Release>("ed118c5f-d940-4b52-a37b-b1a205374abe");
+    fixture::<Work>("a4f0b5fb-8e05-3b4c-8a5d-a8ba1f8e3ba4");
+    fixture::<Work>("b1df2cf3-69a9-3bc0-be44-f71e79b27a22");
+}
+
+#[test]
+fn golden_area_country()
+{
+    let area: Area = fixture("2db42837-c832-3c27-b4a3-08198f75693c");
+    assert_eq!(
+        area,
+        Area {
+            mbid: mbid("2db42837-c832-3c27-b4a3-08198f75693c"),
+            name: "Japan".to_string(),
+            sort_name: "Japan".to_string(),
+            area_type: AreaType::Country,
+            iso_3166: Some("JP".to_string()),
+            disambiguation: None,
+        }
+    );
+}
+
+#[test]
+fn golden_event_with_setlist()
+{
+    let event: Event = fixture("9754f4dd-6fad-49b7-8f30-940c9af6b776");
+    assert_eq!(
+        event,
+        Event {
+            mbid: mbid("9754f4dd-6fad-49b7-8f30-940c9af6b776"),
+            name: "Lady Gaga at Roseland Ballroom".to_string(),
+            // The fixture doesn't contain an alias list.
+            aliases: None,
+            event_type: Some(EventType::Concert),
+            setlist: Some(
+                "* \"Born This Way\" (Piano Version)* \"Black Jesus + Amen Fashion\"* \
+                 \"Monster\"* \"Bad Romance\"* \"Sexxx Dreams\"* \"Dope\"* \"You and I\"* \
+                 \"Just Dance\"* \"Poker Face\" (Piano Version)* \"Artpop\" (Interlude)* \
+                 \"Applause\"* \"G.U.Y.\""
+                    .to_string(),
+            ),
+            begin_date: Some(PartialDate::new(Some(2014), Some(3), Some(28))),
+            end_date: Some(PartialDate::new(Some(2014), Some(3), Some(28))),
+            // The life span has an end date but isn't marked as ended.
+            ended: false,
+            time: None,
+            disambiguation: None,
+            annotation: None,
+        }
+    );
+}
+
+#[test]
+fn golden_label_with_aliases()
+{
+    let label: Label = fixture("168f48c8-057e-4974-9600-aa9956d21e1a");
+    assert_eq!(
+        label,
+        Label {
+            mbid: mbid("168f48c8-057e-4974-9600-aa9956d21e1a"),
+            name: "avex trax".to_string(),
+            sort_name: "avex trax".to_string(),
+            disambiguation: None,
+            aliases: Some(vec![
+                "Avex Trax Japan".to_string(),
+                "エイベックス・トラックス".to_string(),
+            ]),
+            label_code: None,
+            label_type: Some(LabelType::ProductionOriginal),
+            country: Some("JP".to_string()),
+            ipi_code: None,
+            isni_code: None,
+            begin_date: Some(PartialDate::new(Some(1990), Some(9), None)),
+            end_date: None,
+            ended: false,
+        }
+    );
+}
+
+#[test]
+fn golden_place()
+{
+    let place: Place = fixture("d1ab65f8-d082-492a-bd70-ce375548dabf");
+    assert_eq!(
+        place,
+        Place {
+            mbid: mbid("d1ab65f8-d082-492a-bd70-ce375548dabf"),
+            name: "Chipping Norton Recording Studios".to_string(),
+            place_type: Some(PlaceType::Studio),
+            address: Some("28–30 New Street, Chipping Norton".to_string()),
+            coordinates: Some(Coordinates {
+                latitude: "51.9414".to_string(),
+                longitude: "-1.548".to_string(),
+            }),
+            area: Some(AreaRef {
+                mbid: mbid("716234d3-b8ed-45ac-8983-e7219eb85956"),
+                name: "Chipping Norton".to_string(),
+                sort_name: "Chipping Norton".to_string(),
+                area_type: None,
+                iso_3166: None,
+                disambiguation: None,
+            }),
+            begin: Some(PartialDate::new(Some(1971), None, None)),
+            end: Some(PartialDate::new(Some(1999), Some(10), None)),
+            ended: true,
+            aliases: None,
+            disambiguation: None,
+            annotation: None,
+        }
+    );
+}
+
+#[test]
+fn golden_multi_disc_release()
+{
+    let release: Release = fixture("ce22b20d-3a45-4e47-abaa-b7c8d10281fa");
+
+    let mediums = release.mediums.as_ref().unwrap();
+    let tracks_per_medium: Vec<(u16, usize)> = mediums
+        .iter()
+        .map(|medium| (medium.position, medium.tracks.as_ref().unwrap().len()))
+        .collect();
+    assert_eq!(tracks_per_medium, vec![(1, 11), (2, 9)]);
+    assert_eq!(release.track_count(), 20);
+
+    // Track positions restart on every medium.
+    for medium in mediums.iter() {
+        let tracks = medium.tracks.as_ref().unwrap();
+        let positions: Vec<u16> = tracks.iter().map(|t| t.position).collect();
+        let expected: Vec<u16> = (1..tracks.len() as u16 + 1).collect();
+        assert_eq!(positions, expected);
+    }
+}
+
+#[test]
+fn golden_area_city()
+{
+    let area: Area = fixture("a1411661-be21-4290-8dc1-50f3d8e3ea67");
+    assert_eq!(
+        area,
+        Area {
+            mbid: mbid("a1411661-be21-4290-8dc1-50f3d8e3ea67"),
+            name: "Honolulu".to_string(),
+            sort_name: "Honolulu".to_string(),
+            area_type: AreaType::City,
+            iso_3166: None,
+            disambiguation: None,
+        }
+    );
+}
+
+#[test]
+fn golden_artist_with_aliases()
+{
+    let artist: Artist = fixture("650e7db6-b795-4eb5-a702-5ea2fc46c848");
+    assert_eq!(
+        artist,
+        Artist {
+            mbid: mbid("650e7db6-b795-4eb5-a702-5ea2fc46c848"),
+            name: "Lady Gaga".to_string(),
+            sort_name: "Lady Gaga".to_string(),
+            aliases: Some(vec![
+                Alias {
+                    name: "Lady Ga Ga".to_string(),
+                    sort_name: Some("Lady Ga Ga".to_string()),
+                    locale: None,
+                    primary: false,
+                    alias_type: None,
+                },
+                Alias {
+                    name: "Stefani Joanne Angelina Germanotta".to_string(),
+                    sort_name: Some("Germanotta, Stefani Joanne Angelina".to_string()),
+                    locale: None,
+                    primary: false,
+                    alias_type: Some("Legal name".to_string()),
+                },
+            ]),
+            annotation: None,
+            disambiguation: None,
+            artist_type: Some(ArtistType::Person),
+            gender: Some(Gender::Female),
+            area: Some(AreaRef {
+                mbid: mbid("489ce91b-6658-3307-9877-795b68554c98"),
+                name: "United States".to_string(),
+                sort_name: "United States".to_string(),
+                area_type: None,
+                iso_3166: Some("US".to_string()),
+                disambiguation: None,
+            }),
+            begin_date: Some(PartialDate::new(Some(1986), Some(3), Some(28))),
+            end_date: None,
+            ended: false,
+            ipi_code: Some("00519338344".to_string()),
+            isni_code: Some("0000000120254559".to_string()),
+            tags: None,
+        }
+    );
+}
+
+#[test]
+fn golden_artist_group()
+{
+    let artist: Artist = fixture("90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e");
+    assert_eq!(artist.name, "NECRONOMIDOL".to_string());
+    assert_eq!(artist.artist_type, Some(ArtistType::Group));
+    assert_eq!(artist.gender, None);
+    assert_eq!(artist.area.map(|a| a.name), Some("Japan".to_string()));
+    assert_eq!(artist.begin_date, Some(PartialDate::new(Some(2014), Some(3), None)));
+    assert_eq!(artist.end_date, None);
+    // The fixture was requested without aliases.
+    assert_eq!(artist.aliases, None);
+}
+
+#[test]
+fn golden_artist_relations()
+{
+    let artist: Artist = fixture("a74b1b7f-71a5-4011-9441-d0b5e4122711");
+    assert_eq!(artist.name, "Radiohead".to_string());
+    assert_eq!(artist.artist_type, Some(ArtistType::Group));
+    assert_eq!(artist.begin_date, Some(PartialDate::new(Some(1991), None, None)));
+
+    let relations = fixture_relations::<Artist>("a74b1b7f-71a5-4011-9441-d0b5e4122711");
+    assert_eq!(relations.len(), 8);
+
+    let members: Vec<&str> = relations
+        .iter()
+        .filter(|r| r.is(&RelType::MemberOf) && r.direction == Direction::Backward)
+        .filter_map(|r| match r.target {
+            RelationTarget::Artist(ref artist) => Some(artist.name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(members, vec!["Thom Yorke", "Jonny Greenwood", "Philip Selway"]);
+    assert_eq!(relations[0].begin, Some(PartialDate::new(Some(1985), None, None)));
+    assert_eq!(
+        relations[1].attributes,
+        vec![
+            RelationAttribute {
+                name: "original".to_string(),
+                value: None,
+            },
+            RelationAttribute {
+                name: "guitar".to_string(),
+                value: None,
+            },
+        ]
+    );
+
+    let collaboration = &relations[3];
+    assert!(collaboration.is(&RelType::Collaboration));
+    assert_eq!(collaboration.direction, Direction::Forward);
+    assert_eq!(collaboration.end, Some(PartialDate::new(Some(2006), None, None)));
+    assert!(collaboration.ended);
+
+    let links = external_links(&relations);
+    assert_eq!(
+        links.get(&LinkService::OfficialSite),
+        Some(&vec!["https://www.radiohead.com/".to_string()])
+    );
+    assert_eq!(
+        links.get(&LinkService::Wikidata),
+        Some(&vec!["https://www.wikidata.org/wiki/Q44190".to_string()])
+    );
+    assert_eq!(
+        links.get(&LinkService::Discogs),
+        Some(&vec!["https://www.discogs.com/artist/3840".to_string()])
+    );
+    assert_eq!(
+        links.get(&LinkService::Other),
+        Some(&vec!["https://twitter.com/radiohead".to_string()])
+    );
+}
+
+#[test]
+fn golden_event_with_annotation()
+{
+    let event: Event = fixture("6e2ab7d5-f340-4c41-99a3-c901733402b4");
+    assert_eq!(event.name, "25. Wave-Gotik-Treffen".to_string());
+    assert_eq!(event.event_type, Some(EventType::Festival));
+    assert_eq!(event.aliases, Some(vec!["WGT 2016".to_string()]));
+    assert_eq!(event.begin_date, Some(PartialDate::new(Some(2016), Some(5), Some(13))));
+    assert_eq!(event.end_date, Some(PartialDate::new(Some(2016), Some(5), Some(16))));
+    assert_eq!(event.setlist, None);
+    let annotation = event.annotation.unwrap();
+    assert!(annotation.starts_with("Announced but not in the list below:"));
+    assert!(annotation.ends_with("WOLFRAM KUHNT (D)"));
+}
+
+#[test]
+fn golden_label_with_code()
+{
+    let label: Label = fixture("c029628b-6633-439e-bcee-ed02e8a338f7");
+    assert_eq!(
+        label,
+        Label {
+            mbid: mbid("c029628b-6633-439e-bcee-ed02e8a338f7"),
+            name: "EMI".to_string(),
+            sort_name: "EMI".to_string(),
+            disambiguation: Some("EMI Records, since 1972".to_string()),
+            aliases: Some(vec![
+                "EMI".to_string(),
+                "EMI Records (UK)".to_string(),
+                "EMI Records Ltd".to_string(),
+                "EMI UK".to_string(),
+            ]),
+            label_code: Some("542".to_string()),
+            label_type: Some(LabelType::ProductionOriginal),
+            country: Some("GB".to_string()),
+            ipi_code: None,
+            isni_code: None,
+            begin_date: Some(PartialDate::new(Some(1972), None, None)),
+            end_date: None,
+            ended: false,
+        }
+    );
+}
+
+#[test]
+fn golden_recording()
+{
+    let recording: Recording = fixture("fbe3d0b9-3990-4a76-bddb-12f4a0447a2c");
+    assert_eq!(recording.title, "The Perfect Drug (Nine Inch Nails)".to_string());
+    assert_eq!(recording.duration, Some(Duration::from_millis(499000)));
+    assert_eq!(recording.isrc_code.as_ref().map(|i| i.as_str()), Some("USIR19701296"));
+    let artists = recording.artists.unwrap();
+    assert_eq!(artists.len(), 1);
+    assert_eq!(artists[0].mbid, mbid("b7ffd2af-418f-4be2-bdd1-22f8b48613da"));
+    assert_eq!(artists[0].name, "Nine Inch Nails".to_string());
+    assert_eq!(recording.disambiguation, None);
+    assert_eq!(recording.annotation, None);
+}
+
+#[test]
+fn golden_release_group()
+{
+    let group: ReleaseGroup = fixture("76a4e2c2-bf7a-445e-8081-5a1e291f3b16");
+    assert_eq!(group.title, "Mixtape".to_string());
+    assert_eq!(
+        group.release_type,
+        ReleaseGroupType {
+            primary: Some(ReleaseGroupPrimaryType::Album),
+            secondary: vec![ReleaseGroupSecondaryType::MixtapeStreet],
+        }
+    );
+    let artists = group.artists.as_ref().unwrap();
+    assert_eq!(artists[0].name, "POP ETC".to_string());
+    let releases = group.releases.as_ref().unwrap();
+    assert_eq!(releases.len(), 1);
+    assert_eq!(releases[0].mbid, mbid("289bf4e7-0af5-433c-b5a2-493b863b4b47"));
+    assert_eq!(releases[0].date, Some(PartialDate::new(Some(2012), Some(3), None)));
+    assert_eq!(releases[0].country, Some("US".to_string()));
+    assert_eq!(releases[0].status, Some(ReleaseStatus::Official));
+}
+
+/// The basic data shared by the golden release tests.
+fn assert_release(
+    release: &Release,
+    title: &str,
+    date: &str,
+    country: &str,
+    language: &str,
+    track_titles: &[&str],
+)
+{
+    assert_eq!(release.title, title.to_string());
+    assert_eq!(release.date, Some(PartialDate::from_str(date).unwrap()));
+    assert_eq!(release.country, Some(country.to_string()));
+    assert_eq!(release.status, Some(ReleaseStatus::Official));
+    assert_eq!(release.language, Some(Language::from_639_3(language).unwrap()));
+    let titles: Vec<&str> = release.all_tracks().iter().map(|t| t.1.title.as_str()).collect();
+    assert_eq!(titles, track_titles);
+}
+
+#[test]
+fn golden_release_single()
+{
+    let release: Release = fixture("02173013-59ed-4229-b0a5-e5aa486ed5d7");
+    assert_release(
+        &release,
+        "蜃気楼 第二章",
+        "2004-06-09",
+        "JP",
+        "jpn",
+        &[
+            "蜃気楼 第二章",
+            "ファンファーレ",
+            "蜃気楼 第二章 (Instrumental)",
+            "ファンファーレ (Instrumental)",
+        ],
+    );
+    assert_eq!(release.script, Some("Jpan".to_string()));
+    assert_eq!(release.barcode, Some("4988009031156".to_string()));
+    assert!(!release.cover_art);
+    assert!(release.all_tracks().iter().all(|t| t.1.length.is_none()));
+    assert!(!release.total_length().complete);
+
+    let artists = release.artists.as_ref().unwrap();
+    assert_eq!(artists[0].name, "童子-T".to_string());
+    assert!(artists[0].is_known_as("竹末充"));
+    let labels = release.labels.as_ref().unwrap();
+    assert_eq!(labels[0].catalog_number, Some("SRCL-6190".to_string()));
+    assert_eq!(labels[0].label.as_ref().map(|l| l.name.as_str()), Some("Atomic Bomb"));
+}
+
+#[test]
+fn golden_release_without_label()
+{
+    let release: Release = fixture("61f8b05f-a3b5-49f4-a3a6-8f0d564c1664");
+    assert_release(
+        &release,
+        "Love Somebody EP",
+        "2014-09-07",
+        "XW",
+        "eng",
+        &[
+            "Love Somebody",
+            "Low",
+            "Love Somebody (Turtle remix)",
+            "Love Somebody (Field Kit remix)",
+        ],
+    );
+    assert_eq!(release.script, None);
+    assert_eq!(release.packaging, Some("None".to_string()));
+    assert_eq!(release.barcode, Some("5065002081001".to_string()));
+    assert!(release.cover_art);
+    assert_eq!(
+        release.labels,
+        Some(vec![
+            LabelInfo {
+                label: None,
+                catalog_number: Some("BIRD 4".to_string()),
+            },
+        ])
+    );
+}
+
+#[test]
+fn golden_release_many_labels()
+{
+    let release: Release = fixture("785d7c67-a920-4cee-a871-8cd9896eb8aa");
+    assert_eq!(release.title, "The Fame".to_string());
+    assert_eq!(release.date, Some(PartialDate::new(Some(2008), Some(8), Some(19))));
+    assert_eq!(release.country, Some("CA".to_string()));
+    assert_eq!(release.packaging, Some("Jewel Case".to_string()));
+    assert_eq!(release.barcode, Some("602517664890".to_string()));
+    assert_eq!(release.track_count(), 12);
+
+    let tracks = release.all_tracks();
+    assert_eq!(tracks[0].1.title, "Just Dance".to_string());
+    assert_eq!(tracks[0].1.length, Some(Duration::from_millis(241000)));
+    assert_eq!(tracks[1].1.title, "LoveGame".to_string());
+
+    let labels: Vec<&str> = release
+        .labels
+        .as_ref()
+        .unwrap()
+        .iter()
+        .filter_map(|info| info.label.as_ref().map(|l| l.name.as_str()))
+        .collect();
+    assert_eq!(
+        labels,
+        vec![
+            "Cherrytree Records",
+            "Interscope Records",
+            "Konlive",
+            "Streamline Records",
+            "Universal Music Canada",
+        ]
+    );
+    assert!(release.label_info_by_catalog_number("0251766489").is_some());
+}
+
+#[test]
+fn golden_release_single_medium()
+{
+    let release: Release = fixture("d1881a4c-0188-4f0f-a2e7-4e7849aec109");
+    assert_release(
+        &release,
+        "EXITIUM",
+        "2015-10-04",
+        "JP",
+        "jpn",
+        &["puella tenebrarum", "LAMINA MALEDICTUM", "SARNATH"],
+    );
+    assert_eq!(release.barcode, None);
+    assert_eq!(
+        release.total_length(),
+        TotalLength {
+            length: Duration::from_millis(718000),
+            complete: true,
+        }
+    );
+    let labels = release.labels.as_ref().unwrap();
+    assert_eq!(labels[0].catalog_number, None);
+    assert_eq!(labels[0].label.as_ref().map(|l| l.name.as_str()), Some("VELOCITRON"));
+}
+
+#[test]
+fn golden_release_with_label_code()
+{
+    let release: Release = fixture("ed118c5f-d940-4b52-a37b-b1a205374abe");
+    assert_release(
+        &release,
+        "Creep",
+        "1992-09-21",
+        "GB",
+        "eng",
+        &["Creep", "Lurgee", "Inside My Head", "Million Dollar Question"],
+    );
+    assert_eq!(release.barcode, Some("724388023429".to_string()));
+    assert_eq!(release.all_tracks()[0].1.length, Some(Duration::from_millis(237933)));
+    let labels = release.labels.as_ref().unwrap();
+    assert_eq!(labels[0].catalog_number, Some("CDR 6078".to_string()));
+    let label = labels[0].label.as_ref().unwrap();
+    assert_eq!(label.name, "Parlophone".to_string());
+    assert_eq!(label.label_code, Some("299".to_string()));
+}
+
+#[test]
+fn golden_work_relations()
+{
+    let work: Work = fixture("a4f0b5fb-8e05-3b4c-8a5d-a8ba1f8e3ba4");
+    assert_eq!(
+        work,
+        Work {
+            mbid: mbid("a4f0b5fb-8e05-3b4c-8a5d-a8ba1f8e3ba4"),
+            title: "Paranoid Android".to_string(),
+            work_type: Some("Song".to_string()),
+            languages: vec![Language::from_639_3("eng").unwrap()],
+            instrumental: false,
+            iswcs: vec!["T-010.168.394-4".to_string()],
+            aliases: Some(vec![
+                Alias {
+                    name: "Paranoid Android (Rain Down)".to_string(),
+                    sort_name: Some("Paranoid Android (Rain Down)".to_string()),
+                    locale: None,
+                    primary: false,
+                    alias_type: None,
+                },
+            ]),
+            disambiguation: None,
+            annotation: None,
+        }
+    );
+
+    let relations = fixture_relations::<Work>("a4f0b5fb-8e05-3b4c-8a5d-a8ba1f8e3ba4");
+    assert_eq!(relations.len(), 4);
+    assert!(relations[0].is(&RelType::Composer));
+    assert!(relations[1].is(&RelType::Lyricist));
+    match relations[1].target {
+        RelationTarget::Artist(ref artist) => assert_eq!(artist.name, "Thom Yorke".to_string()),
+        ref target => panic!("unexpected target: {:?}", target),
+    }
+
+    let performances: Vec<&Relation> = relations
+        .iter()
+        .filter(|r| r.relation_type == "performance")
+        .collect();
+    assert_eq!(performances.len(), 2);
+    assert!(performances.iter().all(|r| r.direction == Direction::Backward));
+    match performances[1].target {
+        RelationTarget::Recording(ref recording) => {
+            assert_eq!(recording.mbid, mbid("c9d3fd2c-1e4e-4ba4-b9c8-7ed7fb2a4f7c"));
+            assert_eq!(recording.length, Some(Duration::from_millis(411000)));
+        }
+        ref target => panic!("unexpected target: {:?}", target),
+    }
+    assert_eq!(performances[1].attributes[0].name, "live".to_string());
+    assert!(performances[1].ended);
+}
+
+#[test]
+fn golden_work_instrumental()
+{
+    let work: Work = fixture("b1df2cf3-69a9-3bc0-be44-f71e79b27a22");
+    assert_eq!(work.title, "Interlude".to_string());
+    assert_eq!(work.disambiguation, Some("instrumental".to_string()));
+    assert!(work.languages.is_empty());
+    assert!(work.instrumental);
+    assert_eq!(work.lyrics_language(), None);
+    assert!(work.iswcs.is_empty());
+    assert_eq!(work.aliases, None);
+}
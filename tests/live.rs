@@ -0,0 +1,155 @@
+//! Tests performing real requests against a MusicBrainz server, validating the
+//! parsers against live data.
+//!
+//! These tests are ignored by default, run them using:
+//!
+//! ```sh
+//! cargo test --test live -- --ignored
+//! ```
+//!
+//! By default test.musicbrainz.org is used. With the `mirror` feature enabled
+//! the tests are run against the server at `MUSICBRAINZ_MIRROR_URL` instead
+//! (defaulting to `http://localhost:5000/ws/2`).
+
+#![cfg(feature = "client")]
+
+extern crate musicbrainz;
+extern crate xpath_reader;
+
+use musicbrainz::client::{Client, ClientConfig};
+use musicbrainz::entities::*;
+use musicbrainz::search::SearchBuilder;
+use std::str::FromStr;
+use xpath_reader::reader::FromXmlContained;
+
+const USER_AGENT: &'static str =
+    "MusicBrainz-Rust/Testing ( https://github.com/leoschwarz/musicbrainz_rust )";
+
+#[cfg(not(feature = "mirror"))]
+fn config() -> ClientConfig
+{
+    ClientConfig::test_server(USER_AGENT)
+}
+
+#[cfg(feature = "mirror")]
+fn config() -> ClientConfig
+{
+    let base_url = ::std::env::var("MUSICBRAINZ_MIRROR_URL")
+        .unwrap_or_else(|_| "http://localhost:5000/ws/2".to_string());
+    ClientConfig::mirror(USER_AGENT, &base_url)
+}
+
+fn client() -> Client
+{
+    Client::new(config())
+}
+
+fn lookup<E: Resource + FromXmlContained>(mbid: &str) -> E
+{
+    match client().get_by_mbid(&Mbid::from_str(mbid).unwrap()) {
+        Ok(entity) => entity,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+#[test]
+#[ignore]
+fn lookup_area()
+{
+    let area: Area = lookup("a1411661-be21-4290-8dc1-50f3d8e3ea67");
+    assert_eq!(area.name, "Honolulu".to_string());
+}
+
+#[test]
+#[ignore]
+fn lookup_artist()
+{
+    let artist: Artist = lookup("90e7c2f9-273b-4d6c-a662-ab2d73ea4b8e");
+    assert_eq!(artist.name, "NECRONOMIDOL".to_string());
+}
+
+#[test]
+#[ignore]
+fn lookup_event()
+{
+    let event: Event = lookup("6e2ab7d5-f340-4c41-99a3-c901733402b4");
+    assert_eq!(event.event_type, Some(EventType::Festival));
+}
+
+#[test]
+#[ignore]
+fn lookup_label()
+{
+    let label: Label = lookup("c029628b-6633-439e-bcee-ed02e8a338f7");
+    assert_eq!(label.name, "EMI".to_string());
+}
+
+#[test]
+#[ignore]
+fn lookup_place()
+{
+    let place: Place = lookup("d1ab65f8-d082-492a-bd70-ce375548dabf");
+    assert_eq!(place.name, "Chipping Norton Recording Studios".to_string());
+}
+
+#[test]
+#[ignore]
+fn lookup_recording()
+{
+    let recording: Recording = lookup("fbe3d0b9-3990-4a76-bddb-12f4a0447a2c");
+    assert_eq!(recording.title, "The Perfect Drug (Nine Inch Nails)".to_string());
+}
+
+#[test]
+#[ignore]
+fn lookup_release()
+{
+    let release: Release = lookup("ed118c5f-d940-4b52-a37b-b1a205374abe");
+    assert_eq!(release.title, "Creep".to_string());
+}
+
+#[test]
+#[ignore]
+fn lookup_release_group()
+{
+    let release_group: ReleaseGroup = lookup("76a4e2c2-bf7a-445e-8081-5a1e291f3b16");
+    assert_eq!(release_group.title, "Mixtape".to_string());
+}
+
+#[test]
+#[ignore]
+fn search_area()
+{
+    use musicbrainz::search::fields::area::AreaName;
+
+    let results = client().search_area().add(AreaName("Honolulu".to_string())).search().unwrap();
+    assert!(!results.is_empty());
+}
+
+#[test]
+#[ignore]
+fn search_artist()
+{
+    use musicbrainz::search::fields::artist::ArtistName;
+
+    let results = client()
+        .search_artist()
+        .add(ArtistName("Radiohead".to_string()))
+        .search()
+        .unwrap();
+    assert!(!results.is_empty());
+}
+
+#[test]
+#[ignore]
+fn search_release_group()
+{
+    use musicbrainz::search::fields::release_group::ReleaseGroupName;
+
+    let results = client()
+        .search_release_group()
+        .add(ReleaseGroupName("霊魂消滅".to_string()))
+        .search()
+        .unwrap();
+    assert!(!results.is_empty());
+}
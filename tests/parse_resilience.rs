@@ -0,0 +1,181 @@
+//! Property tests parsing documents in which the optional elements of an
+//! entity are present, missing or empty in all combinations, or which are cut
+//! off at an arbitrary position.
+//!
+//! Real records of the database have all kinds of gaps, the parsers must
+//! never panic on these but return either the entity or an error describing
+//! what's wrong.
+
+extern crate musicbrainz;
+#[macro_use]
+extern crate proptest;
+
+use musicbrainz::entities::*;
+
+const RELEASE_MBID: &'static str = "61f8b05f-a3b5-49f4-a3a6-8f0d564c1664";
+
+/// The optional children of the release, in document order.
+const RELEASE_PARTS: &'static [&'static str] = &[
+    r#"<status id="4e304316-386d-3409-af2e-78857eec5cfe">Official</status>"#,
+    r#"<packaging id="119eba76-b343-3e02-a292-f0f00644bb9b">None</packaging>"#,
+    r#"<text-representation><language>eng</language><script>Latn</script></text-representation>"#,
+    r#"<artist-credit><name-credit><artist id="29042cf5-0583-45a6-b77c-ba2601008691"><name>Robyn Sherwell</name><sort-name>Sherwell, Robyn</sort-name></artist></name-credit></artist-credit>"#,
+    r#"<date>2014-09-07</date>"#,
+    r#"<country>XW</country>"#,
+    r#"<release-event-list count="1"><release-event><date>2014-09-07</date><area id="525d4e18-3d00-31b9-a58b-a146a916de8f"><name>[Worldwide]</name><sort-name>[Worldwide]</sort-name></area></release-event></release-event-list>"#,
+    r#"<barcode>5065002081001</barcode>"#,
+    r#"<disambiguation>EP</disambiguation>"#,
+    r#"<annotation><text>Self-released.</text></annotation>"#,
+    r#"<cover-art-archive><artwork>true</artwork></cover-art-archive>"#,
+    r#"<label-info-list count="1"><label-info><catalog-number>BIRD 4</catalog-number></label-info></label-info-list>"#,
+    r#"<medium-list count="1"><medium><position>1</position><format>CD</format><track-list count="1" offset="0"><track id="0d0fea83-07c3-47b2-80ea-7d2dc1099e49"><position>1</position><number>1</number><length>249000</length><recording id="3a2de788-a07f-450f-baaf-b5e71182cd7c"><title>Love Somebody</title><length>249000</length></recording></track></track-list></medium></medium-list>"#,
+];
+
+const ARTIST_MBID: &'static str = "650e7db6-b795-4eb5-a702-5ea2fc46c848";
+
+/// The optional children of the artist, in document order.
+const ARTIST_PARTS: &'static [&'static str] = &[
+    r#"<disambiguation>US singer</disambiguation>"#,
+    r#"<ipi>00519338344</ipi>"#,
+    r#"<isni-list><isni>0000000120254559</isni></isni-list>"#,
+    r#"<gender id="93452b5a-a947-30c8-934f-6a4056b151c2">Female</gender>"#,
+    r#"<area id="489ce91b-6658-3307-9877-795b68554c98"><name>United States</name><sort-name>United States</sort-name></area>"#,
+    r#"<life-span><begin>1986-03-28</begin><end>2050</end></life-span>"#,
+    r#"<alias-list count="1"><alias sort-name="Lady Ga Ga">Lady Ga Ga</alias></alias-list>"#,
+    r#"<annotation>Born in New York City.</annotation>"#,
+];
+
+/// The element of a part without any content, e. g. `<date/>`.
+fn empty_element(part: &str) -> String
+{
+    let name: String = part[1..]
+        .chars()
+        .take_while(|c| *c != ' ' && *c != '>')
+        .collect();
+    format!("<{}/>", name)
+}
+
+/// Assembles the document, including the part `i` if bit `i` of `present` is
+/// set, and emptying it if bit `i` of `empty` is set as well.
+fn document(entity: &str, head: &str, parts: &[&str], present: u32, empty: u32) -> String
+{
+    let mut xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><metadata xmlns="http://musicbrainz.org/ns/mmd-2.0#">{}"#,
+        head
+    );
+    for (i, part) in parts.iter().enumerate() {
+        if present & (1 << i) != 0 {
+            if empty & (1 << i) != 0 {
+                xml.push_str(&empty_element(part));
+            } else {
+                xml.push_str(part);
+            }
+        }
+    }
+    xml.push_str(&format!("</{}></metadata>", entity));
+    xml
+}
+
+fn release_xml(present: u32, empty: u32) -> String
+{
+    let head = format!(r#"<release id="{}"><title>Love Somebody EP</title>"#, RELEASE_MBID);
+    document("release", &head, RELEASE_PARTS, present, empty)
+}
+
+fn artist_xml(present: u32, empty: u32) -> String
+{
+    let head = format!(
+        r#"<artist type="Person" id="{}"><name>Lady Gaga</name><sort-name>Lady Gaga</sort-name>"#,
+        ARTIST_MBID
+    );
+    document("artist", &head, ARTIST_PARTS, present, empty)
+}
+
+/// Parsing either succeeds or fails with a message, but never panics.
+fn assert_parsed_or_described<E: FromXmlStr>(xml: &str)
+{
+    if let Err(e) = E::from_xml_str(xml) {
+        assert!(!e.to_string().is_empty(), "empty error message for {}", xml);
+    }
+}
+
+proptest! {
+    #[test]
+    fn release_with_missing_parts(present in 0u32..(1 << 13))
+    {
+        let xml = release_xml(present, 0);
+        let release = Release::from_xml_str(&xml).expect(&xml);
+        let has = |i: u32| present & (1 << i) != 0;
+
+        assert_eq!(release.mbid.to_string(), RELEASE_MBID);
+        assert_eq!(release.status.is_some(), has(0));
+        assert_eq!(release.language.is_some(), has(2));
+        assert_eq!(release.artists.is_some(), has(3));
+        assert_eq!(release.date.is_some(), has(4));
+        assert_eq!(release.barcode.is_some(), has(7));
+        assert_eq!(release.cover_art, has(10));
+        assert_eq!(release.labels.is_some(), has(11));
+        assert_eq!(release.mediums.map(|m| m.len()), if has(12) { Some(1) } else { None });
+    }
+
+    #[test]
+    fn release_with_empty_parts(present in 0u32..(1 << 13), empty in 0u32..(1 << 13))
+    {
+        assert_parsed_or_described::<Release>(&release_xml(present, empty));
+    }
+
+    #[test]
+    fn release_cut_off(present in 0u32..(1 << 13), cut in 0usize..4096)
+    {
+        let xml = release_xml(present, 0);
+        let cut = cut % xml.len();
+        assert!(Release::from_xml_str(&xml[..cut]).is_err());
+    }
+
+    #[test]
+    fn artist_with_missing_parts(present in 0u32..(1 << 8))
+    {
+        let xml = artist_xml(present, 0);
+        let artist = Artist::from_xml_str(&xml).expect(&xml);
+        let has = |i: u32| present & (1 << i) != 0;
+
+        assert_eq!(artist.mbid.to_string(), ARTIST_MBID);
+        assert_eq!(artist.disambiguation.is_some(), has(0));
+        assert_eq!(artist.ipi_code.is_some(), has(1));
+        assert_eq!(artist.isni_code.is_some(), has(2));
+        assert_eq!(artist.gender.is_some(), has(3));
+        assert_eq!(artist.area.is_some(), has(4));
+        assert_eq!(artist.begin_date.is_some(), has(5));
+        assert_eq!(artist.aliases.is_some(), has(6));
+        assert_eq!(artist.annotation.is_some(), has(7));
+    }
+
+    #[test]
+    fn artist_with_empty_parts(present in 0u32..(1 << 8), empty in 0u32..(1 << 8))
+    {
+        assert_parsed_or_described::<Artist>(&artist_xml(present, empty));
+    }
+
+    #[test]
+    fn artist_cut_off(present in 0u32..(1 << 8), cut in 0usize..2048)
+    {
+        let xml = artist_xml(present, 0);
+        let cut = cut % xml.len();
+        assert!(Artist::from_xml_str(&xml[..cut]).is_err());
+    }
+}
+
+#[test]
+fn missing_required_elements()
+{
+    let all = (1 << RELEASE_PARTS.len()) - 1;
+    let xml = release_xml(all, 0);
+    assert!(Release::from_xml_str(&xml).is_ok());
+
+    let without_title = xml.replace("<title>Love Somebody EP</title>", "");
+    let without_id = xml.replace(&format!(r#"<release id="{}">"#, RELEASE_MBID), "<release>");
+    for xml in &[without_title, without_id] {
+        let err = Release::from_xml_str(xml).err().expect(xml);
+        assert!(!err.to_string().is_empty());
+    }
+}